@@ -0,0 +1,41 @@
+#ifndef WINCOMPATLIB_H
+#define WINCOMPATLIB_H
+
+/*
+ * C interface of wincompatlib, built with "ffi" feature:
+ * cargo rustc --release --features ffi --crate-type cdylib
+ *
+ * Define WINCOMPATLIB_DXVK if the library is built with "dxvk" feature as well
+ */
+
+#include <stddef.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct WincompatRunner WincompatRunner;
+
+/* Returns NULL on error. prefix can be NULL to use the default one */
+WincompatRunner *wincompat_runner_new(const char *binary, const char *prefix);
+void wincompat_runner_free(WincompatRunner *runner);
+
+/* Functions below return -1 on error, see wincompat_last_error */
+int wincompat_runner_create_prefix(const WincompatRunner *runner);
+
+/* Returns exit code of the process. args can be NULL */
+int wincompat_runner_run(const WincompatRunner *runner, const char *exe, const char *const *args, size_t args_len);
+
+#ifdef WINCOMPATLIB_DXVK
+int wincompat_runner_install_dxvk(const WincompatRunner *runner, const char *dxvk_folder);
+#endif
+
+/* Returns NULL if there were no errors in the current thread. Free with wincompat_string_free */
+char *wincompat_last_error(void);
+void wincompat_string_free(char *string);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,149 @@
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use wincompatlib::prelude::*;
+
+const USAGE: &str = "Usage: wincompat [--wine <binary>] [--prefix <path>] [--arch <win32|win64>] <command>
+
+Commands:
+    version                  Print version of the wine binary
+    create-prefix            Create (or update) the wine prefix
+    install-dxvk <folder>    Install DXVK from the given folder to the prefix
+    run <exe> [args...]      Run executable in the prefix and wait until it exits
+    list-runners <folder>    List wine builds stored in the given folder
+
+Wine binary defaults to $WINE or \"wine\", and prefix to $WINEPREFIX or ~/.wine";
+
+struct PrintWarnings;
+
+impl EventSink for PrintWarnings {
+    fn on_warning(&self, message: &str) {
+        eprintln!("warning: {message}");
+    }
+}
+
+fn list_runners(folder: PathBuf) -> std::io::Result<()> {
+    let mut entries = folder.read_dir()?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.join("bin/wine").exists())
+        .collect::<Vec<_>>();
+
+    entries.sort();
+
+    for path in entries {
+        let version = Wine::from_binary(path.join("bin/wine"))
+            .version()
+            .map(|version| version.to_string_lossy().trim().to_string())
+            .unwrap_or_else(|_| String::from("unknown version"));
+
+        println!("{}\t{version}", path.display());
+    }
+
+    Ok(())
+}
+
+fn execute(wine: Wine, command: &str, args: Vec<String>) -> std::io::Result<ExitCode> {
+    match (command, args.as_slice()) {
+        ("version", []) => {
+            println!("{}", wine.version()?.to_string_lossy().trim());
+        }
+
+        ("create-prefix", []) => {
+            wine.update_prefix(wine.prefix_path())?;
+        }
+
+        ("install-dxvk", [folder]) => {
+            wine.install_dxvk(folder, InstallParams::default())?;
+        }
+
+        ("run", [_, ..]) => {
+            // Inherits stdio of the current process
+            let status = wine.to_command(&args[0], &args[1..])?.status()?;
+
+            match wincompatlib::wine::ExitCode::from_status(status) {
+                Some(code) if code.is_success() => (),
+
+                Some(code) => {
+                    eprintln!("Process exited with {code}");
+
+                    // Windows status codes don't fit into the unix ones
+                    return Ok(u8::try_from(code.code())
+                        .ok()
+                        .filter(|code| *code != 0)
+                        .map(ExitCode::from)
+                        .unwrap_or(ExitCode::FAILURE));
+                }
+
+                None => {
+                    eprintln!("Process was killed by signal");
+
+                    return Ok(ExitCode::FAILURE);
+                }
+            }
+        }
+
+        ("list-runners", [folder]) => list_runners(PathBuf::from(folder))?,
+
+        _ => {
+            eprintln!("{USAGE}");
+
+            return Ok(ExitCode::from(2));
+        }
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+fn main() -> ExitCode {
+    let mut binary = PathBuf::from(std::env::var_os("WINE").unwrap_or_else(|| "wine".into()));
+    let mut prefix = default_prefix();
+    let mut arch = None;
+
+    let mut args = std::env::args().skip(1);
+
+    let command = loop {
+        match args.next().as_deref() {
+            Some("--wine") => match args.next() {
+                Some(value) => binary = PathBuf::from(value),
+                None => break None
+            }
+
+            Some("--prefix") => match args.next() {
+                Some(value) => prefix = PathBuf::from(value),
+                None => break None
+            }
+
+            Some("--arch") => match args.next().as_deref().and_then(WineArch::from_str) {
+                Some(value) => arch = Some(value),
+                None => break None
+            }
+
+            Some("-h" | "--help") | None => break None,
+
+            Some(command) => break Some(command.to_string())
+        }
+    };
+
+    let Some(command) = command else {
+        eprintln!("{USAGE}");
+
+        return ExitCode::from(2);
+    };
+
+    let mut wine = Wine::from_binary(binary)
+        .with_prefix(prefix)
+        .with_event_sink(PrintWarnings);
+
+    wine.arch = arch;
+
+    match execute(wine, &command, args.collect()) {
+        Ok(code) => code,
+
+        Err(err) => {
+            eprintln!("error: {err}");
+
+            ExitCode::FAILURE
+        }
+    }
+}
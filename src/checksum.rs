@@ -0,0 +1,31 @@
+/// CRC-32 (ISO-HDLC) checksum used by PNG and ZIP formats
+pub fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = 0xFFFFFFFFu32;
+
+    for byte in bytes {
+        crc ^= *byte as u32;
+
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xEDB88320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+
+    !crc
+}
+
+/// Adler-32 checksum used by zlib streams
+pub fn adler32(bytes: &[u8]) -> u32 {
+    let mut a = 1u32;
+    let mut b = 0u32;
+
+    for byte in bytes {
+        a = (a + *byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+
+    (b << 16) | a
+}
@@ -0,0 +1,261 @@
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::io::{Error, ErrorKind, Result};
+
+use serde::{Serialize, Deserialize};
+
+use crate::wine::WineVersion;
+
+/// Compatibility table shipped with the library
+///
+/// Contains only well documented requirements, more entries can be loaded
+/// with `CompatTable::fetch` or `CompatTable::load`
+pub const EMBEDDED_TABLE: &str = r#"
+[[entries]]
+dxvk = ">=2.0"
+wine = "<7.1"
+severity = "broken"
+reason = "DXVK 2.0 and newer require Wine 7.1 or newer"
+link = "https://github.com/doitsujin/dxvk/releases/tag/v2.0"
+
+[[entries]]
+dxvk = ">=2.0"
+driver = "nvidia"
+driver_version = "<510.47"
+severity = "broken"
+reason = "DXVK 2.0 and newer require Vulkan 1.3 which is supported since Nvidia 510.47 driver"
+link = "https://github.com/doitsujin/dxvk/releases/tag/v2.0"
+
+[[entries]]
+dxvk = ">=2.0"
+driver = "mesa"
+driver_version = "<22.0"
+severity = "broken"
+reason = "DXVK 2.0 and newer require Vulkan 1.3 which is supported since Mesa 22.0"
+link = "https://github.com/doitsujin/dxvk/releases/tag/v2.0"
+"#;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Combination works with known problems
+    #[default]
+    Warning,
+
+    /// Combination doesn't work at all
+    Broken
+}
+
+/// Graphics driver used by the game
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Driver {
+    /// Lowercase driver name, e.g. `nvidia`, `mesa` or `amdvlk`
+    pub name: String,
+
+    /// Driver version, e.g. `535.54.03`
+    pub version: String
+}
+
+impl Driver {
+    pub fn new<N: AsRef<str>, V: Into<String>>(name: N, version: V) -> Self {
+        Self {
+            name: name.as_ref().to_ascii_lowercase(),
+            version: version.into()
+        }
+    }
+}
+
+/// Known-bad combination of wine, DXVK and graphics driver versions
+///
+/// Versions are specified as comma separated requirements,
+/// e.g. `">=2.0, <2.3"`. Unspecified fields match anything
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CompatEntry {
+    pub wine: Option<String>,
+    pub dxvk: Option<String>,
+
+    /// Lowercase driver name, see `Driver::name`
+    pub driver: Option<String>,
+    pub driver_version: Option<String>,
+
+    pub severity: Severity,
+    pub reason: String,
+
+    /// URL of the issue or release notes describing the problem
+    pub link: Option<String>
+}
+
+/// Get numeric components of the version string, see `WineVersion::parse`,
+/// e.g. `[8, 0]` for `wine-8.0 (Staging)` or `[2, 3, 1]` for `v2.3.1`
+#[inline]
+fn parse_version(version: &str) -> Option<Vec<u64>> {
+    WineVersion::parse(version).ok().map(|version| version.components)
+}
+
+/// Check if version satisfies all the comma separated requirements
+fn matches_requirements(requirements: &str, version: &str) -> bool {
+    let Some(mut version) = parse_version(version) else {
+        return false;
+    };
+
+    requirements.split(',').all(|requirement| {
+        let requirement = requirement.trim();
+
+        let (operator, required) = [">=", "<=", "==", ">", "<", "="].into_iter()
+            .find_map(|operator| requirement.strip_prefix(operator).map(|required| (operator, required)))
+            .unwrap_or(("=", requirement));
+
+        let Some(mut required) = parse_version(required) else {
+            return false;
+        };
+
+        // 8.0 and 8 are the same versions
+        let len = version.len().max(required.len());
+
+        version.resize(len, 0);
+        required.resize(len, 0);
+
+        match operator {
+            ">=" => version >= required,
+            "<=" => version <= required,
+            ">"  => version > required,
+            "<"  => version < required,
+
+            _ => version == required
+        }
+    })
+}
+
+impl CompatEntry {
+    /// Check if entry describes given combination
+    ///
+    /// Entry which mentions DXVK or driver doesn't match if they're not specified
+    pub fn matches(&self, wine_version: &str, dxvk_version: Option<&str>, driver: Option<&Driver>) -> bool {
+        if let Some(wine) = &self.wine {
+            if !matches_requirements(wine, wine_version) {
+                return false;
+            }
+        }
+
+        if let Some(dxvk) = &self.dxvk {
+            if !dxvk_version.map(|version| matches_requirements(dxvk, version)).unwrap_or(false) {
+                return false;
+            }
+        }
+
+        if self.driver.is_some() || self.driver_version.is_some() {
+            let Some(driver) = driver else {
+                return false;
+            };
+
+            if self.driver.as_ref().map(|name| !name.eq_ignore_ascii_case(&driver.name)).unwrap_or(false) {
+                return false;
+            }
+
+            if self.driver_version.as_ref().map(|version| !matches_requirements(version, &driver.version)).unwrap_or(false) {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+/// Table of known-bad combinations
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CompatTable {
+    pub entries: Vec<CompatEntry>
+}
+
+impl CompatTable {
+    /// Get table shipped with the library
+    pub fn embedded() -> Self {
+        Self::parse(EMBEDDED_TABLE).expect("Embedded compatibility table must be valid")
+    }
+
+    /// Parse table from the TOML string
+    pub fn parse(table: &str) -> Result<Self> {
+        toml::from_str(table).map_err(|err| Error::new(ErrorKind::InvalidData, err))
+    }
+
+    /// Load table from the TOML file
+    pub fn load<T: AsRef<Path>>(path: T) -> Result<Self> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    /// Save table to the TOML file, e.g. to cache the downloaded one
+    pub fn save<T: AsRef<Path>>(&self, path: T) -> Result<()> {
+        let table = toml::to_string_pretty(self)
+            .map_err(|err| Error::new(ErrorKind::InvalidData, err))?;
+
+        std::fs::write(path, table)
+    }
+
+    /// Download table in the TOML format using `curl`
+    ///
+    /// ```no_run
+    /// use wincompatlib::compat::*;
+    ///
+    /// let table = CompatTable::fetch("https://example.com/wincompatlib/compat.toml")
+    ///     .unwrap_or_else(|_| CompatTable::embedded());
+    ///
+    /// for entry in table.check("wine-7.0", Some("2.3"), None) {
+    ///     eprintln!("{:?}: {}", entry.severity, entry.reason);
+    /// }
+    /// ```
+    pub fn fetch<T: AsRef<str>>(url: T) -> Result<Self> {
+        let output = Command::new("curl")
+            .args(["-L", "-f", "-s", "-S"])
+            .arg(url.as_ref())
+            .stdin(Stdio::null())
+            .output()?;
+
+        if !output.status.success() {
+            return Err(Error::other(format!("Failed to download compatibility table: {}", String::from_utf8_lossy(&output.stderr).trim())));
+        }
+
+        Self::parse(&String::from_utf8_lossy(&output.stdout))
+    }
+
+    /// Add entries of another table, e.g. the downloaded one to the embedded one
+    pub fn extend(&mut self, table: CompatTable) {
+        for entry in table.entries {
+            if !self.entries.contains(&entry) {
+                self.entries.push(entry);
+            }
+        }
+    }
+
+    /// Find entries describing given combination, most severe first
+    pub fn check(&self, wine_version: &str, dxvk_version: Option<&str>, driver: Option<&Driver>) -> Vec<&CompatEntry> {
+        let mut entries = self.entries.iter()
+            .filter(|entry| entry.matches(wine_version, dxvk_version, driver))
+            .collect::<Vec<_>>();
+
+        entries.sort_by_key(|entry| std::cmp::Reverse(entry.severity));
+
+        entries
+    }
+}
+
+/// Find known problems of the wine, DXVK and driver combination using the embedded table
+///
+/// ```
+/// use wincompatlib::compat::{self, Driver, Severity};
+///
+/// let issues = compat::check("wine-8.0", Some("2.3"), Some(&Driver::new("nvidia", "470.199.02")));
+///
+/// assert_eq!(issues.len(), 1);
+/// assert_eq!(issues[0].severity, Severity::Broken);
+///
+/// assert!(compat::check("wine-8.0", Some("2.3"), Some(&Driver::new("nvidia", "535.54.03"))).is_empty());
+/// ```
+pub fn check(wine_version: &str, dxvk_version: Option<&str>, driver: Option<&Driver>) -> Vec<CompatEntry> {
+    CompatTable::embedded()
+        .check(wine_version, dxvk_version, driver)
+        .into_iter()
+        .cloned()
+        .collect()
+}
@@ -0,0 +1,269 @@
+use std::ffi::{OsStr, OsString};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::io::Result;
+
+use crate::wine::Wine;
+
+/// Get folder with user's desktop entries: `$XDG_DATA_HOME/applications` or `$HOME/.local/share/applications`
+pub fn applications_dir() -> PathBuf {
+    crate::xdg::data_home().join("applications")
+}
+
+/// Quote argument of the `Exec` key according to the desktop entry specification
+fn quote_exec_arg(arg: &OsStr) -> String {
+    const RESERVED: &[char] = &[' ', '\t', '\n', '"', '\'', '\\', '>', '<', '~', '|', '&', ';', '$', '*', '?', '#', '(', ')', '`'];
+
+    let arg = arg.to_string_lossy().replace('%', "%%");
+
+    if !arg.is_empty() && !arg.contains(RESERVED) {
+        return arg;
+    }
+
+    let mut quoted = String::from("\"");
+
+    for char in arg.chars() {
+        if matches!(char, '"' | '`' | '$' | '\\') {
+            quoted.push('\\');
+        }
+
+        quoted.push(char);
+    }
+
+    quoted.push('"');
+
+    quoted
+}
+
+/// Escape value of string keys according to the desktop entry specification
+fn escape_value(value: &str) -> String {
+    value.replace('\\', "\\\\")
+        .replace('\n', "\\n")
+        .replace('\t', "\\t")
+        .replace('\r', "\\r")
+}
+
+/// Make command line from the command with given environment
+fn exec_from_parts(command: &Command, mut envs: Vec<(&OsStr, Option<&OsStr>)>) -> Vec<OsString> {
+    let mut exec = Vec::new();
+
+    envs.sort();
+
+    if !envs.is_empty() {
+        exec.push(OsString::from("env"));
+
+        // `env` stops parsing options at the first assignment
+        for (key, value) in &envs {
+            if value.is_none() {
+                exec.push(OsString::from("-u"));
+                exec.push(key.to_os_string());
+            }
+        }
+
+        for (key, value) in envs {
+            if let Some(value) = value {
+                let mut var = key.to_os_string();
+
+                var.push("=");
+                var.push(value);
+
+                exec.push(var);
+            }
+        }
+    }
+
+    exec.push(command.get_program().to_os_string());
+    exec.extend(command.get_args().map(OsStr::to_os_string));
+
+    exec
+}
+
+/// Freedesktop launcher file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DesktopEntry {
+    pub name: String,
+    pub comment: Option<String>,
+
+    /// Command line to execute
+    pub exec: Vec<OsString>,
+
+    /// Working directory of the command
+    pub path: Option<PathBuf>,
+
+    pub icon: Option<PathBuf>,
+    pub categories: Vec<String>,
+
+    /// Used by desktop environments to associate windows with the launcher
+    pub startup_wm_class: Option<String>
+}
+
+impl DesktopEntry {
+    pub fn new<T: Into<String>>(name: T, exec: Vec<OsString>) -> Self {
+        Self {
+            name: name.into(),
+            comment: None,
+            exec,
+            path: None,
+            icon: None,
+            categories: Vec::new(),
+            startup_wm_class: None
+        }
+    }
+
+    /// Make command line from the command, setting its environment using `env` program
+    ///
+    /// ```
+    /// use std::process::Command;
+    ///
+    /// use wincompatlib::prelude::*;
+    ///
+    /// let mut command = Command::new("game");
+    ///
+    /// command.env("MANGOHUD", "1").env_remove("DXVK_HUD");
+    ///
+    /// assert_eq!(DesktopEntry::exec_from_command(&command), ["env", "-u", "DXVK_HUD", "MANGOHUD=1", "game"]);
+    /// ```
+    pub fn exec_from_command(command: &Command) -> Vec<OsString> {
+        exec_from_parts(command, command.get_envs().collect())
+    }
+
+    /// Get desktop file id which is used as its file name
+    ///
+    /// ```
+    /// use wincompatlib::prelude::*;
+    ///
+    /// let entry = DesktopEntry::new("My Game: Remastered", vec![]);
+    ///
+    /// assert_eq!(entry.file_id(), "wincompatlib-my-game-remastered");
+    /// ```
+    pub fn file_id(&self) -> String {
+        let mut id = String::from("wincompatlib");
+
+        for word in self.name.split(|char: char| !char.is_alphanumeric()).filter(|word| !word.is_empty()) {
+            id.push('-');
+            id.push_str(&word.to_lowercase());
+        }
+
+        id
+    }
+
+    /// Write desktop file to the given path
+    ///
+    /// File is readable by the current user only since the command can contain private values
+    pub fn write<T: AsRef<Path>>(&self, path: T) -> Result<()> {
+        use std::io::Write;
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+
+        let mut file = std::fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .mode(0o600)
+            .open(path)?;
+
+        // Mode is not applied to already existing files
+        file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+
+        file.write_all(self.to_string().as_bytes())
+    }
+
+    /// Install desktop file to the user's applications folder
+    ///
+    /// Returns path to the installed file
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// let path = Wine::default()
+    ///     .with_prefix("/path/to/prefix")
+    ///     .desktop_entry("My Game", "/path/to/prefix/drive_c/Games/MyGame/game.exe")
+    ///     .install()
+    ///     .expect("Failed to install desktop file");
+    ///
+    /// println!("Desktop file installed to {:?}", path);
+    /// ```
+    pub fn install(&self) -> Result<PathBuf> {
+        let folder = applications_dir();
+
+        std::fs::create_dir_all(&folder)?;
+
+        let path = folder.join(format!("{}.desktop", self.file_id()));
+
+        self.write(&path)?;
+
+        Ok(path)
+    }
+}
+
+impl std::fmt::Display for DesktopEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "[Desktop Entry]")?;
+        writeln!(f, "Type=Application")?;
+        writeln!(f, "Name={}", escape_value(&self.name))?;
+
+        if let Some(comment) = &self.comment {
+            writeln!(f, "Comment={}", escape_value(comment))?;
+        }
+
+        let exec = self.exec.iter()
+            .map(|arg| quote_exec_arg(arg))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        writeln!(f, "Exec={}", escape_value(&exec))?;
+
+        if let Some(path) = &self.path {
+            writeln!(f, "Path={}", escape_value(&path.to_string_lossy()))?;
+        }
+
+        if let Some(icon) = &self.icon {
+            writeln!(f, "Icon={}", escape_value(&icon.to_string_lossy()))?;
+        }
+
+        if !self.categories.is_empty() {
+            writeln!(f, "Categories={};", self.categories.join(";"))?;
+        }
+
+        if let Some(class) = &self.startup_wm_class {
+            writeln!(f, "StartupWMClass={}", escape_value(class))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Wine {
+    /// Make desktop entry running the executable with this struct's configuration
+    ///
+    /// Variables marked as secret or containing secrets are not written to the entry,
+    /// see `WineWithExt::with_secret_env` and `WineWithExt::with_secret`
+    ///
+    /// ```
+    /// use wincompatlib::prelude::*;
+    ///
+    /// let entry = Wine::from_binary("/path/to/wine")
+    ///     .with_prefix("/path/to/prefix")
+    ///     .desktop_entry("My Game", "/path/to/My Game/game.exe");
+    ///
+    /// assert!(entry.to_string().contains("Exec=env WINEPREFIX=/path/to/prefix /path/to/wine \"/path/to/My Game/game.exe\""));
+    /// ```
+    pub fn desktop_entry<N: Into<String>, E: AsRef<Path>>(&self, name: N, exe: E) -> DesktopEntry {
+        let exe = exe.as_ref();
+
+        let command = self.command([exe]);
+
+        let envs = command.get_envs()
+            .filter(|(key, value)| value.is_none_or(|value| self.events.redact_env(key, value) == value))
+            .collect();
+
+        let mut entry = DesktopEntry::new(name, exec_from_parts(&command, envs));
+
+        entry.path = exe.parent().map(Path::to_path_buf);
+        entry.categories = vec![String::from("Game")];
+
+        entry.startup_wm_class = exe.file_name()
+            .map(|name| name.to_string_lossy().to_string());
+
+        entry
+    }
+}
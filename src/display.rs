@@ -0,0 +1,284 @@
+use std::process::Command;
+use std::io::{Error, ErrorKind, Result};
+
+/// Program used to query and change display modes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DisplayBackend {
+    /// `xrandr` for X11 sessions (and Xwayland)
+    Xrandr,
+
+    /// `wlr-randr` for wlroots based wayland compositors
+    WlrRandr
+}
+
+impl DisplayBackend {
+    /// Get backend of the current session
+    ///
+    /// `wlr-randr` is used for wayland sessions if it's installed, `xrandr` otherwise
+    pub fn detect() -> Option<Self> {
+        let installed = |binary: &str| {
+            Command::new(binary)
+                .arg("--help")
+                .output()
+                .is_ok()
+        };
+
+        if std::env::var_os("WAYLAND_DISPLAY").is_some() && installed("wlr-randr") {
+            Some(Self::WlrRandr)
+        }
+
+        else if std::env::var_os("DISPLAY").is_some() && installed("xrandr") {
+            Some(Self::Xrandr)
+        }
+
+        else {
+            None
+        }
+    }
+}
+
+/// Current mode of the display output
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutputMode {
+    /// Output name, e.g. `HDMI-1`
+    pub output: String,
+
+    pub width: u32,
+    pub height: u32,
+
+    /// Refresh rate in Hz
+    pub refresh: Option<f32>
+}
+
+/// Modes of all the enabled display outputs
+///
+/// ```no_run
+/// use wincompatlib::prelude::*;
+///
+/// let modes = DisplayModes::query().expect("Failed to query display modes");
+///
+/// for mode in &modes.outputs {
+///     println!("{}: {}x{}", mode.output, mode.width, mode.height);
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct DisplayModes {
+    pub backend: DisplayBackend,
+    pub outputs: Vec<OutputMode>
+}
+
+fn parse_size(size: &str) -> Option<(u32, u32)> {
+    let (width, height) = size.split_once('x')?;
+
+    Some((width.parse().ok()?, height.parse().ok()?))
+}
+
+impl DisplayModes {
+    /// Query modes using the detected backend
+    ///
+    /// Returns `ErrorKind::Unsupported` error if neither `xrandr` nor `wlr-randr` can be used
+    pub fn query() -> Result<Self> {
+        let Some(backend) = DisplayBackend::detect() else {
+            return Err(Error::new(ErrorKind::Unsupported, "Neither xrandr nor wlr-randr is available"));
+        };
+
+        Self::query_with(backend)
+    }
+
+    pub fn query_with(backend: DisplayBackend) -> Result<Self> {
+        let output = match backend {
+            DisplayBackend::Xrandr   => Command::new("xrandr").arg("--current").output()?,
+            DisplayBackend::WlrRandr => Command::new("wlr-randr").output()?
+        };
+
+        if !output.status.success() {
+            return Err(Error::other(format!("Failed to query display modes: {}", String::from_utf8_lossy(&output.stderr).trim())));
+        }
+
+        let output = String::from_utf8_lossy(&output.stdout);
+
+        Ok(match backend {
+            DisplayBackend::Xrandr   => Self::parse_xrandr(&output),
+            DisplayBackend::WlrRandr => Self::parse_wlr_randr(&output)
+        })
+    }
+
+    /// Parse output of the `xrandr --current` command
+    ///
+    /// ```
+    /// use wincompatlib::prelude::*;
+    ///
+    /// let modes = DisplayModes::parse_xrandr("Screen 0: minimum 8 x 8, current 1920 x 1080, maximum 32767 x 32767
+    /// HDMI-1 connected primary 1920x1080+0+0 (normal left inverted right x axis y axis) 527mm x 296mm
+    ///    1920x1080     60.00*+  50.00
+    ///    800x600       60.32
+    /// DP-1 disconnected (normal left inverted right x axis y axis)");
+    ///
+    /// assert_eq!(modes.outputs.len(), 1);
+    /// assert_eq!(modes.outputs[0].output, "HDMI-1");
+    /// assert_eq!(modes.outputs[0].refresh, Some(60.0));
+    /// ```
+    pub fn parse_xrandr(output: &str) -> Self {
+        let mut outputs = Vec::new();
+        let mut current = None;
+
+        for line in output.lines() {
+            if !line.starts_with(' ') {
+                let mut words = line.split_whitespace();
+
+                current = match (words.next(), words.next()) {
+                    (Some(name), Some("connected")) => Some(name.to_string()),
+                    _ => None
+                };
+            }
+
+            else if let Some(name) = &current {
+                let mut words = line.split_whitespace();
+
+                let Some((width, height)) = words.next().and_then(parse_size) else {
+                    continue;
+                };
+
+                // Current rate is marked by the asterisk
+                if let Some(rate) = words.find(|rate| rate.contains('*')) {
+                    outputs.push(OutputMode {
+                        output: name.clone(),
+                        width,
+                        height,
+                        refresh: rate.trim_end_matches(['*', '+']).parse().ok()
+                    });
+
+                    current = None;
+                }
+            }
+        }
+
+        Self {
+            backend: DisplayBackend::Xrandr,
+            outputs
+        }
+    }
+
+    /// Parse output of the `wlr-randr` command
+    ///
+    /// ```
+    /// use wincompatlib::prelude::*;
+    ///
+    /// let modes = DisplayModes::parse_wlr_randr("DP-1 \"Dell Inc. DELL U2719D\"
+    ///   Enabled: yes
+    ///   Modes:
+    ///     2560x1440 px, 59.951000 Hz (preferred, current)
+    ///     1920x1080 px, 60.000000 Hz");
+    ///
+    /// assert_eq!(modes.outputs[0].width, 2560);
+    /// ```
+    pub fn parse_wlr_randr(output: &str) -> Self {
+        let mut outputs = Vec::new();
+        let mut current = None;
+
+        for line in output.lines() {
+            if !line.starts_with(' ') {
+                current = line.split_whitespace().next().map(String::from);
+            }
+
+            else if let Some(name) = &current {
+                let Some(mode) = line.trim().strip_suffix(')').filter(|mode| mode.contains("current")) else {
+                    continue;
+                };
+
+                let mut words = mode.split_whitespace();
+
+                let Some((width, height)) = words.next().and_then(parse_size) else {
+                    continue;
+                };
+
+                outputs.push(OutputMode {
+                    output: name.clone(),
+                    width,
+                    height,
+                    refresh: words.nth(1).and_then(|rate| rate.parse().ok())
+                });
+
+                current = None;
+            }
+        }
+
+        Self {
+            backend: DisplayBackend::WlrRandr,
+            outputs
+        }
+    }
+
+    /// Set recorded modes back
+    pub fn restore(&self) -> Result<()> {
+        for mode in &self.outputs {
+            let size = format!("{}x{}", mode.width, mode.height);
+
+            let mut command = match self.backend {
+                DisplayBackend::Xrandr => {
+                    let mut command = Command::new("xrandr");
+
+                    command.args(["--output", &mode.output, "--mode", &size]);
+
+                    if let Some(refresh) = mode.refresh {
+                        command.args(["--rate", &format!("{refresh:.2}")]);
+                    }
+
+                    command
+                }
+
+                DisplayBackend::WlrRandr => {
+                    let mut command = Command::new("wlr-randr");
+
+                    let size = match mode.refresh {
+                        Some(refresh) => format!("{size}@{refresh}Hz"),
+                        None => size
+                    };
+
+                    command.args(["--output", &mode.output, "--mode", &size]);
+
+                    command
+                }
+            };
+
+            let output = command.output()?;
+
+            if !output.status.success() {
+                return Err(Error::other(format!("Failed to restore {} mode: {}", mode.output, String::from_utf8_lossy(&output.stderr).trim())));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Record current modes, run the game and restore the modes if the game has changed them
+    ///
+    /// Modes are restored even if the game failed. The game closure should wait until the game exits
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// let wine = Wine::default().with_prefix("/path/to/prefix");
+    ///
+    /// DisplayModes::run(|| {
+    ///     wine.run("/path/to/game.exe")?.wait()
+    /// }).expect("Failed to run the game");
+    /// ```
+    pub fn run<T, F>(game: F) -> Result<T>
+    where
+        F: FnOnce() -> Result<T>
+    {
+        let modes = Self::query()?;
+
+        let result = game();
+
+        let restored = match Self::query_with(modes.backend) {
+            Ok(current) if current == modes => Ok(()),
+            _ => modes.restore()
+        };
+
+        let value = result?;
+
+        restored.map(|_| value)
+    }
+}
@@ -1,9 +1,12 @@
 use std::path::{Path, PathBuf};
-use std::io::{Error, ErrorKind, Result};
+use std::io::ErrorKind;
+
+use crate::error::{Error, Result};
 
 use derive_builder::Builder;
 
 use super::wine::*;
+use super::warning::Warning;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Arch {
@@ -57,6 +60,115 @@ impl Default for InstallParams {
     }
 }
 
+impl InstallParams {
+    /// Get list of libraries names which should be (un)installed
+    pub fn dlls(&self) -> Vec<&'static str> {
+        let mut dlls = Vec::with_capacity(4);
+
+        if self.dxgi {
+            dlls.push("dxgi");
+        }
+
+        if self.d3d9 {
+            dlls.push("d3d9");
+        }
+
+        if self.d3d10core {
+            dlls.push("d3d10core");
+        }
+
+        if self.d3d11 {
+            dlls.push("d3d11");
+        }
+
+        dlls
+    }
+}
+
+/// Error returned by `Dxvk::install` and `Dxvk::uninstall`
+/// 
+/// It's wrapped into `Error::Io` and can be accessed using `get_ref` and `downcast_ref`:
+/// 
+/// ```no_run
+/// use wincompatlib::prelude::*;
+/// 
+/// let wine = Wine::default().with_prefix("/path/to/prefix");
+/// 
+/// if let Err(wincompatlib::Error::Io(err)) = wine.install_dxvk("/path/to/dxvk-x.y.z", InstallParams::default()) {
+///     if let Some(InstallError::MissingDll { path, .. }) = err.get_ref().and_then(|err| err.downcast_ref::<InstallError>()) {
+///         eprintln!("DXVK build doesn't contain {:?}", path);
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InstallError {
+    /// DXVK build doesn't contain library of the requested architecture
+    MissingDll {
+        arch: Arch,
+        path: PathBuf
+    },
+
+    /// Requested libraries architecture can't be installed to the prefix
+    UnsupportedArch {
+        prefix: WineArch,
+        arch: Arch
+    }
+}
+
+impl std::fmt::Display for InstallError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingDll { arch, path } => write!(f, "DXVK build doesn't contain {arch:?} library: {}", path.to_string_lossy()),
+            Self::UnsupportedArch { prefix, arch } => write!(f, "{arch:?} libraries can't be installed to {} prefix", prefix.to_str())
+        }
+    }
+}
+
+impl std::error::Error for InstallError {}
+
+impl From<InstallError> for Error {
+    fn from(err: InstallError) -> Self {
+        match err {
+            InstallError::MissingDll { .. } => Error::new(ErrorKind::NotFound, err),
+            InstallError::UnsupportedArch { .. } => Error::UnsupportedArch(err.to_string())
+        }
+    }
+}
+
+/// Get windows path to the system folder where libraries should be installed,
+/// and the name of the DXVK folder they should be taken from
+pub(crate) fn get_install_folders(wine: &Wine, prefix: &Path, arch: Arch) -> Result<(&'static str, &'static str)> {
+    // Old prefixes don't specify their architecture so we'll rely on wine struct
+    let prefix_arch = match WineArch::from_prefix(prefix)? {
+        Some(arch) => arch,
+        None => {
+            let arch = wine.arch.unwrap_or(WineArch::Win64);
+
+            wine.events.warning(Warning::UnknownPrefixArch { assumed: arch });
+
+            arch
+        }
+    };
+
+    // x32 libraries would be installed but never loaded
+    if prefix_arch == WineArch::Win64 && arch == Arch::Win32 && wine.has_32bit_support() == Some(false) {
+        wine.events.warning(Wow64Warning::Unsupported);
+    }
+
+    match (prefix_arch, arch) {
+        (WineArch::Win64, Arch::Win64) => Ok(("C:\\windows\\system32", "x64")),
+        (WineArch::Win64, Arch::Win32) => Ok(("C:\\windows\\syswow64", "x32")),
+
+        // Pure win32 prefixes don't have syswow64 folder
+        (WineArch::Win32, Arch::Win32) => Ok(("C:\\windows\\system32", "x32")),
+
+        (WineArch::Win32, Arch::Win64) => Err(InstallError::UnsupportedArch {
+            prefix: prefix_arch,
+            arch
+        }.into())
+    }
+}
+
 /// Add dll override to the wine prefix
 pub fn install_dll(wine: &Wine, system32: &Path, dlls_folder: &Path, dll_name: &str) -> Result<()> {
     let src_path = dlls_folder.join(format!("{dll_name}.dll"));
@@ -85,17 +197,11 @@ pub fn install_dll(wine: &Wine, system32: &Path, dlls_folder: &Path, dll_name: &
     // Copy dll to the destination location
     std::fs::copy(&src_path, &dest_path)?;
 
-    // "$wine" reg add 'HKEY_CURRENT_USER\Software\Wine\DllOverrides' /v $1 /d native /f
-    let output = wine.run_args(["reg", "add", "HKEY_CURRENT_USER\\Software\\Wine\\DllOverrides", "/v", dll_name, "/d", "native", "/f"])?.wait_with_output()?;
-
-    match output.status.success() {
-        true  => Ok(()),
-        false => {
-            let stdout = String::from_utf8_lossy(&output.stdout);
+    // Don't let proton replace the library when it updates the prefix
+    wine.set_proton_tracked(&dest_path, false)?;
 
-            Err(Error::new(ErrorKind::Other, "Failed to add dll override: ".to_string() + stdout.trim_end().lines().last().unwrap_or(&stdout)))
-        }
-    }
+    // "$wine" reg add 'HKEY_CURRENT_USER\Software\Wine\DllOverrides' /v $1 /d native /f
+    wine.set_dll_override(dll_name, OverrideMode::Native)
 }
 
 /// Remove dll override from the wine prefix
@@ -108,7 +214,9 @@ pub fn restore_dll(wine: &Wine, system32: &Path, dll_name: &str) -> Result<()> {
         // Original file exists so we'll restore it
         if dest_path_old.exists() {
             std::fs::remove_file(&dest_path)?;
-            std::fs::rename(&dest_path_old, dest_path)?;
+            std::fs::rename(&dest_path_old, &dest_path)?;
+
+            wine.set_proton_tracked(&dest_path, true)?;
         }
 
         // Original file doesn't exist
@@ -118,16 +226,7 @@ pub fn restore_dll(wine: &Wine, system32: &Path, dll_name: &str) -> Result<()> {
     }
 
     // "$wine" reg delete 'HKEY_CURRENT_USER\Software\Wine\DllOverrides' /v $1 /f
-    let output = wine.run_args(["reg", "delete", "HKEY_CURRENT_USER\\Software\\Wine\\DllOverrides", "/v", dll_name, "/f"])?.wait_with_output()?;
-
-    match output.status.success() {
-        true  => Ok(()),
-        false => {
-            let stdout = String::from_utf8_lossy(&output.stdout);
-
-            Err(Error::new(ErrorKind::Other, "Failed to add dll override: ".to_string() + stdout.trim_end().lines().last().unwrap_or(&stdout)))
-        }
-    }
+    wine.remove_dll_override(dll_name)
 }
 
 pub struct Dxvk;
@@ -244,6 +343,17 @@ impl Dxvk {
 
     /// Install DXVK to wine prefix
     /// 
+    /// Libraries are installed according to the prefix architecture:
+    /// 
+    /// | Prefix  | `Arch::Win64`        | `Arch::Win32`            |
+    /// | ------- | -------------------- | ------------------------ |
+    /// | `win64` | `x64` to `system32`  | `x32` to `syswow64`      |
+    /// | `win32` | `UnsupportedArch`    | `x32` to `system32`      |
+    /// 
+    /// If DXVK build doesn't contain some required library, `InstallError` is returned
+    /// wrapped into `Error::Io`. If it can't be installed to the prefix,
+    /// `Error::UnsupportedArch` is returned
+    /// 
     /// ```no_run
     /// use wincompatlib::prelude::*;
     /// 
@@ -260,57 +370,44 @@ impl Dxvk {
         dxvk_folder: T,
         params: InstallParams
     ) -> Result<()> {
+        wine.check_writable()?;
+
         match &wine.prefix {
             Some(prefix) => {
                 // Check correctness of the wine prefix
                 if !prefix.exists() || !prefix.join("system.reg").exists() {
-                    return Err(Error::new(ErrorKind::Other, prefix.to_string_lossy() + " is not a valid wine prefix"));
+                    return Err(Error::PrefixMissing(prefix.clone()));
                 }
 
-                // Verify and repair wine prefix if needed (and asked to)
-                if params.repair_dlls {
-                    let output = wine.update_prefix(prefix)?;
+                let (system_folder, dlls_folder) = get_install_folders(wine, prefix, params.arch)?;
+                let dlls_folder = dxvk_folder.into().join(dlls_folder);
 
-                    if !output.status.success() {
-                        return Err(Error::new(ErrorKind::Other, "Failed to repair wine prefix: ".to_string() + &String::from_utf8_lossy(&output.stderr)));
-                    }
-                }
-
-                let system32 = wine.winepath("C:\\windows\\system32")?;
-                let dxvk_folder = dxvk_folder.into();
+                // Check that DXVK build contains all the needed libraries
+                for dll_name in params.dlls() {
+                    let path = dlls_folder.join(format!("{dll_name}.dll"));
 
-                // DXGI
-                if params.dxgi {
-                    match params.arch {
-                        Arch::Win32 => install_dll(wine, &system32, &dxvk_folder.join("x32"), "dxgi")?,
-                        Arch::Win64 => install_dll(wine, &system32, &dxvk_folder.join("x64"), "dxgi")?
+                    if !path.exists() {
+                        return Err(InstallError::MissingDll { arch: params.arch, path }.into());
                     }
                 }
 
-                // D3D9
-                if params.d3d9 {
-                    match params.arch {
-                        Arch::Win32 => install_dll(wine, &system32, &dxvk_folder.join("x32"), "d3d9")?,
-                        Arch::Win64 => install_dll(wine, &system32, &dxvk_folder.join("x64"), "d3d9")?
-                    }
+                // Verify and repair wine prefix if needed (and asked to)
+                if params.repair_dlls {
+                    wine.update_prefix(prefix)?;
                 }
 
-                // D3D10 Core
-                if params.d3d10core {
-                    match params.arch {
-                        Arch::Win32 => install_dll(wine, &system32, &dxvk_folder.join("x32"), "d3d10core")?,
-                        Arch::Win64 => install_dll(wine, &system32, &dxvk_folder.join("x64"), "d3d10core")?
-                    }
-                }
+                let system_folder = wine.winepath(system_folder)?;
 
-                // D3D11
-                if params.d3d11 {
-                    match params.arch {
-                        Arch::Win32 => install_dll(wine, &system32, &dxvk_folder.join("x32"), "d3d11")?,
-                        Arch::Win64 => install_dll(wine, &system32, &dxvk_folder.join("x64"), "d3d11")?
-                    }
+                let dlls = params.dlls();
+
+                for (i, dll_name) in dlls.iter().enumerate() {
+                    wine.events.progress(i as u64, dlls.len() as u64, &format!("Installing {dll_name}.dll"));
+
+                    install_dll(wine, &system_folder, &dlls_folder, dll_name)?;
                 }
 
+                wine.events.progress(dlls.len() as u64, dlls.len() as u64, "DXVK installed");
+
                 Ok(())
             }
 
@@ -320,6 +417,8 @@ impl Dxvk {
 
     /// Uninstall DXVK from wine prefix
     /// 
+    /// Libraries are restored in the same folders they were installed to by `Dxvk::install`
+    /// 
     /// ```no_run
     /// use wincompatlib::prelude::*;
     /// 
@@ -334,55 +433,33 @@ impl Dxvk {
         wine: &Wine,
         params: InstallParams
     ) -> Result<()> {
+        wine.check_writable()?;
+
         match &wine.prefix {
             Some(prefix) => {
                 // Check correctness of the wine prefix
                 if !prefix.exists() || !prefix.join("system.reg").exists() {
-                    return Err(Error::new(ErrorKind::Other, prefix.to_string_lossy() + " is not a valid wine prefix"));
+                    return Err(Error::PrefixMissing(prefix.clone()));
                 }
 
+                let (system_folder, _) = get_install_folders(wine, prefix, params.arch)?;
+
                 // Verify and repair wine prefix if needed (and asked to)
                 if params.repair_dlls {
-                    let output = wine.update_prefix(prefix)?;
-
-                    if !output.status.success() {
-                        return Err(Error::new(ErrorKind::Other, "Failed to repair wine prefix: ".to_string() + &String::from_utf8_lossy(&output.stderr)));
-                    }
+                    wine.update_prefix(prefix)?;
                 }
 
-                let system32 = wine.winepath("C:\\windows\\system32")?;
+                let system_folder = wine.winepath(system_folder)?;
 
-                // DXGI
-                if params.dxgi {
-                    match params.arch {
-                        Arch::Win32 => restore_dll(wine, &system32, "dxgi")?,
-                        Arch::Win64 => restore_dll(wine, &system32, "dxgi")?
-                    }
-                }
+                let dlls = params.dlls();
 
-                // D3D9
-                if params.d3d9 {
-                    match params.arch {
-                        Arch::Win32 => restore_dll(wine, &system32, "d3d9")?,
-                        Arch::Win64 => restore_dll(wine, &system32, "d3d9")?
-                    }
-                }
+                for (i, dll_name) in dlls.iter().enumerate() {
+                    wine.events.progress(i as u64, dlls.len() as u64, &format!("Restoring {dll_name}.dll"));
 
-                // D3D10 Core
-                if params.d3d10core {
-                    match params.arch {
-                        Arch::Win32 => restore_dll(wine, &system32, "d3d10core")?,
-                        Arch::Win64 => restore_dll(wine, &system32, "d3d10core")?
-                    }
+                    restore_dll(wine, &system_folder, dll_name)?;
                 }
 
-                // D3D11
-                if params.d3d11 {
-                    match params.arch {
-                        Arch::Win32 => restore_dll(wine, &system32, "d3d11")?,
-                        Arch::Win64 => restore_dll(wine, &system32, "d3d11")?
-                    }
-                }
+                wine.events.progress(dlls.len() as u64, dlls.len() as u64, "DXVK uninstalled");
 
                 Ok(())
             }
@@ -0,0 +1,224 @@
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom, Result};
+use std::path::{Path, PathBuf};
+
+/// Log files written by DXVK for each API it translates
+const LOG_SUFFIXES: &[&str] = &["d3d9", "d3d10core", "d3d11", "dxgi"];
+
+/// Graphics adapter reported in the DXVK log
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct DxvkAdapter {
+    pub name: String,
+    pub driver: Option<String>,
+    pub vulkan: Option<String>
+}
+
+/// Information parsed from the DXVK log file
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct DxvkLog {
+    /// Executable name, e.g. `game.exe`
+    pub game: Option<String>,
+
+    /// DXVK version, e.g. `v2.1`
+    pub version: Option<String>,
+
+    pub adapters: Vec<DxvkAdapter>,
+
+    /// Maximal feature level supported by the device, e.g. `D3D_FEATURE_LEVEL_12_1`
+    pub max_feature_level: Option<String>,
+
+    /// Feature level requested by the game
+    pub feature_level: Option<String>,
+
+    /// Lines reported with `err:` level
+    pub errors: Vec<String>
+}
+
+impl DxvkLog {
+    /// Find DXVK log files of the executable
+    ///
+    /// DXVK writes them next to the executable, or to `DXVK_LOG_PATH` folder if it's specified
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// for log in DxvkLog::find("/path/to/game.exe", None::<&str>) {
+    ///     println!("{:?}", DxvkLog::open(log));
+    /// }
+    /// ```
+    pub fn find<T: AsRef<Path>, F: AsRef<Path>>(exe: T, log_folder: Option<F>) -> Vec<PathBuf> {
+        let exe = exe.as_ref();
+
+        let Some(name) = exe.file_stem() else {
+            return Vec::new();
+        };
+
+        let folder = match &log_folder {
+            Some(folder) => folder.as_ref(),
+            None => exe.parent().unwrap_or(Path::new(""))
+        };
+
+        LOG_SUFFIXES.iter()
+            .map(|suffix| folder.join(format!("{}_{suffix}.log", name.to_string_lossy())))
+            .filter(|path| path.exists())
+            .collect()
+    }
+
+    pub fn open<T: AsRef<Path>>(path: T) -> Result<Self> {
+        Ok(Self::parse(&String::from_utf8_lossy(&std::fs::read(path)?)))
+    }
+
+    /// Parse DXVK log file
+    ///
+    /// ```
+    /// use wincompatlib::prelude::*;
+    ///
+    /// let log = DxvkLog::parse("
+    /// info:  Game: game.exe
+    /// info:  DXVK: v2.1
+    /// info:  AMD Radeon RX 6800 (RADV NAVI21):
+    /// info:    Driver : radv 23.0.3
+    /// info:    Vulkan : 1.3.238
+    /// info:  D3D11CoreCreateDevice: Using feature level D3D_FEATURE_LEVEL_11_0
+    /// ");
+    ///
+    /// assert_eq!(log.version.as_deref(), Some("v2.1"));
+    /// assert_eq!(log.adapters[0].name, "AMD Radeon RX 6800 (RADV NAVI21)");
+    /// assert_eq!(log.adapters[0].driver.as_deref(), Some("radv 23.0.3"));
+    /// assert_eq!(log.feature_level.as_deref(), Some("D3D_FEATURE_LEVEL_11_0"));
+    /// ```
+    pub fn parse(content: &str) -> Self {
+        let mut log = Self::default();
+
+        for line in content.lines() {
+            if let Some(error) = line.strip_prefix("err:") {
+                log.errors.push(error.trim().to_string());
+
+                continue;
+            }
+
+            let Some(message) = line.strip_prefix("info:") else {
+                continue;
+            };
+
+            // Adapter properties are indented deeper than the other messages
+            let is_property = message.starts_with("    ");
+
+            let message = message.trim();
+
+            let (key, value) = match message.split_once(':') {
+                Some((key, value)) => (key.trim(), value.trim()),
+                None => continue
+            };
+
+            match key {
+                "Game" => log.game = Some(value.to_string()),
+                "DXVK" => log.version = Some(value.to_string()),
+
+                "Driver" | "Vulkan" if is_property => if let Some(adapter) = log.adapters.last_mut() {
+                    match key {
+                        "Driver" => adapter.driver = Some(value.to_string()),
+                        _        => adapter.vulkan = Some(value.to_string())
+                    }
+                }
+
+                _ if value.is_empty() && !is_property && message.ends_with(':') => {
+                    // Adapter name and lists like "Enabled device extensions:" look the same,
+                    // so adapter is added only when its properties are found
+                    log.adapters.push(DxvkAdapter {
+                        name: key.to_string(),
+                        ..DxvkAdapter::default()
+                    });
+                }
+
+                _ => {
+                    if let Some(level) = value.strip_prefix("Maximum supported feature level: ") {
+                        log.max_feature_level = Some(level.to_string());
+                    }
+
+                    else if let Some(level) = value.strip_prefix("Using feature level ") {
+                        log.feature_level = Some(level.to_string());
+                    }
+                }
+            }
+        }
+
+        log.adapters.retain(|adapter| adapter.driver.is_some() || adapter.vulkan.is_some());
+
+        log
+    }
+}
+
+/// Reader of the new lines appended to the log file, e.g. while the game is running
+///
+/// ```no_run
+/// use wincompatlib::prelude::*;
+///
+/// let mut tail = LogTail::new("/path/to/game_d3d11.log");
+///
+/// loop {
+///     for line in tail.read_lines().expect("Failed to read log") {
+///         println!("{line}");
+///     }
+///
+///     std::thread::sleep(std::time::Duration::from_millis(500));
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogTail {
+    path: PathBuf,
+    offset: u64,
+
+    /// Bytes of the incomplete last line, kept undecoded so multibyte
+    /// characters split between reads are not broken
+    partial: Vec<u8>
+}
+
+impl LogTail {
+    /// Start reading the file from its beginning. File doesn't need to exist yet
+    pub fn new<T: Into<PathBuf>>(path: T) -> Self {
+        Self {
+            path: path.into(),
+            offset: 0,
+            partial: Vec::new()
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Read complete lines appended since the previous call
+    ///
+    /// If file was truncated (e.g. rewritten by the next run), then it's read from the beginning
+    pub fn read_lines(&mut self) -> Result<Vec<String>> {
+        let mut file = match File::open(&self.path) {
+            Ok(file) => file,
+
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err)
+        };
+
+        if file.metadata()?.len() < self.offset {
+            self.offset = 0;
+            self.partial.clear();
+        }
+
+        file.seek(SeekFrom::Start(self.offset))?;
+
+        self.offset += file.read_to_end(&mut self.partial)? as u64;
+
+        let Some(end) = self.partial.iter().rposition(|byte| *byte == b'\n') else {
+            return Ok(Vec::new());
+        };
+
+        let lines = String::from_utf8_lossy(&self.partial[..end])
+            .lines()
+            .map(String::from)
+            .collect();
+
+        self.partial.drain(..=end);
+
+        Ok(lines)
+    }
+}
@@ -0,0 +1,159 @@
+use std::path::PathBuf;
+use std::process::Output;
+use std::io::ErrorKind;
+
+use crate::prefix::PrefixDiff;
+
+/// Error returned by the `wine`, `dxvk`, `proton` and `steam` modules
+///
+/// Converts from and into `std::io::Error`, so `?` works with both.
+/// Use `kind` to check errors the same way as before
+///
+/// ```no_run
+/// use wincompatlib::prelude::*;
+///
+/// match Wine::from_binary("/path/to/wine").version() {
+///     Err(wincompatlib::Error::BinaryNotFound(binary)) => eprintln!("{binary:?} is not installed"),
+///     Err(err) => eprintln!("Failed to get wine version: {err}"),
+///     Ok(version) => println!("Wine version: {version:?}")
+/// }
+/// ```
+#[derive(Debug)]
+pub enum Error {
+    /// Binary (wine, wineboot, wineserver, etc.) doesn't exist
+    BinaryNotFound(PathBuf),
+
+    /// Wine prefix doesn't exist or is not created yet
+    PrefixMissing(PathBuf),
+
+    /// Wineboot finished with error
+    WinebootFailed {
+        code: Option<i32>,
+        stderr: String
+    },
+
+    /// Version string of the binary or component can't be parsed
+    VersionParse(String),
+
+    /// Operation doesn't support the architecture
+    UnsupportedArch(String),
+
+    /// Operation would modify the wine prefix in read-only mode
+    ReadOnlyPrefix(PathBuf),
+
+    /// Wine prefix was modified while it was expected to stay unchanged
+    PrefixModified {
+        prefix: PathBuf,
+        diff: PrefixDiff
+    },
+
+    /// Any other IO error
+    Io(std::io::Error)
+}
+
+impl Error {
+    /// Create IO error, same as `std::io::Error::new`
+    #[inline]
+    pub fn new<E>(kind: ErrorKind, error: E) -> Self
+    where
+        E: Into<Box<dyn std::error::Error + Send + Sync>>
+    {
+        Self::Io(std::io::Error::new(kind, error))
+    }
+
+    /// Create IO error of `ErrorKind::Other` kind, same as `std::io::Error::other`
+    #[inline]
+    pub fn other<E>(error: E) -> Self
+    where
+        E: Into<Box<dyn std::error::Error + Send + Sync>>
+    {
+        Self::Io(std::io::Error::other(error))
+    }
+
+    #[inline]
+    pub fn last_os_error() -> Self {
+        Self::Io(std::io::Error::last_os_error())
+    }
+
+    /// Create `WinebootFailed` error from the wineboot output
+    pub fn wineboot_failed(output: &Output) -> Self {
+        Self::WinebootFailed {
+            code: output.status.code(),
+            stderr: String::from_utf8_lossy(&output.stderr).trim().to_string()
+        }
+    }
+
+    /// Convert error of spawning the binary, replacing `NotFound` with `BinaryNotFound`
+    pub(crate) fn from_spawn(binary: impl Into<PathBuf>, err: std::io::Error) -> Self {
+        if err.kind() == ErrorKind::NotFound {
+            Self::BinaryNotFound(binary.into())
+        } else {
+            Self::Io(err)
+        }
+    }
+
+    /// Get kind of the corresponding `std::io::Error`
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::BinaryNotFound(_)     => ErrorKind::NotFound,
+            Self::PrefixMissing(_)      => ErrorKind::NotFound,
+            Self::WinebootFailed { .. } => ErrorKind::Other,
+            Self::VersionParse(_)       => ErrorKind::InvalidData,
+            Self::UnsupportedArch(_)    => ErrorKind::Unsupported,
+            Self::ReadOnlyPrefix(_)     => ErrorKind::PermissionDenied,
+            Self::PrefixModified { .. } => ErrorKind::Other,
+            Self::Io(err)               => err.kind()
+        }
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::BinaryNotFound(binary) => write!(f, "Binary not found: {binary:?}"),
+            Self::PrefixMissing(prefix) => write!(f, "Wine prefix doesn't exist: {prefix:?}"),
+
+            Self::WinebootFailed { code, stderr } => match code {
+                Some(code) => write!(f, "Wineboot failed with code {code}: {stderr}"),
+                None => write!(f, "Wineboot was terminated: {stderr}")
+            },
+
+            Self::VersionParse(version) => write!(f, "Failed to parse version: {version}"),
+            Self::UnsupportedArch(arch) => write!(f, "Unsupported architecture: {arch}"),
+            Self::ReadOnlyPrefix(prefix) => write!(f, "Wine prefix is read-only: {prefix:?}"),
+
+            Self::PrefixModified { prefix, diff } => write!(f, "Wine prefix {prefix:?} was modified: {} changed, {} removed files",
+                diff.changed.len(), diff.removed.len()),
+
+            Self::Io(err) => write!(f, "{err}")
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            _ => None
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    #[inline]
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<Error> for std::io::Error {
+    fn from(err: Error) -> Self {
+        match err {
+            Error::Io(err) => err,
+            err => std::io::Error::new(err.kind(), err)
+        }
+    }
+}
+
+/// Result with the library's `Error`
+pub type Result<T> = std::result::Result<T, Error>;
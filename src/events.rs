@@ -0,0 +1,385 @@
+use std::ffi::{OsStr, OsString};
+use std::os::unix::ffi::{OsStrExt, OsStringExt};
+use std::path::PathBuf;
+use std::process::{Command, Child, Output};
+use std::sync::{Arc, Mutex};
+use std::io::Result;
+
+use crate::warning::Warning;
+
+/// Information about the command started by the library
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandInfo {
+    pub program: OsString,
+    pub args: Vec<OsString>,
+
+    /// Environment variables explicitly set (`Some`) or removed (`None`) for this command
+    pub envs: Vec<(OsString, Option<OsString>)>
+}
+
+impl CommandInfo {
+    pub fn from_command(command: &Command) -> Self {
+        Self {
+            program: command.get_program().to_os_string(),
+            args: command.get_args().map(OsStr::to_os_string).collect(),
+            envs: command.get_envs()
+                .map(|(key, value)| (key.to_os_string(), value.map(OsStr::to_os_string)))
+                .collect()
+        }
+    }
+
+    /// Replace secret values in arguments and environment with `***`,
+    /// and hide values of the secret environment variables completely
+    ///
+    /// ```
+    /// use std::process::Command;
+    ///
+    /// use wincompatlib::prelude::*;
+    ///
+    /// let mut command = Command::new("launcher.exe");
+    ///
+    /// command.arg("--token=hunter2").env("AUTH_TOKEN", "abcd");
+    ///
+    /// let mut info = CommandInfo::from_command(&command);
+    ///
+    /// info.redact(&["hunter2"], &["AUTH_TOKEN"]);
+    ///
+    /// assert_eq!(info.to_string(), "AUTH_TOKEN=\"***\" \"launcher.exe\" \"--token=***\"");
+    /// ```
+    pub fn redact<T: AsRef<OsStr>, K: AsRef<OsStr>>(&mut self, secrets: &[T], secret_envs: &[K]) {
+        for arg in &mut self.args {
+            *arg = redact(arg, secrets);
+        }
+
+        for (key, value) in &mut self.envs {
+            if let Some(value) = value {
+                *value = if secret_envs.iter().any(|env| env.as_ref() == key) {
+                    OsString::from(REDACTED)
+                } else {
+                    redact(value, secrets)
+                };
+            }
+        }
+    }
+}
+
+const REDACTED: &str = "***";
+
+/// Replace all occurrences of the secrets in the value with `***`
+fn redact<T: AsRef<OsStr>>(value: &OsStr, secrets: &[T]) -> OsString {
+    let mut bytes = value.as_bytes().to_vec();
+
+    for secret in secrets {
+        let secret = secret.as_ref().as_bytes();
+
+        if secret.is_empty() {
+            continue;
+        }
+
+        let mut redacted = Vec::with_capacity(bytes.len());
+        let mut i = 0;
+
+        while i < bytes.len() {
+            if bytes[i..].starts_with(secret) {
+                redacted.extend_from_slice(REDACTED.as_bytes());
+
+                i += secret.len();
+            } else {
+                redacted.push(bytes[i]);
+
+                i += 1;
+            }
+        }
+
+        bytes = redacted;
+    }
+
+    OsString::from_vec(bytes)
+}
+
+impl std::fmt::Display for CommandInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (key, value) in &self.envs {
+            if let Some(value) = value {
+                write!(f, "{}={:?} ", key.to_string_lossy(), value)?;
+            }
+        }
+
+        write!(f, "{:?}", self.program)?;
+
+        for arg in &self.args {
+            write!(f, " {arg:?}")?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputStream {
+    Stdout,
+    Stderr
+}
+
+/// Receiver of everything the library does, so GUI apps can render it without scraping logs
+///
+/// All the methods do nothing by default
+///
+/// ```
+/// use wincompatlib::prelude::*;
+///
+/// struct Logger;
+///
+/// impl EventSink for Logger {
+///     fn on_command_start(&self, command: &CommandInfo) {
+///         println!("Running {command}");
+///     }
+///
+///     fn on_output_line(&self, _stream: OutputStream, line: &str) {
+///         println!("> {line}");
+///     }
+/// }
+///
+/// let wine = Wine::default().with_event_sink(Logger);
+/// ```
+pub trait EventSink: Send + Sync {
+    /// Called before the library spawns some command
+    fn on_command_start(&self, _command: &CommandInfo) {}
+
+    /// Called for each output line of the commands which output is read by the library
+    fn on_output_line(&self, _stream: OutputStream, _line: &str) {}
+
+    /// Called by long operations consisting of several steps
+    fn on_progress(&self, _current: u64, _total: u64, _message: &str) {}
+
+    /// Called when some non-fatal issue happened
+    fn on_warning(&self, _message: &str) {}
+
+    /// Called together with `on_warning`, giving the issue itself instead of its description
+    fn on_typed_warning(&self, _warning: &Warning) {}
+}
+
+impl<T: EventSink + ?Sized> EventSink for Arc<T> {
+    fn on_command_start(&self, command: &CommandInfo) {
+        self.as_ref().on_command_start(command)
+    }
+
+    fn on_output_line(&self, stream: OutputStream, line: &str) {
+        self.as_ref().on_output_line(stream, line)
+    }
+
+    fn on_progress(&self, current: u64, total: u64, message: &str) {
+        self.as_ref().on_progress(current, total, message)
+    }
+
+    fn on_warning(&self, message: &str) {
+        self.as_ref().on_warning(message)
+    }
+
+    fn on_typed_warning(&self, warning: &Warning) {
+        self.as_ref().on_typed_warning(warning)
+    }
+}
+
+/// Commands recently started by the runner, used by `SupportBundle`
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CommandHistory {
+    /// Last command which finished with non-zero status and its output, with secrets redacted
+    pub failed_command: Option<(CommandInfo, Output)>,
+
+    /// Log file of the last process started with `run_detached`
+    pub log_file: Option<PathBuf>
+}
+
+/// List of event sinks attached to the runner
+///
+/// Two lists are equal if they contain the same sinks and secrets
+#[derive(Clone, Default)]
+pub struct Events {
+    sinks: Vec<Arc<dyn EventSink>>,
+
+    /// Shared between clones of the runner
+    history: Arc<Mutex<CommandHistory>>,
+
+    /// Values which are replaced with `***` before being reported
+    secrets: Vec<OsString>,
+
+    /// Environment variables which values are never reported
+    secret_envs: Vec<OsString>
+}
+
+impl Events {
+    pub fn subscribe<T: EventSink + 'static>(&mut self, sink: T) {
+        self.sinks.push(Arc::new(sink));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sinks.is_empty()
+    }
+
+    /// Never report given value (e.g. auth token) in commands and their output
+    pub fn add_secret<T: Into<OsString>>(&mut self, secret: T) {
+        self.secrets.push(secret.into());
+    }
+
+    /// Never report value of the given environment variable
+    pub fn add_secret_env<T: Into<OsString>>(&mut self, key: T) {
+        self.secret_envs.push(key.into());
+    }
+
+    /// Replace secrets in the command info the same way as for the reported commands
+    pub(crate) fn redact(&self, info: &mut CommandInfo) {
+        info.redact(&self.secrets, &self.secret_envs);
+    }
+
+    /// Get last failed command and log file of the runner
+    ///
+    /// Only commands which output is waited for by the library (e.g. `wineboot`, `reg`) are recorded
+    pub fn history(&self) -> CommandHistory {
+        self.history.lock()
+            .map(|history| history.clone())
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn record_log_file(&self, log_file: PathBuf) {
+        if let Ok(mut history) = self.history.lock() {
+            history.log_file = Some(log_file);
+        }
+    }
+
+    /// Replace secrets in the environment variable value, or the whole value if the variable is secret
+    pub(crate) fn redact_env(&self, key: &OsStr, value: &OsStr) -> OsString {
+        if self.secret_envs.iter().any(|env| env == key) {
+            OsString::from(REDACTED)
+        } else {
+            redact(value, &self.secrets)
+        }
+    }
+
+    /// Replace secrets in the raw output of the command
+    pub(crate) fn redact_bytes(&self, bytes: &[u8]) -> Vec<u8> {
+        redact(OsStr::from_bytes(bytes), &self.secrets).into_vec()
+    }
+
+    pub fn command_start(&self, command: &Command) {
+        if !self.sinks.is_empty() {
+            let mut info = CommandInfo::from_command(command);
+
+            info.redact(&self.secrets, &self.secret_envs);
+
+            for sink in &self.sinks {
+                sink.on_command_start(&info);
+            }
+        }
+    }
+
+    pub fn output_line(&self, stream: OutputStream, line: &str) {
+        if !self.sinks.is_empty() {
+            let line = redact(OsStr::new(line), &self.secrets);
+            let line = line.to_string_lossy();
+
+            for sink in &self.sinks {
+                sink.on_output_line(stream, &line);
+            }
+        }
+    }
+
+    pub fn progress(&self, current: u64, total: u64, message: &str) {
+        if !self.sinks.is_empty() {
+            let message = redact(OsStr::new(message), &self.secrets);
+            let message = message.to_string_lossy();
+
+            for sink in &self.sinks {
+                sink.on_progress(current, total, &message);
+            }
+        }
+    }
+
+    pub fn warning<T: Into<Warning>>(&self, warning: T) {
+        if !self.sinks.is_empty() {
+            let redact_string = |value: &str| redact(OsStr::new(value), &self.secrets)
+                .to_string_lossy()
+                .to_string();
+
+            let warning = match warning.into() {
+                Warning::Other(message) => Warning::Other(redact_string(&message)),
+                Warning::DiscMountFallback { reason } => Warning::DiscMountFallback { reason: redact_string(&reason) },
+
+                warning => warning
+            };
+
+            let message = redact_string(&warning.to_string());
+
+            for sink in &self.sinks {
+                sink.on_warning(&message);
+                sink.on_typed_warning(&warning);
+            }
+        }
+    }
+
+    /// Report command start and spawn it
+    pub(crate) fn spawn(&self, command: &mut Command) -> Result<Child> {
+        self.command_start(command);
+
+        command.spawn()
+    }
+
+    /// Report command start, wait for its output and report it as well
+    pub(crate) fn output(&self, command: &mut Command) -> Result<Output> {
+        self.command_start(command);
+
+        let output = command.output()?;
+
+        self.process_output(&output);
+
+        if !output.status.success() {
+            let mut info = CommandInfo::from_command(command);
+
+            self.redact(&mut info);
+
+            let redacted = Output {
+                status: output.status,
+                stdout: self.redact_bytes(&output.stdout),
+                stderr: self.redact_bytes(&output.stderr)
+            };
+
+            if let Ok(mut history) = self.history.lock() {
+                history.failed_command = Some((info, redacted));
+            }
+        }
+
+        Ok(output)
+    }
+
+    /// Report lines of already finished command
+    pub(crate) fn process_output(&self, output: &Output) {
+        if !self.sinks.is_empty() {
+            for line in String::from_utf8_lossy(&output.stdout).lines() {
+                self.output_line(OutputStream::Stdout, line);
+            }
+
+            for line in String::from_utf8_lossy(&output.stderr).lines() {
+                self.output_line(OutputStream::Stderr, line);
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for Events {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Events({} sinks, {} secrets)", self.sinks.len(), self.secrets.len() + self.secret_envs.len())
+    }
+}
+
+impl PartialEq for Events {
+    fn eq(&self, other: &Self) -> bool {
+        self.secrets == other.secrets &&
+        self.secret_envs == other.secret_envs &&
+        self.sinks.len() == other.sinks.len() &&
+        self.sinks.iter()
+            .zip(other.sinks.iter())
+            .all(|(a, b)| Arc::ptr_eq(a, b))
+    }
+}
+
+impl Eq for Events {}
@@ -0,0 +1,201 @@
+//! C ABI for using wincompatlib from other languages
+//!
+//! Functions returning `int` return `-1` on error, and the error message
+//! can be retrieved with `wincompat_last_error`. Panics are caught and reported the same way.
+//! See `include/wincompatlib.h`
+//!
+//! Shared library is built with `cargo rustc --release --features ffi --crate-type cdylib`
+
+use std::cell::RefCell;
+use std::ffi::{CStr, CString, OsStr};
+use std::os::raw::{c_char, c_int};
+use std::os::unix::ffi::OsStrExt;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::io::{Error, ErrorKind, Result};
+
+use crate::prelude::*;
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(err: Error) {
+    let message = CString::new(err.to_string().replace('\0', ""))
+        .unwrap_or_default();
+
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+}
+
+/// Run function body, returning `default` if it panics since unwinding into C is undefined behavior
+fn guard<T>(default: T, body: impl FnOnce() -> T) -> T {
+    match catch_unwind(AssertUnwindSafe(body)) {
+        Ok(value) => value,
+
+        Err(panic) => {
+            let message = panic.downcast_ref::<&str>().map(|message| message.to_string())
+                .or_else(|| panic.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| String::from("unknown reason"));
+
+            set_last_error(Error::other(format!("Library panicked: {message}")));
+
+            default
+        }
+    }
+}
+
+/// Convert result to the C return value, storing the error message
+fn to_c_result(body: impl FnOnce() -> Result<c_int>) -> c_int {
+    guard(-1, || match body() {
+        Ok(value) => value,
+
+        Err(err) => {
+            set_last_error(err);
+
+            -1
+        }
+    })
+}
+
+unsafe fn to_os_str<'a>(value: *const c_char) -> Result<&'a OsStr> {
+    if value.is_null() {
+        return Err(Error::new(ErrorKind::InvalidInput, "Unexpected null pointer"));
+    }
+
+    Ok(OsStr::from_bytes(CStr::from_ptr(value).to_bytes()))
+}
+
+unsafe fn to_wine<'a>(runner: *const Wine) -> Result<&'a Wine> {
+    runner.as_ref().ok_or_else(|| Error::new(ErrorKind::InvalidInput, "Unexpected null runner pointer"))
+}
+
+/// Create new runner using given wine binary and prefix
+///
+/// Returns null pointer on error
+///
+/// # Safety
+///
+/// `binary` must be a valid null-terminated string, and `prefix` either the same or null
+#[no_mangle]
+pub unsafe extern "C" fn wincompat_runner_new(binary: *const c_char, prefix: *const c_char) -> *mut Wine {
+    guard(std::ptr::null_mut(), || {
+        let binary = match to_os_str(binary) {
+            Ok(binary) => binary,
+
+            Err(err) => {
+                set_last_error(err);
+
+                return std::ptr::null_mut();
+            }
+        };
+
+        let mut wine = Wine::from_binary(binary);
+
+        if !prefix.is_null() {
+            wine = wine.with_prefix(OsStr::from_bytes(CStr::from_ptr(prefix).to_bytes()));
+        }
+
+        Box::into_raw(Box::new(wine))
+    })
+}
+
+/// Destroy runner created by `wincompat_runner_new`
+///
+/// # Safety
+///
+/// `runner` must be either null or a pointer returned by `wincompat_runner_new` which wasn't freed yet
+#[no_mangle]
+pub unsafe extern "C" fn wincompat_runner_free(runner: *mut Wine) {
+    guard((), || {
+        if !runner.is_null() {
+            drop(Box::from_raw(runner));
+        }
+    })
+}
+
+/// Create (or update) the runner's wine prefix
+///
+/// Returns `0` on success
+///
+/// # Safety
+///
+/// `runner` must be a valid pointer returned by `wincompat_runner_new`
+#[no_mangle]
+pub unsafe extern "C" fn wincompat_runner_create_prefix(runner: *const Wine) -> c_int {
+    to_c_result(|| {
+        let wine = to_wine(runner)?;
+        wine.update_prefix(wine.prefix_path())?;
+
+        Ok(0)
+    })
+}
+
+/// Run executable with given arguments and wait until it exits
+///
+/// Returns exit code of the process
+///
+/// # Safety
+///
+/// `runner` must be a valid pointer returned by `wincompat_runner_new`, `exe` a valid null-terminated string,
+/// and `args` either null or an array of `args_len` valid null-terminated strings
+#[no_mangle]
+pub unsafe extern "C" fn wincompat_runner_run(runner: *const Wine, exe: *const c_char, args: *const *const c_char, args_len: usize) -> c_int {
+    to_c_result(|| {
+        let wine = to_wine(runner)?;
+
+        let mut command = vec![to_os_str(exe)?];
+
+        if !args.is_null() {
+            for i in 0..args_len {
+                command.push(to_os_str(*args.add(i))?);
+            }
+        }
+
+        let status = wine.run_args_with_stdin(command, WineStdin::Null)?.wait()?;
+
+        status.code().ok_or_else(|| Error::other("Process was killed by signal"))
+    })
+}
+
+#[cfg(feature = "dxvk")]
+/// Install DXVK from the given folder with default parameters
+///
+/// Returns `0` on success
+///
+/// # Safety
+///
+/// `runner` must be a valid pointer returned by `wincompat_runner_new`, and `dxvk_folder` a valid null-terminated string
+#[no_mangle]
+pub unsafe extern "C" fn wincompat_runner_install_dxvk(runner: *const Wine, dxvk_folder: *const c_char) -> c_int {
+    to_c_result(|| {
+        to_wine(runner)?.install_dxvk(to_os_str(dxvk_folder)?, InstallParams::default())?;
+
+        Ok(0)
+    })
+}
+
+/// Get message of the last error happened in the current thread
+///
+/// Returns null pointer if there were no errors. Returned string must be freed with `wincompat_string_free`
+#[no_mangle]
+pub extern "C" fn wincompat_last_error() -> *mut c_char {
+    guard(std::ptr::null_mut(), || LAST_ERROR.with(|last| {
+        last.borrow()
+            .as_ref()
+            .map(|message| message.clone().into_raw())
+            .unwrap_or(std::ptr::null_mut())
+    }))
+}
+
+/// Free string returned by the library
+///
+/// # Safety
+///
+/// `string` must be either null or a pointer returned by this library which wasn't freed yet
+#[no_mangle]
+pub unsafe extern "C" fn wincompat_string_free(string: *mut c_char) {
+    guard((), || {
+        if !string.is_null() {
+            drop(CString::from_raw(string));
+        }
+    })
+}
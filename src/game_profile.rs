@@ -0,0 +1,302 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::Child;
+use std::io::{Error, ErrorKind, Result};
+
+use serde::{Serialize, Deserialize};
+
+use crate::wine::*;
+use crate::prefix::PrefixConfig;
+use crate::sources::Sources;
+
+/// Program running the game, see `Wrapper`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ProfileWrapper {
+    pub program: PathBuf,
+
+    #[serde(default)]
+    pub args: Vec<String>
+}
+
+impl From<&ProfileWrapper> for Wrapper {
+    fn from(wrapper: &ProfileWrapper) -> Self {
+        Wrapper::new(&wrapper.program).with_args(&wrapper.args)
+    }
+}
+
+/// Everything needed to run the game: runner, prefix, environment, wrappers and components
+///
+/// Supported components are `dxvk` (with the DXVK version) and `vulkan-translation`
+/// (with the `VulkanProfile` name). Their builds are downloaded using given `Sources`
+///
+/// ```no_run
+/// use wincompatlib::prelude::*;
+///
+/// let mut profile = GameProfile::new("my-game", "/path/to/wine", "/path/to/prefix");
+///
+/// profile.env.insert(String::from("DXVK_HUD"), String::from("fps"));
+/// profile.components.insert(String::from("dxvk"), String::from("2.3"));
+///
+/// profile.save("/path/to/my-game.toml").expect("Failed to save profile");
+///
+/// profile.ensure_ready(&Sources::default(), |current, total, message| println!("[{current}/{total}] {message}"))
+///     .expect("Failed to prepare prefix");
+///
+/// profile.launch("/path/to/game.exe")
+///     .expect("Failed to launch game")
+///     .wait()
+///     .expect("Failed to wait for game");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameProfile {
+    pub name: String,
+
+    /// Path to the wine binary
+    pub runner: PathBuf,
+
+    pub prefix: PathBuf,
+
+    /// Environment variables set when launching the game
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+
+    /// Programs running the game, from the outermost one
+    #[serde(default)]
+    pub wrappers: Vec<ProfileWrapper>,
+
+    /// Components which must be installed to the prefix and their versions
+    #[serde(default)]
+    pub components: BTreeMap<String, String>,
+
+    /// Folder to download components builds to. Temp folder is used by default
+    #[serde(default)]
+    pub builds: Option<PathBuf>,
+
+    /// Environment variables inherited from `ProfileDefaults` which shouldn't be set
+    #[serde(default)]
+    pub unset_env: Vec<String>
+}
+
+/// Settings shared by all the game profiles, e.g. always enabled fsync and MangoHud
+///
+/// ```
+/// use wincompatlib::prelude::*;
+///
+/// let mut defaults = ProfileDefaults::default();
+///
+/// defaults.env.insert(String::from("WINEFSYNC"), String::from("1"));
+/// defaults.env.insert(String::from("DXVK_HUD"), String::from("fps"));
+///
+/// let mut profile = GameProfile::new("my-game", "/path/to/wine", "/path/to/prefix");
+///
+/// profile.env.insert(String::from("DXVK_HUD"), String::from("full"));
+///
+/// let resolved = profile.resolve(&defaults);
+///
+/// assert_eq!(resolved.env["WINEFSYNC"], "1");
+/// assert_eq!(resolved.env["DXVK_HUD"], "full");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProfileDefaults {
+    pub env: BTreeMap<String, String>,
+
+    /// Wrappers placed before the game's ones
+    pub wrappers: Vec<ProfileWrapper>,
+
+    pub components: BTreeMap<String, String>,
+
+    /// Used if the game profile doesn't specify its builds folder
+    pub builds: Option<PathBuf>
+}
+
+impl ProfileDefaults {
+    pub fn load<T: AsRef<Path>>(path: T) -> Result<Self> {
+        let defaults = std::fs::read_to_string(path)?;
+
+        toml::from_str(&defaults).map_err(|err| Error::new(ErrorKind::InvalidData, err))
+    }
+
+    pub fn save<T: AsRef<Path>>(&self, path: T) -> Result<()> {
+        let defaults = toml::to_string_pretty(self).map_err(|err| Error::new(ErrorKind::InvalidData, err))?;
+
+        std::fs::write(path, defaults)
+    }
+}
+
+impl GameProfile {
+    pub fn new<N: Into<String>, R: Into<PathBuf>, P: Into<PathBuf>>(name: N, runner: R, prefix: P) -> Self {
+        Self {
+            name: name.into(),
+            runner: runner.into(),
+            prefix: prefix.into(),
+            env: BTreeMap::new(),
+            wrappers: Vec::new(),
+            components: BTreeMap::new(),
+            builds: None,
+            unset_env: Vec::new()
+        }
+    }
+
+    /// Apply the game's settings on top of the defaults
+    ///
+    /// - Game's env variables and components override the default ones,
+    ///   variables listed in `unset_env` are removed
+    /// - Default wrappers go first. If the game specifies the same program,
+    ///   then only the game's wrapper is kept
+    pub fn resolve(&self, defaults: &ProfileDefaults) -> Self {
+        let mut env = defaults.env.clone();
+
+        env.extend(self.env.clone());
+
+        for name in &self.unset_env {
+            env.remove(name);
+        }
+
+        let mut components = defaults.components.clone();
+
+        components.extend(self.components.clone());
+
+        let wrappers = defaults.wrappers.iter()
+            .filter(|wrapper| !self.wrappers.iter().any(|game| game.program == wrapper.program))
+            .chain(self.wrappers.iter())
+            .cloned()
+            .collect();
+
+        Self {
+            name: self.name.clone(),
+            runner: self.runner.clone(),
+            prefix: self.prefix.clone(),
+            env,
+            wrappers,
+            components,
+            builds: self.builds.clone().or_else(|| defaults.builds.clone()),
+            unset_env: Vec::new()
+        }
+    }
+
+    pub fn load<T: AsRef<Path>>(path: T) -> Result<Self> {
+        let profile = std::fs::read_to_string(path)?;
+
+        toml::from_str(&profile).map_err(|err| Error::new(ErrorKind::InvalidData, err))
+    }
+
+    pub fn save<T: AsRef<Path>>(&self, path: T) -> Result<()> {
+        let profile = toml::to_string_pretty(self).map_err(|err| Error::new(ErrorKind::InvalidData, err))?;
+
+        std::fs::write(path, profile)
+    }
+
+    /// Get wine struct running the game's prefix through its wrappers
+    pub fn wine(&self) -> Wine {
+        self.wrappers.iter().fold(
+            Wine::from_binary(&self.runner).with_prefix(&self.prefix),
+            |wine, wrapper| wine.with_wrapper(wrapper.into())
+        )
+    }
+
+    /// Get components which are not installed to the prefix, or installed with different versions
+    pub fn missing_components(&self) -> Result<Vec<(String, String)>> {
+        let config = PrefixConfig::load_or_default(&self.prefix)?;
+
+        Ok(self.components.iter()
+            .filter(|(name, version)| config.components.get(*name) != Some(*version))
+            .map(|(name, version)| (name.clone(), version.clone()))
+            .collect())
+    }
+
+    /// Create the prefix if needed and install missing components
+    ///
+    /// Builds of the components are downloaded from `sources`. `progress` is called
+    /// with the current step, total steps number and step description
+    pub fn ensure_ready<F: Fn(u64, u64, &str)>(&self, sources: &Sources, progress: F) -> Result<()> {
+        let wine = self.wine();
+
+        let components = self.missing_components()?;
+
+        let total = components.len() as u64 + 1;
+
+        if !self.prefix.join("system.reg").exists() {
+            progress(0, total, "Creating wine prefix");
+
+            wine.update_prefix(&self.prefix)?;
+        }
+
+        let builds = self.builds.clone()
+            .unwrap_or_else(|| std::env::temp_dir().join("wincompatlib-builds"));
+
+        for (i, (name, version)) in components.iter().enumerate() {
+            progress(i as u64 + 1, total, &format!("Installing {name} {version}"));
+
+            install_component(&wine, sources, name, version, &builds)?;
+        }
+
+        progress(total, total, "Prefix is ready");
+
+        Ok(())
+    }
+
+    /// Launch the game with the profile's environment
+    pub fn launch<T: AsRef<std::ffi::OsStr>>(&self, exe: T) -> Result<Child> {
+        Ok(self.wine().run_with_scoped_env(&self.env, exe)?)
+    }
+
+    /// Resolve the profile on top of the defaults, prepare the prefix and launch the game
+    pub fn launch_with_defaults<T, F>(&self, defaults: &ProfileDefaults, sources: &Sources, exe: T, progress: F) -> Result<Child>
+    where
+        T: AsRef<std::ffi::OsStr>,
+        F: Fn(u64, u64, &str)
+    {
+        let profile = self.resolve(defaults);
+
+        profile.ensure_ready(sources, progress)?;
+        profile.launch(exe)
+    }
+}
+
+#[cfg(feature = "dxvk")]
+fn install_component(wine: &Wine, sources: &Sources, name: &str, version: &str, builds: &Path) -> Result<()> {
+    use crate::dxvk::{Dxvk, Arch, InstallParams};
+    use crate::vulkan_translation::{VulkanProfile, VulkanTranslation};
+
+    match name {
+        "dxvk" => {
+            let folder = sources.download_build("dxvk", version, builds)?;
+
+            let archs = match WineArch::from_prefix(wine.prefix_path())? {
+                Some(WineArch::Win32) => vec![Arch::Win32],
+                _ => vec![Arch::Win64, Arch::Win32]
+            };
+
+            for arch in archs {
+                Dxvk::install(wine, &folder, InstallParams {
+                    arch,
+                    repair_dlls: false,
+                    ..InstallParams::default()
+                })?;
+            }
+
+            let mut config = PrefixConfig::load_or_default(wine.prefix_path())?;
+
+            config.components.insert(String::from("dxvk"), version.to_string());
+
+            config.save(wine.prefix_path())
+        }
+
+        "vulkan-translation" => {
+            let profile = VulkanProfile::get(version)
+                .ok_or_else(|| Error::new(ErrorKind::NotFound, format!("Unknown vulkan translation profile: {version}")))?;
+
+            profile.download(sources, builds)?;
+
+            VulkanTranslation::install(wine, &profile, builds)
+        }
+
+        _ => Err(Error::new(ErrorKind::Unsupported, format!("Unsupported component: {name}")))
+    }
+}
+
+#[cfg(not(feature = "dxvk"))]
+fn install_component(_wine: &Wine, _sources: &Sources, name: &str, _version: &str, _builds: &Path) -> Result<()> {
+    Err(Error::new(ErrorKind::Unsupported, format!("Unsupported component: {name}")))
+}
@@ -0,0 +1,240 @@
+use std::path::Path;
+use std::io::{Error, ErrorKind, Result};
+
+use crate::pe::*;
+use crate::checksum::{crc32, adler32};
+
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+/// Icon extracted from the windows executable
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Icon {
+    pub width: u32,
+    pub height: u32,
+
+    /// PNG encoded image
+    pub png: Vec<u8>
+}
+
+impl Icon {
+    /// Save icon as PNG file
+    pub fn save<T: AsRef<Path>>(&self, path: T) -> Result<()> {
+        std::fs::write(path, &self.png)
+    }
+}
+
+/// Extract the largest icon of the executable
+///
+/// Returns `Ok(None)` if executable has no icons
+///
+/// ```no_run
+/// use wincompatlib::prelude::*;
+///
+/// if let Some(icon) = extract_icon("/path/to/game.exe").expect("Failed to read executable") {
+///     println!("Found {}x{} icon", icon.width, icon.height);
+///
+///     icon.save("/path/to/game.png").expect("Failed to save icon");
+/// }
+/// ```
+pub fn extract_icon<T: AsRef<Path>>(exe: T) -> Result<Option<Icon>> {
+    let exe = PeFile::open(exe)?;
+
+    let groups = exe.resources(RT_GROUP_ICON)?;
+
+    // First group is used by windows as the application icon
+    let Some(group) = groups.first() else {
+        return Ok(None);
+    };
+
+    // GRPICONDIR: reserved, type, count and 14 bytes entries
+    let count = read_u16(&group.data, 4)? as usize;
+
+    let mut best: Option<(u32, u16, u16)> = None;
+
+    for i in 0..count {
+        let entry = 6 + i * 14;
+
+        // 0 means 256 pixels
+        let width = match group.data.get(entry) {
+            Some(0) => 256,
+            Some(width) => *width as u32,
+            None => break
+        };
+
+        let bit_count = read_u16(&group.data, entry + 6)?;
+        let id = read_u16(&group.data, entry + 12)?;
+
+        if best.map(|(best_width, best_bits, _)| (width, bit_count) > (best_width, best_bits)).unwrap_or(true) {
+            best = Some((width, bit_count, id));
+        }
+    }
+
+    let Some((_, _, id)) = best else {
+        return Ok(None);
+    };
+
+    let icons = exe.resources(RT_ICON)?;
+
+    let Some(icon) = icons.iter().find(|icon| icon.id == Some(id as u32)) else {
+        return Ok(None);
+    };
+
+    decode_icon(&icon.data).map(Some)
+}
+
+/// Convert icon image (PNG or DIB) to PNG
+fn decode_icon(data: &[u8]) -> Result<Icon> {
+    // Large icons are stored as PNG files
+    if data.starts_with(PNG_SIGNATURE) {
+        let width = u32::from_be_bytes(data.get(16..20).and_then(|bytes| bytes.try_into().ok()).unwrap_or_default());
+        let height = u32::from_be_bytes(data.get(20..24).and_then(|bytes| bytes.try_into().ok()).unwrap_or_default());
+
+        return Ok(Icon {
+            width,
+            height,
+            png: data.to_vec()
+        });
+    }
+
+    let (width, height, rgba) = decode_dib(data)?;
+
+    Ok(Icon {
+        width,
+        height,
+        png: encode_png(width, height, &rgba)
+    })
+}
+
+/// Decode icon bitmap (BITMAPINFOHEADER, XOR and AND masks) to RGBA pixels
+fn decode_dib(data: &[u8]) -> Result<(u32, u32, Vec<u8>)> {
+    let header_size = read_u32(data, 0)? as usize;
+
+    let width = read_u32(data, 4)? as i32;
+
+    // Height includes both XOR and AND masks
+    let height = read_u32(data, 8)? as i32 / 2;
+
+    let bit_count = read_u16(data, 14)? as usize;
+    let colors_used = read_u32(data, 32)? as usize;
+
+    if width <= 0 || height <= 0 || width > 1024 || height > 1024 {
+        return Err(Error::new(ErrorKind::InvalidData, "Unsupported icon size"));
+    }
+
+    let (width, height) = (width as usize, height as usize);
+
+    let palette_size = match bit_count {
+        1 | 4 | 8 if colors_used == 0 => 1 << bit_count,
+        1 | 4 | 8 => colors_used,
+        24 | 32 => 0,
+
+        _ => return Err(Error::new(ErrorKind::InvalidData, format!("Unsupported icon bit count: {bit_count}")))
+    };
+
+    let palette = header_size;
+    let pixels = palette + palette_size * 4;
+
+    let xor_stride = (width * bit_count).div_ceil(32) * 4;
+    let and_stride = width.div_ceil(32) * 4;
+
+    let mask = pixels + xor_stride * height;
+
+    let byte = |offset: usize| data.get(offset).copied()
+        .ok_or_else(|| Error::new(ErrorKind::InvalidData, "Unexpected end of icon data"));
+
+    let mut rgba = vec![0; width * height * 4];
+    let mut has_alpha = false;
+
+    for y in 0..height {
+        // Rows are stored bottom-up
+        let row = pixels + (height - y - 1) * xor_stride;
+
+        for x in 0..width {
+            let (b, g, r, a) = match bit_count {
+                32 => (byte(row + x * 4)?, byte(row + x * 4 + 1)?, byte(row + x * 4 + 2)?, byte(row + x * 4 + 3)?),
+                24 => (byte(row + x * 3)?, byte(row + x * 3 + 1)?, byte(row + x * 3 + 2)?, 0),
+
+                _ => {
+                    let bit = x * bit_count;
+                    let index = (byte(row + bit / 8)? >> (8 - bit_count - bit % 8)) & ((1 << bit_count) - 1) as u8;
+                    let color = palette + index as usize * 4;
+
+                    (byte(color)?, byte(color + 1)?, byte(color + 2)?, 0)
+                }
+            };
+
+            has_alpha |= a != 0;
+
+            let pixel = (y * width + x) * 4;
+
+            rgba[pixel..pixel + 4].copy_from_slice(&[r, g, b, a]);
+        }
+    }
+
+    // Apply AND mask transparency if image doesn't have alpha channel
+    if !has_alpha {
+        for y in 0..height {
+            let row = mask + (height - y - 1) * and_stride;
+
+            for x in 0..width {
+                let transparent = (byte(row + x / 8).unwrap_or(0) >> (7 - x % 8)) & 1 == 1;
+
+                rgba[(y * width + x) * 4 + 3] = if transparent { 0 } else { 255 };
+            }
+        }
+    }
+
+    Ok((width as u32, height as u32, rgba))
+}
+
+/// Encode RGBA pixels as PNG file using uncompressed deflate blocks
+fn encode_png(width: u32, height: u32, rgba: &[u8]) -> Vec<u8> {
+    fn chunk(png: &mut Vec<u8>, name: &[u8], data: &[u8]) {
+        png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+
+        let start = png.len();
+
+        png.extend_from_slice(name);
+        png.extend_from_slice(data);
+
+        let crc = crc32(&png[start..]);
+
+        png.extend_from_slice(&crc.to_be_bytes());
+    }
+
+    let mut png = PNG_SIGNATURE.to_vec();
+
+    // Width, height, 8 bits depth, RGBA color type, default compression, filter and interlace
+    let mut header = Vec::with_capacity(13);
+
+    header.extend_from_slice(&width.to_be_bytes());
+    header.extend_from_slice(&height.to_be_bytes());
+    header.extend_from_slice(&[8, 6, 0, 0, 0]);
+
+    chunk(&mut png, b"IHDR", &header);
+
+    // Each row starts with "none" filter type
+    let mut raw = Vec::with_capacity(rgba.len() + height as usize);
+
+    for row in rgba.chunks(width as usize * 4) {
+        raw.push(0);
+        raw.extend_from_slice(row);
+    }
+
+    let mut zlib = vec![0x78, 0x01];
+    let mut blocks = raw.chunks(65535).peekable();
+
+    while let Some(block) = blocks.next() {
+        zlib.push(if blocks.peek().is_none() { 1 } else { 0 });
+        zlib.extend_from_slice(&(block.len() as u16).to_le_bytes());
+        zlib.extend_from_slice(&(!(block.len() as u16)).to_le_bytes());
+        zlib.extend_from_slice(block);
+    }
+
+    zlib.extend_from_slice(&adler32(&raw).to_be_bytes());
+
+    chunk(&mut png, b"IDAT", &zlib);
+    chunk(&mut png, b"IEND", &[]);
+
+    png
+}
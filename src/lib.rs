@@ -1,14 +1,71 @@
+pub mod error;
 pub mod wine;
+pub mod prefix;
+pub mod events;
+pub mod warning;
+pub mod queue;
+pub mod desktop;
+pub mod pe;
+pub mod icon;
+pub mod lnk;
+pub mod proton;
+pub mod steam;
+pub mod sources;
+pub mod save_sync;
+pub mod game_profile;
+pub mod runner;
+pub mod compat;
+pub mod support;
+
+mod checksum;
+mod xdg;
+
+pub use error::{Error, Result};
 
 #[cfg(feature = "dxvk")]
 pub mod dxvk;
 
+#[cfg(feature = "dxvk")]
+pub mod dxvk_log;
+
+#[cfg(feature = "dxvk")]
+pub mod vulkan_translation;
+
+#[cfg(feature = "display")]
+pub mod display;
+
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
 #[cfg(test)]
 mod test;
 
 pub mod prelude {
     pub use super::wine::*;
+    pub use super::prefix::*;
+    pub use super::events::*;
+    pub use super::warning::*;
+    pub use super::queue::*;
+    pub use super::desktop::*;
+    pub use super::icon::*;
+    pub use super::lnk::*;
+    pub use super::proton::*;
+    pub use super::steam::*;
+    pub use super::sources::*;
+    pub use super::save_sync::*;
+    pub use super::game_profile::*;
+    pub use super::runner::*;
+    pub use super::support::*;
 
     #[cfg(feature = "dxvk")]
     pub use super::dxvk::*;
+
+    #[cfg(feature = "dxvk")]
+    pub use super::dxvk_log::*;
+
+    #[cfg(feature = "dxvk")]
+    pub use super::vulkan_translation::*;
+
+    #[cfg(feature = "display")]
+    pub use super::display::*;
 }
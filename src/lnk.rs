@@ -0,0 +1,237 @@
+use std::path::{Path, PathBuf};
+use std::io::{Error, ErrorKind, Result};
+
+use crate::pe::{read_u16, read_u32};
+
+const HAS_LINK_TARGET_ID_LIST: u32 = 0x01;
+const HAS_LINK_INFO: u32           = 0x02;
+const HAS_NAME: u32                = 0x04;
+const HAS_RELATIVE_PATH: u32       = 0x08;
+const HAS_WORKING_DIR: u32         = 0x10;
+const HAS_ARGUMENTS: u32           = 0x20;
+const HAS_ICON_LOCATION: u32       = 0x40;
+const IS_UNICODE: u32              = 0x80;
+
+/// Windows shortcut (`.lnk` file)
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Shortcut {
+    /// Windows path to the target file, e.g. `C:\Games\Game\game.exe`
+    pub target: Option<String>,
+
+    pub description: Option<String>,
+
+    /// Path to the target relative to the shortcut file
+    pub relative_path: Option<String>,
+
+    pub working_dir: Option<String>,
+    pub arguments: Option<String>,
+
+    /// Windows path to the file containing the icon
+    pub icon_location: Option<String>,
+    pub icon_index: i32
+}
+
+/// Read null-terminated single byte string
+fn read_cstr(bytes: &[u8], offset: usize) -> Option<String> {
+    let bytes = bytes.get(offset..)?;
+    let end = bytes.iter().position(|byte| *byte == 0)?;
+
+    Some(String::from_utf8_lossy(&bytes[..end]).to_string())
+}
+
+/// Read null-terminated UTF-16 string
+fn read_wstr(bytes: &[u8], offset: usize) -> Option<String> {
+    let mut chars = Vec::new();
+
+    for pair in bytes.get(offset..)?.chunks_exact(2) {
+        match u16::from_le_bytes([pair[0], pair[1]]) {
+            0 => return Some(String::from_utf16_lossy(&chars)),
+            char => chars.push(char)
+        }
+    }
+
+    None
+}
+
+impl Shortcut {
+    /// Parse shortcut file
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// let shortcut = Shortcut::open("/path/to/prefix/drive_c/users/Public/Desktop/Game.lnk")
+    ///     .expect("Failed to parse shortcut");
+    ///
+    /// println!("Target: {:?}, arguments: {:?}", shortcut.target, shortcut.arguments);
+    /// ```
+    pub fn open<T: AsRef<Path>>(path: T) -> Result<Self> {
+        Self::parse(&std::fs::read(path)?)
+    }
+
+    pub fn parse(bytes: &[u8]) -> Result<Self> {
+        if read_u32(bytes, 0)? != 0x4C {
+            return Err(Error::new(ErrorKind::InvalidData, "Not a windows shortcut file"));
+        }
+
+        let flags = read_u32(bytes, 20)?;
+
+        let mut shortcut = Self {
+            icon_index: read_u32(bytes, 56)? as i32,
+            ..Self::default()
+        };
+
+        let mut offset = 0x4C;
+
+        if flags & HAS_LINK_TARGET_ID_LIST != 0 {
+            offset += 2 + read_u16(bytes, offset)? as usize;
+        }
+
+        if flags & HAS_LINK_INFO != 0 {
+            let info_size = read_u32(bytes, offset)? as usize;
+            let header_size = read_u32(bytes, offset + 4)? as usize;
+            let info_flags = read_u32(bytes, offset + 8)?;
+
+            // VolumeIDAndLocalBasePath
+            if info_flags & 1 != 0 {
+                shortcut.target = if header_size >= 0x24 {
+                    read_wstr(bytes, offset + read_u32(bytes, offset + 28)? as usize)
+                } else {
+                    read_cstr(bytes, offset + read_u32(bytes, offset + 16)? as usize)
+                };
+            }
+
+            offset += info_size;
+        }
+
+        let unicode = flags & IS_UNICODE != 0;
+
+        let mut read_string = |flag: u32| -> Result<Option<String>> {
+            if flags & flag == 0 {
+                return Ok(None);
+            }
+
+            let length = read_u16(bytes, offset)? as usize;
+
+            offset += 2;
+
+            let string = if unicode {
+                let data = bytes.get(offset..offset + length * 2)
+                    .ok_or_else(|| Error::new(ErrorKind::InvalidData, "Unexpected end of file"))?;
+
+                offset += length * 2;
+
+                String::from_utf16_lossy(&data.chunks_exact(2).map(|pair| u16::from_le_bytes([pair[0], pair[1]])).collect::<Vec<_>>())
+            } else {
+                let data = bytes.get(offset..offset + length)
+                    .ok_or_else(|| Error::new(ErrorKind::InvalidData, "Unexpected end of file"))?;
+
+                offset += length;
+
+                String::from_utf8_lossy(data).to_string()
+            };
+
+            Ok(Some(string))
+        };
+
+        shortcut.description   = read_string(HAS_NAME)?;
+        shortcut.relative_path = read_string(HAS_RELATIVE_PATH)?;
+        shortcut.working_dir   = read_string(HAS_WORKING_DIR)?;
+        shortcut.arguments     = read_string(HAS_ARGUMENTS)?;
+        shortcut.icon_location = read_string(HAS_ICON_LOCATION)?;
+
+        Ok(shortcut)
+    }
+
+    /// Get unix path to the shortcut target inside of the wine prefix
+    ///
+    /// ```
+    /// use wincompatlib::prelude::*;
+    ///
+    /// use std::path::PathBuf;
+    ///
+    /// let shortcut = Shortcut {
+    ///     target: Some(String::from("C:\\Games\\Game\\game.exe")),
+    ///     ..Shortcut::default()
+    /// };
+    ///
+    /// assert_eq!(shortcut.unix_target("/path/to/prefix"), Some(PathBuf::from("/path/to/prefix/dosdevices/c:/Games/Game/game.exe")));
+    /// ```
+    pub fn unix_target<T: AsRef<Path>>(&self, prefix: T) -> Option<PathBuf> {
+        self.target.as_ref().and_then(|target| windows_to_unix_path(prefix, target))
+    }
+}
+
+/// Convert windows path to the unix one using `dosdevices` folder of the wine prefix
+///
+/// Returns `None` if path doesn't start with a drive letter
+pub fn windows_to_unix_path<T: AsRef<Path>>(prefix: T, path: &str) -> Option<PathBuf> {
+    let mut chars = path.chars();
+
+    let drive = chars.next()?.to_ascii_lowercase();
+
+    if !drive.is_ascii_alphabetic() || chars.next()? != ':' {
+        return None;
+    }
+
+    let mut unix_path = prefix.as_ref().join("dosdevices").join(format!("{drive}:"));
+
+    for part in path[2..].split('\\').filter(|part| !part.is_empty()) {
+        unix_path.push(part);
+    }
+
+    Some(unix_path)
+}
+
+fn find_lnk_files(folder: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = folder.read_dir() else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+
+        if path.is_dir() {
+            find_lnk_files(&path, files);
+        }
+
+        else if path.extension().map(|ext| ext.eq_ignore_ascii_case("lnk")).unwrap_or(false) {
+            files.push(path);
+        }
+    }
+}
+
+/// Find shortcuts created by installers in Start Menu and Desktop folders of the wine prefix
+///
+/// Files which failed to parse are skipped
+///
+/// ```no_run
+/// use wincompatlib::prelude::*;
+///
+/// for (path, shortcut) in find_shortcuts("/path/to/prefix") {
+///     println!("{:?} -> {:?}", path, shortcut.unix_target("/path/to/prefix"));
+/// }
+/// ```
+pub fn find_shortcuts<T: AsRef<Path>>(prefix: T) -> Vec<(PathBuf, Shortcut)> {
+    let drive_c = prefix.as_ref().join("drive_c");
+
+    let mut folders = vec![
+        drive_c.join("ProgramData/Microsoft/Windows/Start Menu")
+    ];
+
+    if let Ok(users) = drive_c.join("users").read_dir() {
+        for user in users.flatten() {
+            folders.push(user.path().join("AppData/Roaming/Microsoft/Windows/Start Menu"));
+            folders.push(user.path().join("Desktop"));
+        }
+    }
+
+    let mut files = Vec::new();
+
+    for folder in folders {
+        find_lnk_files(&folder, &mut files);
+    }
+
+    files.into_iter()
+        .filter_map(|path| Shortcut::open(&path).ok().map(|shortcut| (path, shortcut)))
+        .collect()
+}
@@ -0,0 +1,388 @@
+use std::path::Path;
+use std::io::{Error, ErrorKind, Result};
+
+/// `RT_ICON` resource type
+pub const RT_ICON: u32 = 3;
+
+/// `RT_GROUP_ICON` resource type
+pub const RT_GROUP_ICON: u32 = 14;
+
+/// Libraries distributed by winetricks verbs: (library prefix, verb)
+const WINETRICKS_VERBS: &[(&str, &str)] = &[
+    ("msvcp140",       "vcrun2022"),
+    ("vcruntime140",   "vcrun2022"),
+    ("msvcp120",       "vcrun2013"),
+    ("msvcr120",       "vcrun2013"),
+    ("msvcp110",       "vcrun2012"),
+    ("msvcr110",       "vcrun2012"),
+    ("msvcp100",       "vcrun2010"),
+    ("msvcr100",       "vcrun2010"),
+    ("msvcp90",        "vcrun2008"),
+    ("msvcr90",        "vcrun2008"),
+    ("d3dx9_",         "d3dx9"),
+    ("d3dx10_",        "d3dx10"),
+    ("d3dx11_",        "d3dx11_43"),
+    ("d3dcompiler_43", "d3dcompiler_43"),
+    ("d3dcompiler_47", "d3dcompiler_47"),
+    ("xinput1_",       "xinput"),
+    ("xact",           "xact"),
+    ("x3daudio",       "xact"),
+    ("physxloader",    "physx"),
+    ("mfplat",         "mf"),
+    ("mfreadwrite",    "mf"),
+    ("mscoree",        "dotnet48")
+];
+
+fn invalid(message: &str) -> Error {
+    Error::new(ErrorKind::InvalidData, message.to_string())
+}
+
+pub(crate) fn read_u16(bytes: &[u8], offset: usize) -> Result<u16> {
+    bytes.get(offset..offset + 2)
+        .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]))
+        .ok_or_else(|| invalid("Unexpected end of file"))
+}
+
+pub(crate) fn read_u32(bytes: &[u8], offset: usize) -> Result<u32> {
+    bytes.get(offset..offset + 4)
+        .map(|bytes| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+        .ok_or_else(|| invalid("Unexpected end of file"))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section {
+    pub name: String,
+    pub virtual_address: u32,
+    pub virtual_size: u32,
+    pub raw_offset: u32,
+    pub raw_size: u32
+}
+
+/// Resource stored in the executable
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Resource {
+    /// Numeric id of the resource, or `None` if it's named
+    pub id: Option<u32>,
+    pub data: Vec<u8>
+}
+
+/// Parsed windows executable (PE) file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PeFile {
+    bytes: Vec<u8>,
+
+    /// `IMAGE_FILE_MACHINE_*` value from the COFF header
+    pub machine: u16,
+
+    /// `true` for PE32+ files
+    pub is_64bit: bool,
+
+    pub sections: Vec<Section>,
+
+    /// Data directories entries: (rva, size)
+    pub data_directories: Vec<(u32, u32)>
+}
+
+impl PeFile {
+    /// Parse PE file
+    ///
+    /// ```no_run
+    /// use wincompatlib::pe::PeFile;
+    ///
+    /// let exe = PeFile::open("/path/to/game.exe").expect("Failed to parse executable");
+    ///
+    /// println!("64 bit: {}", exe.is_64bit);
+    /// ```
+    pub fn open<T: AsRef<Path>>(path: T) -> Result<Self> {
+        Self::parse(std::fs::read(path)?)
+    }
+
+    pub fn parse(bytes: Vec<u8>) -> Result<Self> {
+        if bytes.get(..2) != Some(b"MZ") {
+            return Err(invalid("Not a windows executable: missing MZ signature"));
+        }
+
+        let pe_offset = read_u32(&bytes, 0x3C)? as usize;
+
+        if bytes.get(pe_offset..pe_offset + 4) != Some(b"PE\0\0") {
+            return Err(invalid("Not a windows executable: missing PE signature"));
+        }
+
+        let coff = pe_offset + 4;
+
+        let machine = read_u16(&bytes, coff)?;
+        let sections_number = read_u16(&bytes, coff + 2)? as usize;
+        let optional_size = read_u16(&bytes, coff + 16)? as usize;
+
+        let optional = coff + 20;
+
+        let is_64bit = match read_u16(&bytes, optional)? {
+            0x10b => false,
+            0x20b => true,
+
+            _ => return Err(invalid("Unknown PE optional header format"))
+        };
+
+        let (directories_number, directories_offset) = match is_64bit {
+            false => (read_u32(&bytes, optional + 92)?, optional + 96),
+            true  => (read_u32(&bytes, optional + 108)?, optional + 112)
+        };
+
+        let mut data_directories = Vec::new();
+
+        for i in 0..directories_number.min(16) as usize {
+            let offset = directories_offset + i * 8;
+
+            data_directories.push((read_u32(&bytes, offset)?, read_u32(&bytes, offset + 4)?));
+        }
+
+        let mut sections = Vec::with_capacity(sections_number);
+
+        for i in 0..sections_number {
+            let offset = optional + optional_size + i * 40;
+
+            let name = bytes.get(offset..offset + 8)
+                .ok_or_else(|| invalid("Unexpected end of file"))?;
+
+            sections.push(Section {
+                name: String::from_utf8_lossy(name).trim_end_matches('\0').to_string(),
+                virtual_size: read_u32(&bytes, offset + 8)?,
+                virtual_address: read_u32(&bytes, offset + 12)?,
+                raw_size: read_u32(&bytes, offset + 16)?,
+                raw_offset: read_u32(&bytes, offset + 20)?
+            });
+        }
+
+        Ok(Self {
+            bytes,
+            machine,
+            is_64bit,
+            sections,
+            data_directories
+        })
+    }
+
+    /// Get raw file bytes
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Convert relative virtual address to the file offset
+    pub fn rva_to_offset(&self, rva: u32) -> Option<usize> {
+        self.sections.iter()
+            .find(|section| rva >= section.virtual_address && rva < section.virtual_address.saturating_add(section.virtual_size.max(section.raw_size)))
+            .map(|section| (rva - section.virtual_address) as usize + section.raw_offset as usize)
+    }
+
+    /// Get data directory (rva, size) by its index, if it's present
+    pub fn data_directory(&self, index: usize) -> Option<(u32, u32)> {
+        self.data_directories.get(index)
+            .copied()
+            .filter(|(rva, size)| *rva != 0 && *size != 0)
+    }
+
+    /// Read null-terminated string at the given relative virtual address
+    fn read_string(&self, rva: u32) -> Result<String> {
+        let offset = self.rva_to_offset(rva)
+            .ok_or_else(|| invalid("Invalid string address"))?;
+
+        let bytes = self.bytes.get(offset..)
+            .ok_or_else(|| invalid("Unexpected end of file"))?;
+
+        let end = bytes.iter().position(|byte| *byte == 0)
+            .ok_or_else(|| invalid("Unexpected end of file"))?;
+
+        Ok(String::from_utf8_lossy(&bytes[..end]).to_string())
+    }
+
+    /// Get names of the libraries imported by the executable, e.g. `KERNEL32.dll`
+    pub fn imports(&self) -> Result<Vec<String>> {
+        // IMAGE_DIRECTORY_ENTRY_IMPORT
+        let Some((rva, _)) = self.data_directory(1) else {
+            return Ok(Vec::new());
+        };
+
+        let mut offset = self.rva_to_offset(rva)
+            .ok_or_else(|| invalid("Invalid imports directory address"))?;
+
+        let mut imports = Vec::new();
+
+        // IMAGE_IMPORT_DESCRIPTOR entries with the name address at 12 bytes offset,
+        // terminated by zeroed entry
+        loop {
+            let name = read_u32(&self.bytes, offset + 12)?;
+
+            if name == 0 {
+                break;
+            }
+
+            imports.push(self.read_string(name)?);
+
+            offset += 20;
+        }
+
+        Ok(imports)
+    }
+
+    /// Get names of the libraries loaded by the executable only when they're used
+    pub fn delay_imports(&self) -> Result<Vec<String>> {
+        // IMAGE_DIRECTORY_ENTRY_DELAY_IMPORT
+        let Some((rva, _)) = self.data_directory(13) else {
+            return Ok(Vec::new());
+        };
+
+        let mut offset = self.rva_to_offset(rva)
+            .ok_or_else(|| invalid("Invalid delay imports directory address"))?;
+
+        let mut imports = Vec::new();
+
+        // IMAGE_DELAYLOAD_DESCRIPTOR entries with the name address at 4 bytes offset
+        loop {
+            let name = read_u32(&self.bytes, offset + 4)?;
+
+            if name == 0 {
+                break;
+            }
+
+            imports.push(self.read_string(name)?);
+
+            offset += 32;
+        }
+
+        Ok(imports)
+    }
+
+    /// Get all resources of the given type (e.g. `RT_ICON`)
+    ///
+    /// Only the first language of each resource is returned
+    pub fn resources(&self, resource_type: u32) -> Result<Vec<Resource>> {
+        // IMAGE_DIRECTORY_ENTRY_RESOURCE
+        let Some((rva, _)) = self.data_directory(2) else {
+            return Ok(Vec::new());
+        };
+
+        let root = self.rva_to_offset(rva)
+            .ok_or_else(|| invalid("Invalid resources directory address"))?;
+
+        let mut resources = Vec::new();
+
+        for (type_id, type_entry) in self.resource_entries(root)? {
+            let Some(type_directory) = subdirectory(root, type_entry) else {
+                continue;
+            };
+
+            if type_id != Some(resource_type) {
+                continue;
+            }
+
+            for (id, name_entry) in self.resource_entries(type_directory)? {
+                let Some(name_directory) = subdirectory(root, name_entry) else {
+                    continue;
+                };
+
+                // Take first language only
+                let Some((_, data_entry)) = self.resource_entries(name_directory)?.into_iter().next() else {
+                    continue;
+                };
+
+                // Language entry must point to the data, not to another directory
+                if subdirectory(root, data_entry).is_some() {
+                    continue;
+                }
+
+                let data_entry = root + data_entry as usize;
+
+                let data_rva = read_u32(&self.bytes, data_entry)?;
+                let data_size = read_u32(&self.bytes, data_entry + 4)? as usize;
+
+                let data_offset = self.rva_to_offset(data_rva)
+                    .ok_or_else(|| invalid("Invalid resource data address"))?;
+
+                let data = self.bytes.get(data_offset..data_offset + data_size)
+                    .ok_or_else(|| invalid("Unexpected end of file"))?;
+
+                resources.push(Resource {
+                    id,
+                    data: data.to_vec()
+                });
+            }
+        }
+
+        Ok(resources)
+    }
+
+    /// Read entries of the resource directory: (id, offset)
+    ///
+    /// Id is `None` for named entries
+    fn resource_entries(&self, directory: usize) -> Result<Vec<(Option<u32>, u32)>> {
+        let named = read_u16(&self.bytes, directory + 12)? as usize;
+        let ids = read_u16(&self.bytes, directory + 14)? as usize;
+
+        let mut entries = Vec::with_capacity(named + ids);
+
+        for i in 0..named + ids {
+            let entry = directory + 16 + i * 8;
+
+            let name = read_u32(&self.bytes, entry)?;
+            let offset = read_u32(&self.bytes, entry + 4)?;
+
+            let id = if name & 0x80000000 == 0 { Some(name) } else { None };
+
+            entries.push((id, offset));
+        }
+
+        Ok(entries)
+    }
+}
+
+/// Get file offset of the resource subdirectory, or `None` if entry points to the data
+fn subdirectory(root: usize, offset: u32) -> Option<usize> {
+    if offset & 0x80000000 != 0 {
+        Some(root + (offset & 0x7FFFFFFF) as usize)
+    } else {
+        None
+    }
+}
+
+/// List libraries imported by the executable, both directly and with delayed loading
+///
+/// Duplicates are removed, original names case is kept
+///
+/// ```no_run
+/// use wincompatlib::pe::{list_imports, winetricks_verb};
+///
+/// for dll in list_imports("/path/to/game.exe").expect("Failed to read executable") {
+///     if let Some(verb) = winetricks_verb(&dll) {
+///         println!("{dll} is provided by winetricks {verb}");
+///     }
+/// }
+/// ```
+pub fn list_imports<T: AsRef<Path>>(exe: T) -> Result<Vec<String>> {
+    let exe = PeFile::open(exe)?;
+
+    let mut imports: Vec<String> = Vec::new();
+
+    for dll in exe.imports()?.into_iter().chain(exe.delay_imports()?) {
+        if !imports.iter().any(|import| import.eq_ignore_ascii_case(&dll)) {
+            imports.push(dll);
+        }
+    }
+
+    Ok(imports)
+}
+
+/// Get winetricks verb installing the library, if it's known
+///
+/// ```
+/// use wincompatlib::pe::winetricks_verb;
+///
+/// assert_eq!(winetricks_verb("D3DX9_43.dll"), Some("d3dx9"));
+/// assert_eq!(winetricks_verb("kernel32.dll"), None);
+/// ```
+pub fn winetricks_verb(dll: &str) -> Option<&'static str> {
+    let dll = dll.to_ascii_lowercase();
+
+    WINETRICKS_VERBS.iter()
+        .find(|(library, _)| dll.starts_with(library))
+        .map(|(_, verb)| *verb)
+}
@@ -0,0 +1,111 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::io::{Error, ErrorKind, Result};
+
+use serde::{Serialize, Deserialize};
+
+use crate::wine::{Wine, InstalledDllMod};
+
+/// Name of the configuration file stored in the root of the wine prefix
+pub const PREFIX_CONFIG_FILE: &str = "wincompatlib.toml";
+
+/// Configuration persisted inside of the wine prefix
+/// 
+/// Stored as `wincompatlib.toml` file so launcher settings survive
+/// even if the launcher's own database is lost
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PrefixConfig {
+    /// Path to the wine binary used to run this prefix
+    pub runner: Option<PathBuf>,
+
+    /// Environment variables to set when running this prefix
+    pub env: BTreeMap<String, String>,
+
+    /// Installed components and their versions, e.g. `dxvk = "2.1"`
+    pub components: BTreeMap<String, String>,
+
+    /// Dll-based mods installed to the games folders
+    pub dll_mods: Vec<InstalledDllMod>
+}
+
+impl PrefixConfig {
+    /// Create config with runner path taken from the wine struct
+    /// 
+    /// ```
+    /// use wincompatlib::prelude::*;
+    /// 
+    /// let config = PrefixConfig::from_wine(&Wine::from_binary("/path/to/wine"));
+    /// 
+    /// assert_eq!(config.runner, Some(std::path::PathBuf::from("/path/to/wine")));
+    /// ```
+    pub fn from_wine(wine: &Wine) -> Self {
+        Self {
+            runner: Some(wine.binary()),
+            ..Self::default()
+        }
+    }
+
+    /// Get path to the config file of the wine prefix
+    /// 
+    /// ```
+    /// use wincompatlib::prelude::*;
+    /// 
+    /// use std::path::PathBuf;
+    /// 
+    /// assert_eq!(PrefixConfig::path("/path/to/prefix"), PathBuf::from("/path/to/prefix/wincompatlib.toml"));
+    /// ```
+    pub fn path<T: AsRef<Path>>(prefix: T) -> PathBuf {
+        prefix.as_ref().join(PREFIX_CONFIG_FILE)
+    }
+
+    /// Load config from the wine prefix
+    /// 
+    /// Returns `ErrorKind::NotFound` error if prefix doesn't have config file
+    /// 
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    /// 
+    /// let config = PrefixConfig::load("/path/to/prefix")
+    ///     .expect("Failed to load prefix config");
+    /// 
+    /// println!("Installed components: {:?}", config.components);
+    /// ```
+    pub fn load<T: AsRef<Path>>(prefix: T) -> Result<Self> {
+        let config = std::fs::read_to_string(Self::path(prefix))?;
+
+        toml::from_str(&config).map_err(|err| Error::new(ErrorKind::InvalidData, err))
+    }
+
+    /// Load config from the wine prefix, or return default one if prefix doesn't have it
+    /// 
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    /// 
+    /// let config = PrefixConfig::load_or_default("/path/to/prefix")
+    ///     .expect("Failed to load prefix config");
+    /// ```
+    pub fn load_or_default<T: AsRef<Path>>(prefix: T) -> Result<Self> {
+        match Self::load(prefix) {
+            Ok(config) => Ok(config),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err)
+        }
+    }
+
+    /// Save config to the wine prefix
+    /// 
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    /// 
+    /// let mut config = PrefixConfig::from_wine(&Wine::from_binary("/path/to/wine"));
+    /// 
+    /// config.components.insert(String::from("dxvk"), String::from("2.1"));
+    /// config.save("/path/to/prefix").expect("Failed to save prefix config");
+    /// ```
+    pub fn save<T: AsRef<Path>>(&self, prefix: T) -> Result<()> {
+        let config = toml::to_string_pretty(self).map_err(|err| Error::new(ErrorKind::InvalidData, err))?;
+
+        std::fs::write(Self::path(prefix), config)
+    }
+}
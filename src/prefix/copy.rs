@@ -0,0 +1,80 @@
+use std::path::Path;
+use std::fs::File;
+use std::os::fd::AsRawFd;
+use std::io::{Error, Result};
+
+/// Try to make reflink copy of the file, sharing its data blocks on btrfs and XFS
+///
+/// Returns `Ok(false)` if filesystem doesn't support reflinks
+fn reflink_file(source: &Path, target: &Path) -> Result<bool> {
+    let source_file = File::open(source)?;
+    let target_file = File::create(target)?;
+
+    if unsafe { libc::ioctl(target_file.as_raw_fd(), libc::FICLONE, source_file.as_raw_fd()) } == 0 {
+        target_file.set_permissions(source_file.metadata()?.permissions())?;
+
+        return Ok(true);
+    }
+
+    let err = Error::last_os_error();
+
+    drop(target_file);
+
+    std::fs::remove_file(target)?;
+
+    match err.raw_os_error() {
+        Some(libc::EOPNOTSUPP | libc::EXDEV | libc::EINVAL | libc::ENOTTY) => Ok(false),
+        _ => Err(err)
+    }
+}
+
+/// Recursively copy wine prefix folder
+///
+/// Symlinks (e.g. `dosdevices` entries) are copied as symlinks, not followed
+///
+/// On btrfs and XFS files are copied as reflinks, making the copy nearly instant
+/// and taking no additional space until files are changed.
+/// Regular copies are made on other filesystems
+///
+/// ```no_run
+/// use wincompatlib::prelude::*;
+///
+/// copy_prefix("/path/to/prefix", "/path/to/backup")
+///     .expect("Failed to copy prefix");
+/// ```
+pub fn copy_prefix<A: AsRef<Path>, B: AsRef<Path>>(from: A, to: B) -> Result<()> {
+    copy_folder(from.as_ref(), to.as_ref(), &mut true)
+}
+
+fn copy_folder(from: &Path, to: &Path, reflink: &mut bool) -> Result<()> {
+    std::fs::create_dir_all(to)?;
+
+    for entry in from.read_dir()? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+
+        let source = entry.path();
+        let target = to.join(entry.file_name());
+
+        if file_type.is_symlink() {
+            if target.symlink_metadata().is_ok() {
+                std::fs::remove_file(&target)?;
+            }
+
+            std::os::unix::fs::symlink(std::fs::read_link(&source)?, &target)?;
+        }
+
+        else if file_type.is_dir() {
+            copy_folder(&source, &target, reflink)?;
+        }
+
+        // Don't try reflinks anymore if filesystem doesn't support them
+        else if !*reflink || !reflink_file(&source, &target)? {
+            *reflink = false;
+
+            std::fs::copy(&source, &target)?;
+        }
+    }
+
+    Ok(())
+}
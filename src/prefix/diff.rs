@@ -0,0 +1,352 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{Error, ErrorKind, Read, Result};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use serde::{Serialize, Deserialize};
+
+use super::copy_prefix;
+
+/// Name of the diff manifest stored in the exported patch folder
+pub const PREFIX_DIFF_FILE: &str = "diff.toml";
+
+/// File-level difference between two wine prefixes
+///
+/// All the paths are relative to the prefix root
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PrefixDiff {
+    /// Files, symlinks and folders which are new or changed
+    pub changed: Vec<PathBuf>,
+
+    /// Files, symlinks and folders which were removed
+    pub removed: Vec<PathBuf>
+}
+
+/// Compare content of two files
+fn same_content(a: &Path, b: &Path) -> Result<bool> {
+    if a.metadata()?.len() != b.metadata()?.len() {
+        return Ok(false);
+    }
+
+    let mut a = File::open(a)?;
+    let mut b = File::open(b)?;
+
+    let mut a_buf = vec![0; 64 * 1024];
+    let mut b_buf = vec![0; 64 * 1024];
+
+    loop {
+        let read = a.read(&mut a_buf)?;
+
+        if read == 0 {
+            return Ok(true);
+        }
+
+        b.read_exact(&mut b_buf[..read])?;
+
+        if a_buf[..read] != b_buf[..read] {
+            return Ok(false);
+        }
+    }
+}
+
+fn diff_folder(a: &Path, b: &Path, relative: &Path, diff: &mut PrefixDiff) -> Result<()> {
+    for entry in b.join(relative).read_dir()? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+
+        let path = relative.join(entry.file_name());
+        let original = a.join(&path);
+
+        let Ok(original_metadata) = original.symlink_metadata() else {
+            diff.changed.push(path);
+
+            continue;
+        };
+
+        let original_type = original_metadata.file_type();
+
+        if file_type.is_symlink() {
+            if !original_type.is_symlink() || std::fs::read_link(&original)? != std::fs::read_link(entry.path())? {
+                diff.changed.push(path);
+            }
+        }
+
+        else if file_type.is_dir() {
+            if original_type.is_dir() {
+                diff_folder(a, b, &path, diff)?;
+            } else {
+                diff.changed.push(path);
+            }
+        }
+
+        else if !original_type.is_file() || !same_content(&original, &entry.path())? {
+            diff.changed.push(path);
+        }
+    }
+
+    for entry in a.join(relative).read_dir()? {
+        let path = relative.join(entry?.file_name());
+
+        if b.join(&path).symlink_metadata().is_err() {
+            diff.removed.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+/// Compute which files should be changed in the prefix `a` to make it the same as the prefix `b`
+///
+/// ```no_run
+/// use wincompatlib::prelude::*;
+///
+/// let diff = diff_prefix("/path/to/template-v1", "/path/to/template-v2")
+///     .expect("Failed to compare prefixes");
+///
+/// // Ship only changed files
+/// diff.export("/path/to/template-v2", "/path/to/patch")
+///     .expect("Failed to export prefix patch");
+///
+/// // And apply them on the user's side
+/// apply_prefix_diff("/path/to/user/prefix", "/path/to/patch")
+///     .expect("Failed to apply prefix patch");
+/// ```
+pub fn diff_prefix<A: AsRef<Path>, B: AsRef<Path>>(a: A, b: B) -> Result<PrefixDiff> {
+    let mut diff = PrefixDiff::default();
+
+    diff_folder(a.as_ref(), b.as_ref(), Path::new(""), &mut diff)?;
+
+    diff.changed.sort();
+    diff.removed.sort();
+
+    Ok(diff)
+}
+
+/// Remove file, symlink or folder
+fn remove_path(path: &Path) -> Result<()> {
+    match path.symlink_metadata() {
+        Ok(metadata) if metadata.is_dir() => std::fs::remove_dir_all(path),
+        Ok(_) => std::fs::remove_file(path),
+
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(()),
+        Err(err) => Err(err)
+    }
+}
+
+/// Copy file, symlink or folder
+fn copy_path(from: &Path, to: &Path) -> Result<()> {
+    remove_path(to)?;
+
+    if let Some(parent) = to.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let file_type = from.symlink_metadata()?.file_type();
+
+    if file_type.is_symlink() {
+        std::os::unix::fs::symlink(std::fs::read_link(from)?, to)
+    }
+
+    else if file_type.is_dir() {
+        copy_prefix(from, to)
+    }
+
+    else {
+        std::fs::copy(from, to).map(|_| ())
+    }
+}
+
+impl PrefixDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changed.is_empty() && self.removed.is_empty()
+    }
+
+    /// Export patch folder with changed files taken from the prefix `b` and the `diff.toml` manifest
+    pub fn export<B: AsRef<Path>, T: AsRef<Path>>(&self, b: B, folder: T) -> Result<()> {
+        let b = b.as_ref();
+        let folder = folder.as_ref();
+
+        for path in &self.changed {
+            copy_path(&b.join(path), &folder.join("files").join(path))?;
+        }
+
+        let manifest = toml::to_string(self)
+            .map_err(|err| Error::new(ErrorKind::InvalidData, err))?;
+
+        std::fs::write(folder.join(PREFIX_DIFF_FILE), manifest)
+    }
+
+    /// Load manifest of the exported patch folder
+    pub fn load<T: AsRef<Path>>(folder: T) -> Result<Self> {
+        let manifest = std::fs::read_to_string(folder.as_ref().join(PREFIX_DIFF_FILE))?;
+
+        toml::from_str(&manifest).map_err(|err| Error::new(ErrorKind::InvalidData, err))
+    }
+}
+
+/// Check that none of the path's parent folders inside of the prefix is a symlink,
+/// e.g. `dosdevices/z:` pointing to the host's root folder
+fn check_parents(prefix: &Path, path: &Path) -> Result<()> {
+    let mut current = prefix.to_path_buf();
+
+    let Some(parent) = path.parent() else {
+        return Ok(());
+    };
+
+    for component in parent.components() {
+        current.push(component);
+
+        match current.symlink_metadata() {
+            Ok(metadata) if metadata.file_type().is_symlink() => {
+                return Err(Error::new(ErrorKind::InvalidData, format!("Prefix diff path {path:?} goes through symlink {current:?}")));
+            }
+
+            Ok(_) => (),
+
+            Err(err) if err.kind() == ErrorKind::NotFound => break,
+            Err(err) => return Err(err)
+        }
+    }
+
+    Ok(())
+}
+
+/// Apply patch folder exported by `PrefixDiff::export` to the prefix
+///
+/// Paths going through symlinks of the prefix (e.g. `dosdevices/z:/etc/hosts`)
+/// are rejected as they would change files outside of the prefix
+pub fn apply_prefix_diff<P: AsRef<Path>, T: AsRef<Path>>(prefix: P, folder: T) -> Result<()> {
+    let prefix = prefix.as_ref();
+    let folder = folder.as_ref();
+
+    let diff = PrefixDiff::load(folder)?;
+
+    // Paths from the manifest must not escape the prefix
+    let is_safe = |path: &Path| path.components().all(|component| matches!(component, std::path::Component::Normal(_)));
+
+    if !diff.changed.iter().chain(diff.removed.iter()).all(|path| is_safe(path)) {
+        return Err(Error::new(ErrorKind::InvalidData, "Prefix diff contains paths outside of the prefix"));
+    }
+
+    for path in &diff.removed {
+        check_parents(prefix, path)?;
+
+        remove_path(&prefix.join(path))?;
+    }
+
+    // Paths are sorted so the parent changed to a symlink is checked before its children
+    for path in &diff.changed {
+        check_parents(prefix, path)?;
+
+        copy_path(&folder.join("files").join(path), &prefix.join(path))?;
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum SnapshotEntry {
+    File {
+        len: u64,
+        modified: Option<SystemTime>
+    },
+
+    Symlink(PathBuf),
+    Folder
+}
+
+/// Metadata of all the files of the wine prefix, used to find out
+/// which files were changed without keeping a copy of the prefix
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrefixSnapshot {
+    prefix: PathBuf,
+    entries: BTreeMap<PathBuf, SnapshotEntry>
+}
+
+fn snapshot_folder(root: &Path, relative: &Path, entries: &mut BTreeMap<PathBuf, SnapshotEntry>) -> Result<()> {
+    for entry in root.join(relative).read_dir()? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+
+        let path = relative.join(entry.file_name());
+
+        if file_type.is_symlink() {
+            entries.insert(path, SnapshotEntry::Symlink(std::fs::read_link(entry.path())?));
+        }
+
+        else if file_type.is_dir() {
+            snapshot_folder(root, &path, entries)?;
+
+            entries.insert(path, SnapshotEntry::Folder);
+        }
+
+        else {
+            let metadata = entry.metadata()?;
+
+            entries.insert(path, SnapshotEntry::File {
+                len: metadata.len(),
+                modified: metadata.modified().ok()
+            });
+        }
+    }
+
+    Ok(())
+}
+
+impl PrefixSnapshot {
+    /// Remember size and modification time of all the prefix files
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// let snapshot = PrefixSnapshot::capture("/path/to/prefix")
+    ///     .expect("Failed to read prefix");
+    ///
+    /// // ...
+    ///
+    /// let diff = snapshot.diff().expect("Failed to read prefix");
+    ///
+    /// for path in diff.changed {
+    ///     println!("Changed: {path:?}");
+    /// }
+    /// ```
+    pub fn capture<T: Into<PathBuf>>(prefix: T) -> Result<Self> {
+        let prefix = prefix.into();
+        let mut entries = BTreeMap::new();
+
+        snapshot_folder(&prefix, Path::new(""), &mut entries)?;
+
+        Ok(Self {
+            prefix,
+            entries
+        })
+    }
+
+    #[inline]
+    pub fn prefix(&self) -> &Path {
+        &self.prefix
+    }
+
+    /// Compare snapshot with the current state of the prefix
+    pub fn diff(&self) -> Result<PrefixDiff> {
+        let current = Self::capture(&self.prefix)?;
+
+        let mut diff = PrefixDiff::default();
+
+        for (path, entry) in &current.entries {
+            if self.entries.get(path) != Some(entry) {
+                diff.changed.push(path.clone());
+            }
+        }
+
+        for path in self.entries.keys() {
+            if !current.entries.contains_key(path) {
+                diff.removed.push(path.clone());
+            }
+        }
+
+        Ok(diff)
+    }
+}
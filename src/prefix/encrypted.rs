@@ -0,0 +1,216 @@
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::io::{Error, Result, Write};
+
+use crate::wine::{Wine, WineWithExt, WineServerExt};
+
+use super::MountInfo;
+
+/// Encryption tool storing the prefix
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EncryptionBackend {
+    /// Encrypted folder mounted to another one with FUSE
+    Gocryptfs,
+
+    /// Native ext4 / f2fs folder encryption, folder is unlocked in place
+    Fscrypt
+}
+
+/// Wine prefix stored inside of the encrypted container
+///
+/// Container is unlocked only while the prefix is used, see `EncryptedPrefix::run`
+///
+/// ```no_run
+/// use wincompatlib::prelude::*;
+///
+/// let prefix = EncryptedPrefix::gocryptfs("/path/to/prefix.encrypted", "/path/to/prefix");
+///
+/// if !prefix.is_initialized() {
+///     prefix.init("password").expect("Failed to create encrypted container");
+/// }
+///
+/// prefix.run(&Wine::default(), "password", |wine| {
+///     wine.run("/path/to/game.exe")?.wait()?;
+///
+///     Ok(())
+/// }).expect("Failed to run the game");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncryptedPrefix {
+    pub backend: EncryptionBackend,
+
+    /// Folder with encrypted files
+    pub cipher_folder: PathBuf,
+
+    /// Folder with decrypted prefix files. Same as `cipher_folder` for fscrypt
+    pub mount_point: PathBuf
+}
+
+/// Run command feeding it the password
+fn run_with_password(command: &mut Command, password: &str) -> Result<()> {
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(password.as_bytes())?;
+        stdin.write_all(b"\n")?;
+    }
+
+    let output = child.wait_with_output()?;
+
+    if !output.status.success() {
+        return Err(Error::other(String::from_utf8_lossy(&output.stderr).trim().to_string()));
+    }
+
+    Ok(())
+}
+
+fn run(command: &mut Command) -> Result<()> {
+    let output = command.stdin(Stdio::null()).output()?;
+
+    if !output.status.success() {
+        return Err(Error::other(String::from_utf8_lossy(&output.stderr).trim().to_string()));
+    }
+
+    Ok(())
+}
+
+impl EncryptedPrefix {
+    pub fn gocryptfs<C: Into<PathBuf>, M: Into<PathBuf>>(cipher_folder: C, mount_point: M) -> Self {
+        Self {
+            backend: EncryptionBackend::Gocryptfs,
+            cipher_folder: cipher_folder.into(),
+            mount_point: mount_point.into()
+        }
+    }
+
+    pub fn fscrypt<T: Into<PathBuf>>(folder: T) -> Self {
+        let folder = folder.into();
+
+        Self {
+            backend: EncryptionBackend::Fscrypt,
+            cipher_folder: folder.clone(),
+            mount_point: folder
+        }
+    }
+
+    /// Check if encrypted container is created
+    pub fn is_initialized(&self) -> bool {
+        match self.backend {
+            EncryptionBackend::Gocryptfs => self.cipher_folder.join("gocryptfs.conf").exists(),
+
+            EncryptionBackend::Fscrypt => Command::new("fscrypt")
+                .arg("status")
+                .arg(&self.cipher_folder)
+                .output()
+                .map(|output| output.status.success())
+                .unwrap_or(false)
+        }
+    }
+
+    /// Create encrypted container protected by the password
+    ///
+    /// Fscrypt folder must be empty, and filesystem must have encryption enabled by `fscrypt setup`
+    pub fn init(&self, password: &str) -> Result<()> {
+        std::fs::create_dir_all(&self.cipher_folder)?;
+
+        match self.backend {
+            EncryptionBackend::Gocryptfs => run_with_password(Command::new("gocryptfs")
+                .args(["-init", "-q", "-passfile", "/dev/stdin"])
+                .arg(&self.cipher_folder), password),
+
+            EncryptionBackend::Fscrypt => run_with_password(Command::new("fscrypt")
+                .arg("encrypt")
+                .arg(&self.cipher_folder)
+                .args(["--source=custom_passphrase", "--quiet"])
+                .arg(format!("--name={}", self.cipher_folder.to_string_lossy())), password)
+        }
+    }
+
+    /// Check if decrypted prefix files are available
+    pub fn is_mounted(&self) -> bool {
+        match self.backend {
+            EncryptionBackend::Gocryptfs => MountInfo::find(&self.mount_point)
+                .map(|mount| mount.map(|mount| mount.mount_point == self.mount_point && mount.fs_type == "fuse.gocryptfs").unwrap_or(false))
+                .unwrap_or(false),
+
+            EncryptionBackend::Fscrypt => Command::new("fscrypt")
+                .arg("status")
+                .arg(&self.cipher_folder)
+                .output()
+                .map(|output| String::from_utf8_lossy(&output.stdout).contains("Unlocked: Yes"))
+                .unwrap_or(false)
+        }
+    }
+
+    /// Decrypt the prefix
+    pub fn mount(&self, password: &str) -> Result<()> {
+        match self.backend {
+            EncryptionBackend::Gocryptfs => {
+                std::fs::create_dir_all(&self.mount_point)?;
+
+                run_with_password(Command::new("gocryptfs")
+                    .args(["-q", "-passfile", "/dev/stdin"])
+                    .arg(&self.cipher_folder)
+                    .arg(&self.mount_point), password)
+            }
+
+            EncryptionBackend::Fscrypt => run_with_password(Command::new("fscrypt")
+                .arg("unlock")
+                .arg(&self.cipher_folder)
+                .arg("--quiet"), password)
+        }
+    }
+
+    /// Lock the prefix back. Wine processes must be stopped first
+    pub fn unmount(&self) -> Result<()> {
+        match self.backend {
+            EncryptionBackend::Gocryptfs => super::overlay::fuse_unmount(&self.mount_point),
+
+            EncryptionBackend::Fscrypt => run(Command::new("fscrypt")
+                .arg("lock")
+                .arg(&self.cipher_folder))
+        }
+    }
+
+    /// Get path to the decrypted prefix
+    pub fn prefix_path(&self) -> &Path {
+        &self.mount_point
+    }
+
+    /// Decrypt the prefix, run the game and lock the prefix after the game and its wineserver exit
+    ///
+    /// The game closure gets wine struct using the decrypted prefix and should wait until the game exits.
+    /// Prefix which was already decrypted before is kept decrypted
+    pub fn run<T, F>(&self, wine: &Wine, password: &str, game: F) -> Result<T>
+    where
+        F: FnOnce(&Wine) -> Result<T>
+    {
+        let mounted = self.is_mounted();
+
+        if !mounted {
+            self.mount(password)?;
+        }
+
+        let wine = wine.clone().with_prefix(&self.mount_point);
+
+        let result = game(&wine);
+
+        if !mounted {
+            // Wineserver keeps prefix files open for a few seconds after the game exits
+            let stopped = wine.wait_for_server_exit(std::time::Duration::from_secs(30))
+                .map_err(Error::from)
+                .and_then(|_| self.unmount());
+
+            // Game's error is more important than the unmount one
+            if result.is_ok() {
+                stopped?;
+            }
+        }
+
+        result
+    }
+}
@@ -0,0 +1,142 @@
+use std::path::{Path, PathBuf};
+use std::io::{Error, Result};
+
+use crate::wine::*;
+
+use super::copy_prefix;
+
+/// Windows folders created in the prefix skeleton
+const SKELETON_FOLDERS: &[&str] = &[
+    "drive_c/windows/system32/drivers/etc",
+    "drive_c/windows/Fonts",
+    "drive_c/windows/temp",
+    "drive_c/Program Files/Common Files",
+    "drive_c/ProgramData/Microsoft/Windows/Start Menu/Programs",
+    "drive_c/users/Public/Desktop",
+    "drive_c/users/Public/Documents"
+];
+
+/// Folders created in the user's profile folder
+const PROFILE_FOLDERS: &[&str] = &[
+    "Desktop",
+    "Documents",
+    "Downloads",
+    "Music",
+    "Pictures",
+    "Videos",
+    "Saved Games",
+    "Temp",
+    "AppData/Roaming/Microsoft/Windows/Start Menu/Programs",
+    "AppData/Local"
+];
+
+/// Find template prefix bundled with the wine build (`share/default_pfx` of proton builds)
+fn default_prefix(wine: &Wine) -> Option<PathBuf> {
+    let binary = wine.binary();
+    let root = binary.parent()?.parent()?;
+
+    [root.join("share/default_pfx"), root.join("../share/default_pfx")]
+        .into_iter()
+        .find(|path| path.join("system.reg").exists())
+}
+
+/// Create minimal wine prefix skeleton: folders, drive symlinks and empty registry files
+///
+/// Created prefix has automatic updates disabled (`.update-timestamp` is set to `disable`),
+/// so wine starts programs without running `wineboot -u` first.
+/// Run `WineBootExt::update_prefix` to turn it into a complete prefix
+///
+/// This is an experimental feature: programs relying on the default registry entries may not work
+///
+/// ```no_run
+/// use wincompatlib::prelude::*;
+///
+/// create_prefix_skeleton("/path/to/prefix", WineArch::Win64)
+///     .expect("Failed to create prefix");
+/// ```
+pub fn create_prefix_skeleton<T: AsRef<Path>>(path: T, arch: WineArch) -> Result<()> {
+    let path = path.as_ref();
+
+    if path.join("system.reg").exists() {
+        return Err(Error::new(std::io::ErrorKind::AlreadyExists, format!("Wine prefix already exists: {path:?}")));
+    }
+
+    for folder in SKELETON_FOLDERS {
+        std::fs::create_dir_all(path.join(folder))?;
+    }
+
+    if arch == WineArch::Win64 {
+        std::fs::create_dir_all(path.join("drive_c/windows/syswow64"))?;
+        std::fs::create_dir_all(path.join("drive_c/Program Files (x86)/Common Files"))?;
+    }
+
+    let user = std::env::var("USER").unwrap_or_else(|_| String::from("steamuser"));
+    let profile = path.join("drive_c/users").join(user);
+
+    for folder in PROFILE_FOLDERS {
+        std::fs::create_dir_all(profile.join(folder))?;
+    }
+
+    let dosdevices = path.join("dosdevices");
+
+    std::fs::create_dir_all(&dosdevices)?;
+
+    for (drive, target) in [("c:", "../drive_c"), ("z:", "/")] {
+        if dosdevices.join(drive).symlink_metadata().is_err() {
+            std::os::unix::fs::symlink(target, dosdevices.join(drive))?;
+        }
+    }
+
+    let registry = |relative: &str| format!("WINE REGISTRY Version 2\n;; All keys relative to {relative}\n\n#arch={}\n", arch.to_str());
+
+    std::fs::write(path.join("system.reg"), registry("\\\\Machine"))?;
+    std::fs::write(path.join("user.reg"), registry("\\\\User\\\\S-1-5-21-0-0-0-1000"))?;
+    std::fs::write(path.join("userdef.reg"), registry("\\\\User\\\\.Default"))?;
+
+    std::fs::write(path.join(".update-timestamp"), "disable")
+}
+
+impl Wine {
+    /// Create wine prefix without running wineboot
+    ///
+    /// Template prefix bundled with the wine build (proton's `default_pfx`) is copied if available,
+    /// otherwise minimal skeleton is created (see `create_prefix_skeleton`).
+    /// If it fails and `fallback` is `true` then `wineboot -u` is used instead.
+    /// Returns `AlreadyExists` error if the prefix is already created
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// Wine::from_binary("/path/to/wine")
+    ///     .with_arch(WineArch::Win64)
+    ///     .create_prefix_fast("/path/to/prefix", true)
+    ///     .expect("Failed to create prefix");
+    /// ```
+    pub fn create_prefix_fast<T: Into<PathBuf>>(&self, path: T, fallback: bool) -> Result<()> {
+        self.check_writable()?;
+
+        let path = path.into();
+
+        // Template is copied over the existing files otherwise
+        if path.join("system.reg").exists() {
+            return Err(Error::new(std::io::ErrorKind::AlreadyExists, format!("Wine prefix already exists: {path:?}")));
+        }
+
+        let result = match default_prefix(self) {
+            Some(template) => copy_prefix(template, &path),
+            None => create_prefix_skeleton(&path, self.arch.unwrap_or(WineArch::Win64))
+        };
+
+        match result {
+            Err(err) if fallback => {
+                self.events.warning(format!("Failed to create prefix without wineboot, falling back: {err}"));
+
+                self.update_prefix(&path)?;
+
+                Ok(())
+            }
+
+            result => result
+        }
+    }
+}
@@ -0,0 +1,390 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::os::fd::AsRawFd;
+use std::os::unix::fs::MetadataExt;
+use std::process::{Command, Stdio};
+use std::io::{Error, Result};
+
+use crate::wine::Wine;
+use crate::warning::Warning;
+
+/// `FS_CASEFOLD_FL` inode flag of ext4 and f2fs filesystems
+const FS_CASEFOLD_FL: libc::c_int = 0x40000000;
+
+/// Name of the temporary file used to probe case sensitivity
+const CASE_PROBE_FILE: &str = ".wincompatlib-case-probe";
+
+/// Check if files lookup in the folder ignores case, e.g. because of vfat filesystem or ext4 casefolding
+///
+/// Creates and removes a temporary file, so the folder must be writable
+pub fn is_case_insensitive<T: AsRef<Path>>(folder: T) -> Result<bool> {
+    let folder = folder.as_ref();
+    let probe = folder.join(CASE_PROBE_FILE);
+
+    File::create(&probe)?;
+
+    let insensitive = folder.join(CASE_PROBE_FILE.to_uppercase()).exists();
+
+    std::fs::remove_file(probe)?;
+
+    Ok(insensitive)
+}
+
+fn get_flags(file: &File) -> Result<libc::c_int> {
+    let mut flags: libc::c_int = 0;
+
+    if unsafe { libc::ioctl(file.as_raw_fd(), libc::FS_IOC_GETFLAGS, &mut flags) } == -1 {
+        return Err(Error::last_os_error());
+    }
+
+    Ok(flags)
+}
+
+/// Check if ext4 or f2fs casefolding is enabled for the folder
+///
+/// Returns `ErrorKind::Unsupported` or `ErrorKind::InvalidInput` error if filesystem doesn't support inode flags
+pub fn is_casefold_enabled<T: AsRef<Path>>(folder: T) -> Result<bool> {
+    Ok(get_flags(&File::open(folder)?)? & FS_CASEFOLD_FL != 0)
+}
+
+/// Enable ext4 or f2fs casefolding for the folder, so its content is looked up ignoring case
+///
+/// Folder must be empty, and the filesystem must be created with `casefold` feature.
+/// Subfolders created later inherit this flag
+///
+/// ```no_run
+/// use wincompatlib::prelude::*;
+///
+/// std::fs::create_dir_all("/path/to/game").expect("Failed to create game folder");
+///
+/// if let Err(err) = enable_casefold("/path/to/game") {
+///     eprintln!("Casefolding is not supported: {err}");
+/// }
+/// ```
+pub fn enable_casefold<T: AsRef<Path>>(folder: T) -> Result<()> {
+    let file = File::open(folder)?;
+
+    let flags = get_flags(&file)? | FS_CASEFOLD_FL;
+
+    if unsafe { libc::ioctl(file.as_raw_fd(), libc::FS_IOC_SETFLAGS, &flags) } == -1 {
+        return Err(Error::last_os_error());
+    }
+
+    Ok(())
+}
+
+fn find_conflicts(folder: &Path, conflicts: &mut Vec<Vec<PathBuf>>) {
+    let Ok(entries) = folder.read_dir() else {
+        return;
+    };
+
+    let mut names = BTreeMap::<String, Vec<PathBuf>>::new();
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+
+        if entry.file_type().map(|kind| kind.is_dir()).unwrap_or(false) {
+            find_conflicts(&path, conflicts);
+        }
+
+        names.entry(entry.file_name().to_string_lossy().to_lowercase())
+            .or_default()
+            .push(path);
+    }
+
+    conflicts.extend(names.into_values().filter(|paths| paths.len() > 1).map(|mut paths| {
+        paths.sort();
+
+        paths
+    }));
+}
+
+/// Find files in the folder which names differ only by case
+///
+/// Windows programs can access only one file of each returned group
+pub fn find_case_conflicts<T: AsRef<Path>>(folder: T) -> Vec<Vec<PathBuf>> {
+    let mut conflicts = Vec::new();
+
+    find_conflicts(folder.as_ref(), &mut conflicts);
+
+    conflicts
+}
+
+/// Filesystem mount found in `/proc/self/mountinfo`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MountInfo {
+    pub mount_point: PathBuf,
+
+    /// Filesystem type, e.g. `ext4` or `ntfs3`
+    ///
+    /// FUSE drivers (ntfs-3g, exfat-fuse) are reported as `fuseblk`,
+    /// `MountInfo::find` replaces it with the type of the mounted device (`ntfs`, `exfat`)
+    pub fs_type: String,
+
+    /// Mounted device, e.g. `/dev/sda1`
+    pub source: String,
+
+    /// Mount and superblock options, e.g. `noexec` or `uid=1000`
+    pub options: Vec<String>
+}
+
+/// Get filesystem type of the block device, e.g. `ntfs`
+///
+/// udev database is checked first since it doesn't need access to the device,
+/// `device` is its `major:minor` numbers
+fn probe_fs_type(device: &str, source: &str) -> Option<String> {
+    let udev = std::fs::read_to_string(format!("/run/udev/data/b{device}")).unwrap_or_default();
+
+    if let Some(fs_type) = udev.lines().find_map(|line| line.strip_prefix("E:ID_FS_TYPE=")) {
+        return Some(fs_type.to_string());
+    }
+
+    let output = Command::new("blkid")
+        .args(["-o", "value", "-s", "TYPE", source])
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+
+    let fs_type = String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+    (output.status.success() && !fs_type.is_empty()).then_some(fs_type)
+}
+
+/// Decode `\040`-like escapes of the mountinfo paths
+fn unescape(value: &str) -> String {
+    let mut result = Vec::with_capacity(value.len());
+    let bytes = value.as_bytes();
+
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'\\' {
+            if let Some(byte) = value.get(i + 1..i + 4).and_then(|code| u8::from_str_radix(code, 8).ok()) {
+                result.push(byte);
+
+                i += 4;
+
+                continue;
+            }
+        }
+
+        result.push(bytes[i]);
+
+        i += 1;
+    }
+
+    String::from_utf8_lossy(&result).to_string()
+}
+
+impl MountInfo {
+    /// Parse line of the `/proc/self/mountinfo` file
+    ///
+    /// ```
+    /// use wincompatlib::prelude::*;
+    ///
+    /// let mount = MountInfo::parse("36 28 8:17 / /mnt/Games rw,nosuid,relatime - fuseblk /dev/sdb1 rw,user_id=0,group_id=0")
+    ///     .expect("Failed to parse mount");
+    ///
+    /// assert_eq!(mount.fs_type, "fuseblk");
+    /// assert_eq!(mount.option("user_id"), Some("0"));
+    /// ```
+    pub fn parse(line: &str) -> Option<Self> {
+        let (mount, superblock) = line.split_once(" - ")?;
+
+        let mount = mount.split(' ').collect::<Vec<_>>();
+        let mut superblock = superblock.split(' ');
+
+        let fs_type = superblock.next()?.to_string();
+        let source = unescape(superblock.next()?);
+
+        let mut options = mount.get(5)?.split(',').map(String::from).collect::<Vec<_>>();
+
+        options.extend(superblock.next().unwrap_or_default().split(',').map(String::from));
+
+        Some(Self {
+            mount_point: PathBuf::from(unescape(mount.get(4)?)),
+            fs_type,
+            source,
+            options
+        })
+    }
+
+    /// Find mount containing the given path
+    pub fn find<T: AsRef<Path>>(path: T) -> Result<Option<Self>> {
+        let path = path.as_ref().canonicalize()?;
+
+        let mounts = std::fs::read_to_string("/proc/self/mountinfo")?;
+
+        // Later mounts override the earlier ones with the same mount point
+        let found = mounts.lines()
+            .filter_map(|line| Some((line.split(' ').nth(2)?, Self::parse(line)?)))
+            .filter(|(_, mount)| path.starts_with(&mount.mount_point))
+            .fold(None, |found: Option<(&str, Self)>, (device, mount)| match found {
+                Some(found) if found.1.mount_point.as_os_str().len() > mount.mount_point.as_os_str().len() => Some(found),
+                _ => Some((device, mount))
+            });
+
+        Ok(found.map(|(device, mut mount)| {
+            if mount.fs_type == "fuseblk" {
+                if let Some(fs_type) = probe_fs_type(device, &mount.source) {
+                    mount.fs_type = fs_type;
+                }
+            }
+
+            mount
+        }))
+    }
+
+    /// Check if mount has the given option
+    pub fn has_option(&self, name: &str) -> bool {
+        self.options.iter().any(|option| option == name)
+    }
+
+    /// Get value of the `name=value` option
+    pub fn option(&self, name: &str) -> Option<&str> {
+        self.options.iter().find_map(|option| {
+            option.strip_prefix(name).and_then(|value| value.strip_prefix('='))
+        })
+    }
+
+    /// Check if filesystem is NTFS, mounted by either ntfs3 driver or ntfs-3g
+    ///
+    /// `fuseblk` mounts which type couldn't be probed are assumed to be ntfs-3g ones
+    pub fn is_ntfs(&self) -> bool {
+        matches!(self.fs_type.as_str(), "ntfs" | "ntfs3" | "fuseblk")
+    }
+
+    /// Check if filesystem doesn't support unix permissions and symlinks
+    pub fn is_fat(&self) -> bool {
+        matches!(self.fs_type.as_str(), "vfat" | "exfat" | "msdos")
+    }
+
+    /// Get remediation of the problems this mount causes to wine, or `None` if it's suitable
+    ///
+    /// `owner` is the user id owning files of the mount, and `prefix` tells if the mount stores a wine prefix
+    ///
+    /// ```
+    /// use wincompatlib::prelude::*;
+    ///
+    /// let mount = MountInfo::parse("36 28 8:17 / /mnt/Games rw,relatime - ntfs3 /dev/sdb1 rw,uid=1000,gid=1000")
+    ///     .expect("Failed to parse mount");
+    ///
+    /// assert_eq!(mount.remediation(1000, 1000, false), None);
+    /// assert!(mount.remediation(0, 1000, false).is_some());
+    /// ```
+    pub fn remediation(&self, owner: u32, uid: u32, prefix: bool) -> Option<String> {
+        if !self.is_ntfs() && !self.is_fat() {
+            return None;
+        }
+
+        let device = &self.source;
+        let mount_point = self.mount_point.to_string_lossy();
+
+        if prefix && self.is_fat() {
+            return Some(format!("{} doesn't support symlinks needed by wine prefixes, move the prefix to a linux filesystem", self.fs_type));
+        }
+
+        if prefix && self.has_option("windows_names") {
+            return Some(format!("Remount {device} without windows_names option: wine prefixes use ':' in file names"));
+        }
+
+        if owner != uid {
+            let gid = unsafe { libc::getgid() };
+
+            return Some(format!("Files of {mount_point} are owned by another user, remount {device} with uid={uid},gid={gid},umask=022 options"));
+        }
+
+        if self.has_option("noexec") {
+            return Some(format!("Remount {device} with exec option, so programs can be run from {mount_point}"));
+        }
+
+        None
+    }
+}
+
+impl Wine {
+    /// Check that files of the folder (e.g. the game one) can be accessed by windows programs
+    ///
+    /// Wine emulates case insensitive lookups on case sensitive filesystems, but files which names
+    /// differ only by case can't be distinguished. Found problem is reported as a warning
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// if let Some(warning) = Wine::default().check_case_sensitivity("/path/to/game") {
+    ///     eprintln!("{warning}");
+    /// }
+    /// ```
+    pub fn check_case_sensitivity<T: AsRef<Path>>(&self, folder: T) -> Option<Warning> {
+        let folder = folder.as_ref();
+
+        let conflicts = find_case_conflicts(folder);
+
+        if conflicts.is_empty() {
+            return None;
+        }
+
+        let warning = Warning::CaseConflicts {
+            folder: folder.to_path_buf(),
+            conflicts
+        };
+
+        self.events.warning(warning.clone());
+
+        Some(warning)
+    }
+
+    /// Check that the prefix and the optional game folder are stored on filesystems suitable for wine
+    ///
+    /// NTFS and exFAT partitions mounted without proper `uid` and `gid` options are a common cause of failures.
+    /// Found problems are reported as warnings with remediation messages
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// let wine = Wine::default().with_prefix("/path/to/prefix");
+    ///
+    /// for warning in wine.check_mounts(Some("/mnt/Games/game")).expect("Failed to check mounts") {
+    ///     eprintln!("{warning}");
+    /// }
+    /// ```
+    pub fn check_mounts<T: AsRef<Path>>(&self, game: Option<T>) -> Result<Vec<Warning>> {
+        let uid = unsafe { libc::getuid() };
+
+        let prefix = self.prefix_path();
+
+        let mut paths = vec![(prefix.as_path(), true)];
+
+        if let Some(game) = &game {
+            paths.push((game.as_ref(), false));
+        }
+
+        let mut warnings = Vec::new();
+
+        for (path, is_prefix) in paths {
+            // Prefix can be not created yet
+            let Some(path) = path.ancestors().find(|path| path.exists()) else {
+                continue;
+            };
+
+            let Some(mount) = MountInfo::find(path)? else {
+                continue;
+            };
+
+            if let Some(remediation) = mount.remediation(path.metadata()?.uid(), uid, is_prefix) {
+                let warning = Warning::UnsuitableMount {
+                    path: path.to_path_buf(),
+                    fs_type: mount.fs_type,
+                    remediation
+                };
+
+                self.events.warning(warning.clone());
+
+                warnings.push(warning);
+            }
+        }
+
+        Ok(warnings)
+    }
+}
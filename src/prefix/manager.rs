@@ -0,0 +1,200 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::io::{Error, ErrorKind, Result};
+
+use serde::{Serialize, Deserialize};
+
+use crate::wine::*;
+
+use super::{PrefixConfig, copy_prefix};
+
+/// Wine prefix owned by the `PrefixManager`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManagedPrefix {
+    pub name: String,
+    pub path: PathBuf,
+
+    /// Path to the wine binary used to run this prefix
+    pub runner: Option<PathBuf>,
+
+    /// Environment variables to set when running this prefix
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+
+    /// Arbitrary information stored by the application
+    #[serde(default)]
+    pub metadata: BTreeMap<String, String>
+}
+
+impl ManagedPrefix {
+    pub fn new<T: Into<String>, F: Into<PathBuf>>(name: T, path: F) -> Self {
+        Self {
+            name: name.into(),
+            path: path.into(),
+            runner: None,
+            env: BTreeMap::new(),
+            metadata: BTreeMap::new()
+        }
+    }
+
+    /// Get wine struct running this prefix
+    ///
+    /// If runner is not specified, then system wine is used
+    pub fn wine(&self) -> Wine {
+        match &self.runner {
+            Some(runner) => Wine::from_binary(runner),
+            None => Wine::default()
+        }.with_prefix(&self.path)
+    }
+}
+
+/// Set of named wine prefixes
+///
+/// ```no_run
+/// use wincompatlib::prelude::*;
+///
+/// let mut manager = PrefixManager::new("/path/to/prefixes");
+///
+/// manager.create("my-game", &Wine::from_binary("/path/to/wine"))
+///     .expect("Failed to create prefix");
+///
+/// for prefix in manager.list() {
+///     println!("{}: {:?}", prefix.name, prefix.path);
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrefixManager {
+    root: PathBuf,
+    prefixes: BTreeMap<String, ManagedPrefix>,
+
+    /// Environment variables set to all the prefixes. Prefix's own variables have higher priority
+    pub default_env: BTreeMap<String, String>
+}
+
+impl PrefixManager {
+    /// Create empty manager which will store new prefixes in the `root` folder
+    pub fn new<T: Into<PathBuf>>(root: T) -> Self {
+        Self {
+            root: root.into(),
+            prefixes: BTreeMap::new(),
+            default_env: BTreeMap::new()
+        }
+    }
+
+    /// Get folder where new prefixes are created
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// List managed prefixes sorted by their names
+    pub fn list(&self) -> impl Iterator<Item = &ManagedPrefix> {
+        self.prefixes.values()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&ManagedPrefix> {
+        self.prefixes.get(name)
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut ManagedPrefix> {
+        self.prefixes.get_mut(name)
+    }
+
+    /// Add existing prefix to the manager
+    ///
+    /// Returns `ErrorKind::AlreadyExists` error if prefix with the same name is already managed
+    pub fn add(&mut self, prefix: ManagedPrefix) -> Result<()> {
+        if self.prefixes.contains_key(&prefix.name) {
+            return Err(Error::new(ErrorKind::AlreadyExists, format!("Prefix {} already exists", prefix.name)));
+        }
+
+        self.prefixes.insert(prefix.name.clone(), prefix);
+
+        Ok(())
+    }
+
+    /// Remove prefix from the manager without deleting its files
+    pub fn forget(&mut self, name: &str) -> Option<ManagedPrefix> {
+        self.prefixes.remove(name)
+    }
+
+    /// Create new prefix in the root folder using given wine
+    ///
+    /// Prefix config file is saved with the runner path and default environment
+    pub fn create<T: Into<String>>(&mut self, name: T, wine: &Wine) -> Result<&ManagedPrefix> {
+        let name = name.into();
+
+        if self.prefixes.contains_key(&name) {
+            return Err(Error::new(ErrorKind::AlreadyExists, format!("Prefix {name} already exists")));
+        }
+
+        let path = self.root.join(&name);
+
+        wine.update_prefix(&path)?;
+
+        let mut prefix = ManagedPrefix::new(&name, path);
+
+        prefix.runner = Some(wine.binary());
+
+        let mut config = PrefixConfig::load_or_default(&prefix.path)?;
+
+        config.runner = prefix.runner.clone();
+        config.env.extend(self.default_env.clone());
+        config.save(&prefix.path)?;
+
+        Ok(self.prefixes.entry(name).or_insert(prefix))
+    }
+
+    /// Delete prefix files and remove it from the manager
+    ///
+    /// Prefix stays managed if its files can't be deleted
+    pub fn delete(&mut self, name: &str) -> Result<ManagedPrefix> {
+        let Some(prefix) = self.prefixes.get(name) else {
+            return Err(Error::new(ErrorKind::NotFound, format!("Prefix {name} is not managed")));
+        };
+
+        if prefix.path.exists() {
+            std::fs::remove_dir_all(&prefix.path)?;
+        }
+
+        self.prefixes.remove(name)
+            .ok_or_else(|| Error::new(ErrorKind::NotFound, format!("Prefix {name} is not managed")))
+    }
+
+    /// Copy prefix files to the backup folder
+    pub fn backup<T: AsRef<Path>>(&self, name: &str, backup: T) -> Result<()> {
+        let Some(prefix) = self.prefixes.get(name) else {
+            return Err(Error::new(ErrorKind::NotFound, format!("Prefix {name} is not managed")));
+        };
+
+        copy_prefix(&prefix.path, backup)
+    }
+
+    /// Get environment variables of the prefix merged with the default ones
+    ///
+    /// ```
+    /// use wincompatlib::prelude::*;
+    ///
+    /// let mut manager = PrefixManager::new("/path/to/prefixes");
+    /// let mut prefix = ManagedPrefix::new("game", "/path/to/prefixes/game");
+    ///
+    /// prefix.env.insert(String::from("DXVK_HUD"), String::from("fps"));
+    ///
+    /// manager.default_env.insert(String::from("DXVK_HUD"), String::from("full"));
+    /// manager.default_env.insert(String::from("MANGOHUD"), String::from("1"));
+    /// manager.add(prefix).unwrap();
+    ///
+    /// let env = manager.envs("game").unwrap();
+    ///
+    /// assert_eq!(env["DXVK_HUD"], "fps");
+    /// assert_eq!(env["MANGOHUD"], "1");
+    /// ```
+    pub fn envs(&self, name: &str) -> Option<BTreeMap<String, String>> {
+        self.prefixes.get(name).map(|prefix| {
+            let mut env = self.default_env.clone();
+
+            env.extend(prefix.env.clone());
+
+            env
+        })
+    }
+}
@@ -0,0 +1,33 @@
+mod config;
+mod state;
+mod copy;
+mod manager;
+mod registry;
+mod overlay;
+mod diff;
+mod reg_file;
+mod programs;
+mod shell_folders;
+mod fast;
+mod filesystem;
+mod encrypted;
+
+#[cfg(feature = "watcher")]
+mod watcher;
+
+pub use config::*;
+pub use state::*;
+pub use copy::*;
+pub use manager::*;
+pub use registry::*;
+pub use overlay::*;
+pub use diff::*;
+pub use reg_file::*;
+pub use programs::*;
+pub use shell_folders::*;
+pub use fast::*;
+pub use filesystem::*;
+pub use encrypted::*;
+
+#[cfg(feature = "watcher")]
+pub use watcher::*;
@@ -0,0 +1,210 @@
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::io::{Error, ErrorKind, Result};
+
+use super::copy_prefix;
+
+/// Unmount FUSE filesystem with `fusermount3`, falling back to `fusermount` of FUSE 2
+pub(crate) fn fuse_unmount(path: &Path) -> Result<()> {
+    let mut error = None;
+
+    for program in ["fusermount3", "fusermount"] {
+        let output = Command::new(program)
+            .arg("-u")
+            .arg(path)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .output();
+
+        match output {
+            Ok(output) if output.status.success() => return Ok(()),
+
+            Ok(output) => error = Some(Error::other(format!("{program} failed: {}", String::from_utf8_lossy(&output.stderr).trim()))),
+
+            // Keep the actual failure instead of the missing fallback
+            Err(err) if error.is_none() => error = Some(err),
+            Err(_) => ()
+        }
+    }
+
+    Err(error.unwrap_or_else(|| Error::other("fusermount is not available")))
+}
+
+/// How the writable layer of the `PrefixOverlay` is made
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OverlayKind {
+    /// Base prefix is mounted as the lower layer of `fuse-overlayfs`,
+    /// so only changed files are stored
+    Overlayfs,
+
+    /// Base prefix is copied, used when `fuse-overlayfs` is not available
+    Copy
+}
+
+/// Writable layer on top of the read-only base prefix
+///
+/// Allows to run games in a throwaway prefix and reset it to the clean state
+/// without re-running installers
+///
+/// ```no_run
+/// use wincompatlib::prelude::*;
+///
+/// let overlay = PrefixOverlay::new("/path/to/base-prefix", "/path/to/run")
+///     .expect("Failed to create prefix overlay");
+///
+/// Wine::from_binary("/path/to/wine")
+///     .with_prefix(overlay.path())
+///     .run("/path/to/game.exe")
+///     .expect("Failed to run game")
+///     .wait()
+///     .expect("Failed to wait for game");
+///
+/// // Drop all the changes made by the game
+/// overlay.discard().expect("Failed to remove prefix overlay");
+/// ```
+/// The struct owns the mount, so it can't be cloned
+#[derive(Debug, PartialEq, Eq)]
+pub struct PrefixOverlay {
+    base: PathBuf,
+    root: PathBuf,
+    kind: OverlayKind
+}
+
+impl PrefixOverlay {
+    /// Create overlay of the base prefix in the root folder, which will contain
+    /// `upper`, `work` and `merged` folders. Existing changes in the root folder are kept,
+    /// so if the existing `fuse-overlayfs` overlay can't be mounted again an error is returned
+    pub fn new<B: Into<PathBuf>, R: Into<PathBuf>>(base: B, root: R) -> Result<Self> {
+        let base = base.into();
+        let root = root.into();
+
+        if !base.join("system.reg").exists() {
+            return Err(Error::new(ErrorKind::InvalidInput, format!("{:?} is not a valid wine prefix", base)));
+        }
+
+        std::fs::create_dir_all(root.join("merged"))?;
+
+        let kind = Self::mount(&base, &root)?;
+
+        Ok(Self {
+            base,
+            root,
+            kind
+        })
+    }
+
+    fn mount(base: &Path, root: &Path) -> Result<OverlayKind> {
+        let upper = root.join("upper");
+        let work = root.join("work");
+
+        // Remember which folders are created here so only they are removed on failure
+        let created_upper = !upper.exists();
+        let created_work = !work.exists();
+
+        std::fs::create_dir_all(&upper)?;
+        std::fs::create_dir_all(&work)?;
+
+        let mut options = OsString::from("lowerdir=");
+
+        options.push(base);
+        options.push(",upperdir=");
+        options.push(&upper);
+        options.push(",workdir=");
+        options.push(&work);
+
+        let mounted = Command::new("fuse-overlayfs")
+            .arg("-o")
+            .arg(options)
+            .arg(root.join("merged"))
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .map(|status| status.success())
+            .unwrap_or(false);
+
+        if mounted {
+            return Ok(OverlayKind::Overlayfs);
+        }
+
+        // Changes made in the existing overlay can't be moved to the copy
+        if !created_upper {
+            return Err(Error::other(format!("Failed to mount existing prefix overlay {:?} with fuse-overlayfs", root)));
+        }
+
+        // Copy-up fallback: the whole prefix is the writable layer
+        std::fs::remove_dir_all(&upper)?;
+
+        if created_work {
+            std::fs::remove_dir_all(&work)?;
+        }
+
+        if root.join("merged").read_dir()?.next().is_none() {
+            copy_prefix(base, root.join("merged"))?;
+        }
+
+        Ok(OverlayKind::Copy)
+    }
+
+    fn unmount_merged(&self) -> Result<()> {
+        if self.kind != OverlayKind::Overlayfs {
+            return Ok(());
+        }
+
+        fuse_unmount(&self.path())
+            .map_err(|err| Error::other(format!("Failed to unmount prefix overlay {:?}: {err}", self.path())))
+    }
+
+    /// Path to the base prefix
+    pub fn base(&self) -> &Path {
+        &self.base
+    }
+
+    /// Path to the folder storing the overlay
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn kind(&self) -> OverlayKind {
+        self.kind
+    }
+
+    /// Path to the writable prefix which should be used to run applications
+    pub fn path(&self) -> PathBuf {
+        self.root.join("merged")
+    }
+
+    /// Drop all the changes, returning the overlay to the state of its base prefix
+    ///
+    /// Wineserver of the overlay must be stopped
+    pub fn reset(&mut self) -> Result<()> {
+        self.unmount_merged()?;
+
+        for folder in ["upper", "work", "merged"] {
+            let path = self.root.join(folder);
+
+            if path.exists() {
+                std::fs::remove_dir_all(path)?;
+            }
+        }
+
+        std::fs::create_dir_all(self.path())?;
+
+        self.kind = Self::mount(&self.base, &self.root)?;
+
+        Ok(())
+    }
+
+    /// Unmount the overlay keeping its changes, so it can be created again later
+    pub fn unmount(self) -> Result<()> {
+        self.unmount_merged()
+    }
+
+    /// Unmount the overlay and remove all its files
+    pub fn discard(self) -> Result<()> {
+        self.unmount_merged()?;
+
+        std::fs::remove_dir_all(&self.root)
+    }
+}
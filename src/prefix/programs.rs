@@ -0,0 +1,180 @@
+use std::path::Path;
+use std::process::Child;
+use std::io::{Error, ErrorKind, Result};
+
+use crate::wine::{Wine, WineRunExt, windows_split};
+
+use super::{RegFile, RegKey};
+
+const UNINSTALL_KEYS: &[&str] = &[
+    "Software\\Microsoft\\Windows\\CurrentVersion\\Uninstall",
+    "Software\\Wow6432Node\\Microsoft\\Windows\\CurrentVersion\\Uninstall"
+];
+
+/// Windows program registered in the prefix's Uninstall registry keys
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstalledProgram {
+    /// Name of the registry key, e.g. product GUID
+    pub id: String,
+
+    pub display_name: String,
+    pub display_version: Option<String>,
+    pub publisher: Option<String>,
+
+    /// Windows path to the program's folder
+    pub install_location: Option<String>,
+
+    pub uninstall_string: Option<String>,
+    pub quiet_uninstall_string: Option<String>
+}
+
+impl InstalledProgram {
+    fn from_key(key: &RegKey) -> Option<Self> {
+        // System components are hidden by windows as well
+        if key.dword("SystemComponent") == Some(1) {
+            return None;
+        }
+
+        Some(Self {
+            id: key.path.rsplit('\\').next()?.to_string(),
+            display_name: key.string("DisplayName")?.to_string(),
+            display_version: key.string("DisplayVersion").map(String::from),
+            publisher: key.string("Publisher").map(String::from),
+            install_location: key.string("InstallLocation").map(String::from),
+            uninstall_string: key.string("UninstallString").map(String::from),
+            quiet_uninstall_string: key.string("QuietUninstallString").map(String::from)
+        })
+    }
+
+    /// Get command line uninstalling the program without user interaction
+    ///
+    /// If program doesn't provide quiet uninstall string, then silent flags
+    /// of the known installers (MSI, Inno Setup, NSIS) are added to the regular one
+    ///
+    /// ```
+    /// use wincompatlib::prelude::*;
+    ///
+    /// let program = InstalledProgram {
+    ///     id: String::from("Game_is1"),
+    ///     display_name: String::from("Game"),
+    ///     display_version: None,
+    ///     publisher: None,
+    ///     install_location: None,
+    ///     uninstall_string: Some(String::from("\"C:\\Games\\Game\\unins000.exe\"")),
+    ///     quiet_uninstall_string: None
+    /// };
+    ///
+    /// assert_eq!(program.quiet_uninstall_command().unwrap(), [
+    ///     "C:\\Games\\Game\\unins000.exe", "/VERYSILENT", "/SUPPRESSMSGBOXES", "/NORESTART"
+    /// ]);
+    /// ```
+    pub fn quiet_uninstall_command(&self) -> Option<Vec<String>> {
+        if let Some(quiet) = &self.quiet_uninstall_string {
+            return Some(windows_split(quiet));
+        }
+
+        let mut command = windows_split(self.uninstall_string.as_ref()?);
+
+        let exe = command.first()?
+            .rsplit(['\\', '/'])
+            .next()?
+            .to_lowercase();
+
+        let has_arg = |command: &[String], arg: &str| command.iter().skip(1).any(|value| value.eq_ignore_ascii_case(arg));
+
+        if exe == "msiexec.exe" || exe == "msiexec" {
+            // Install flag is often used in uninstall strings to open the "modify" dialog
+            for arg in command.iter_mut().skip(1) {
+                if arg.len() > 2 && arg.get(..2).is_some_and(|flag| flag.eq_ignore_ascii_case("/i")) {
+                    arg.replace_range(..2, "/X");
+                }
+            }
+
+            if !has_arg(&command, "/qn") {
+                command.push(String::from("/qn"));
+            }
+        }
+
+        else if exe.starts_with("unins") {
+            for arg in ["/VERYSILENT", "/SUPPRESSMSGBOXES", "/NORESTART"] {
+                if !has_arg(&command, arg) {
+                    command.push(arg.to_string());
+                }
+            }
+        }
+
+        else if exe.starts_with("uninst") && !has_arg(&command, "/S") {
+            command.push(String::from("/S"));
+        }
+
+        Some(command)
+    }
+}
+
+/// List programs installed in the wine prefix, sorted by their names
+///
+/// ```no_run
+/// use wincompatlib::prelude::*;
+///
+/// for program in installed_programs("/path/to/prefix").expect("Failed to read registry") {
+///     println!("{} {}", program.display_name, program.display_version.unwrap_or_default());
+/// }
+/// ```
+pub fn installed_programs<T: AsRef<Path>>(prefix: T) -> Result<Vec<InstalledProgram>> {
+    let prefix = prefix.as_ref();
+
+    let mut programs = Vec::new();
+
+    for file in ["system.reg", "user.reg"] {
+        let registry = match RegFile::open(prefix.join(file)) {
+            Ok(registry) => registry,
+
+            Err(err) if err.kind() == ErrorKind::NotFound && file == "user.reg" => continue,
+            Err(err) => return Err(err)
+        };
+
+        for key in UNINSTALL_KEYS {
+            programs.extend(registry.subkeys(key).filter_map(InstalledProgram::from_key));
+        }
+    }
+
+    programs.sort_by_key(|program| program.display_name.to_lowercase());
+
+    Ok(programs)
+}
+
+impl Wine {
+    /// List programs installed in the wine prefix
+    pub fn installed_programs(&self) -> Result<Vec<InstalledProgram>> {
+        installed_programs(self.prefix_path())
+    }
+
+    /// Run program's uninstaller, without user interaction if `quiet = true` or the struct is `unattended`
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// let wine = Wine::default().with_prefix("/path/to/prefix");
+    ///
+    /// for program in wine.installed_programs().unwrap() {
+    ///     if program.display_name == "Some Launcher" {
+    ///         wine.uninstall_program(&program, true)
+    ///             .expect("Failed to run uninstaller")
+    ///             .wait()
+    ///             .expect("Failed to wait for uninstaller");
+    ///     }
+    /// }
+    /// ```
+    pub fn uninstall_program(&self, program: &InstalledProgram, quiet: bool) -> Result<Child> {
+        let command = match quiet || self.unattended {
+            true  => program.quiet_uninstall_command(),
+            false => program.uninstall_string.as_deref().map(windows_split)
+        };
+
+        match command {
+            Some(command) if !command.is_empty() => Ok(self.run_args(command)?),
+
+            _ => Err(Error::new(ErrorKind::NotFound, format!("{} doesn't have uninstaller", program.display_name)))
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::io::Result;
+
+use crate::wine::RegValue;
+
+/// Registry key read from the `.reg` file
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RegKey {
+    /// Original key path, e.g. `Software\Wine\DllOverrides`
+    pub path: String,
+
+    /// String and dword values of the key. Default value is stored with empty name
+    ///
+    /// Values of other types are skipped
+    pub values: BTreeMap<String, RegValue>
+}
+
+impl RegKey {
+    /// Get string value of the key
+    pub fn string(&self, name: &str) -> Option<&str> {
+        match self.values.get(name) {
+            Some(RegValue::String(value)) => Some(value),
+            _ => None
+        }
+    }
+
+    /// Get dword value of the key
+    pub fn dword(&self, name: &str) -> Option<u32> {
+        match self.values.get(name) {
+            Some(RegValue::Dword(value)) => Some(*value),
+            _ => None
+        }
+    }
+}
+
+/// Registry file of the wine prefix: `system.reg`, `user.reg` or `userdef.reg`
+///
+/// Keys are relative to the file's root: `HKEY_LOCAL_MACHINE` for `system.reg`
+/// and `HKEY_CURRENT_USER` for `user.reg`
+///
+/// ```no_run
+/// use wincompatlib::prelude::*;
+///
+/// let registry = RegFile::open("/path/to/prefix/user.reg")
+///     .expect("Failed to read registry");
+///
+/// if let Some(key) = registry.key("Software\\Wine\\DllOverrides") {
+///     println!("Overrides: {:?}", key.values);
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RegFile {
+    /// Keys indexed by their lowercased path, as registry is case insensitive
+    pub keys: BTreeMap<String, RegKey>
+}
+
+/// Parse quoted string with `\"`, `\\`, `\n` and other escapes
+///
+/// Returns string and the rest of the line
+fn parse_string(line: &str) -> Option<(String, &str)> {
+    let mut chars = line.strip_prefix('"')?.char_indices();
+    let mut string = String::new();
+
+    while let Some((i, char)) = chars.next() {
+        match char {
+            '"' => return Some((string, &line[i + 2..])),
+
+            '\\' => match chars.next()?.1 {
+                'n' => string.push('\n'),
+                't' => string.push('\t'),
+                'r' => string.push('\r'),
+                '0' => string.push('\0'),
+
+                'x' => {
+                    let mut code = 0;
+
+                    while let Some(digit) = chars.clone().next().and_then(|(_, char)| char.to_digit(16)) {
+                        code = code * 16 + digit;
+
+                        chars.next();
+                    }
+
+                    string.push(char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER));
+                }
+
+                char => string.push(char)
+            }
+
+            char => string.push(char)
+        }
+    }
+
+    None
+}
+
+/// Unescape key path: `Software\\Wine` -> `Software\Wine`
+fn parse_key_path(path: &str) -> String {
+    let mut unescaped = String::with_capacity(path.len());
+    let mut chars = path.chars();
+
+    while let Some(char) = chars.next() {
+        match char {
+            '\\' => unescaped.push(chars.next().unwrap_or('\\')),
+            char => unescaped.push(char)
+        }
+    }
+
+    unescaped
+}
+
+impl RegFile {
+    pub fn open<T: AsRef<Path>>(path: T) -> Result<Self> {
+        Ok(Self::parse(&std::fs::read_to_string(path)?))
+    }
+
+    pub fn parse(content: &str) -> Self {
+        let mut keys = BTreeMap::new();
+        let mut current: Option<RegKey> = None;
+
+        for line in content.lines() {
+            if let Some(path) = line.strip_prefix('[') {
+                if let Some(key) = current.take() {
+                    keys.insert(key.path.to_lowercase(), key);
+                }
+
+                // Key line ends with modification time: [Software\\Wine] 1690000000
+                let Some((path, _)) = path.rsplit_once(']') else {
+                    continue;
+                };
+
+                current = Some(RegKey {
+                    path: parse_key_path(path),
+                    values: BTreeMap::new()
+                });
+            }
+
+            else if let Some(key) = current.as_mut() {
+                let (name, rest) = match line.strip_prefix('@') {
+                    Some(rest) => (String::new(), rest),
+
+                    None => match parse_string(line) {
+                        Some(value) => value,
+                        None => continue
+                    }
+                };
+
+                let Some(value) = rest.strip_prefix('=') else {
+                    continue;
+                };
+
+                if let Some(dword) = value.strip_prefix("dword:") {
+                    if let Ok(dword) = u32::from_str_radix(dword.trim(), 16) {
+                        key.values.insert(name, RegValue::Dword(dword));
+                    }
+                }
+
+                else if let Some((string, _)) = parse_string(value) {
+                    key.values.insert(name, RegValue::String(string));
+                }
+            }
+        }
+
+        if let Some(key) = current.take() {
+            keys.insert(key.path.to_lowercase(), key);
+        }
+
+        Self { keys }
+    }
+
+    /// Get key by its path, case insensitive
+    pub fn key(&self, path: &str) -> Option<&RegKey> {
+        self.keys.get(&path.to_lowercase())
+    }
+
+    /// Get direct subkeys of the key
+    pub fn subkeys<'a>(&'a self, path: &str) -> impl Iterator<Item = &'a RegKey> + 'a {
+        let prefix = format!("{}\\", path.to_lowercase());
+
+        self.keys.iter()
+            .filter(move |(key, _)| key.strip_prefix(&prefix).map(|name| !name.contains('\\')).unwrap_or(false))
+            .map(|(_, key)| key)
+    }
+}
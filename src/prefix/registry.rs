@@ -0,0 +1,217 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::io::{Error, ErrorKind, Result};
+
+use serde::{Serialize, Deserialize};
+
+use super::{PrefixManager, ManagedPrefix, PrefixConfig};
+
+/// Get library data folder: `$XDG_DATA_HOME/wincompatlib` or `$HOME/.local/share/wincompatlib`
+pub fn data_dir() -> PathBuf {
+    crate::xdg::data_home().join("wincompatlib")
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegistryEntry {
+    pub path: PathBuf,
+
+    /// Path to the wine binary used to run this prefix
+    pub runner: Option<PathBuf>,
+
+    /// Name of the application which registered the prefix
+    pub owner: String,
+
+    /// Arbitrary information stored by the application
+    #[serde(default)]
+    pub metadata: BTreeMap<String, String>
+}
+
+/// Named prefixes registered by all the applications using the library
+///
+/// Stored in `prefixes.toml` file of the `data_dir()` folder
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct PrefixRegistry {
+    #[serde(default)]
+    pub prefixes: BTreeMap<String, RegistryEntry>
+}
+
+impl PrefixRegistry {
+    /// Get path to the default registry file
+    pub fn default_path() -> PathBuf {
+        data_dir().join("prefixes.toml")
+    }
+
+    /// Load registry from the file, or return empty one if it doesn't exist
+    pub fn load<T: AsRef<Path>>(path: T) -> Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(registry) => toml::from_str(&registry).map_err(|err| Error::new(ErrorKind::InvalidData, err)),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(err) => Err(err)
+        }
+    }
+
+    /// Load registry from the default file
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// let registry = PrefixRegistry::load_default().expect("Failed to load prefixes registry");
+    ///
+    /// for (name, entry) in registry.prefixes {
+    ///     println!("[{}] {name}: {:?}", entry.owner, entry.path);
+    /// }
+    /// ```
+    pub fn load_default() -> Result<Self> {
+        Self::load(Self::default_path())
+    }
+
+    pub fn save<T: AsRef<Path>>(&self, path: T) -> Result<()> {
+        let path = path.as_ref();
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let registry = toml::to_string_pretty(self).map_err(|err| Error::new(ErrorKind::InvalidData, err))?;
+
+        std::fs::write(path, registry)
+    }
+
+    pub fn save_default(&self) -> Result<()> {
+        self.save(Self::default_path())
+    }
+
+    /// Find name and entry of the prefix registered with given path
+    pub fn find_by_path<T: AsRef<Path>>(&self, path: T) -> Option<(&str, &RegistryEntry)> {
+        let path = path.as_ref();
+
+        self.prefixes.iter()
+            .find(|(_, entry)| entry.path == path)
+            .map(|(name, entry)| (name.as_str(), entry))
+    }
+
+    /// Register prefix under given name
+    ///
+    /// Returns `ErrorKind::AlreadyExists` error if this name or prefix path
+    /// is already registered by another application
+    ///
+    /// ```
+    /// use wincompatlib::prelude::*;
+    ///
+    /// let mut registry = PrefixRegistry::default();
+    ///
+    /// let entry = RegistryEntry {
+    ///     path: "/path/to/prefix".into(),
+    ///     runner: None,
+    ///     owner: String::from("first-launcher"),
+    ///     metadata: Default::default()
+    /// };
+    ///
+    /// assert!(registry.register("game", entry.clone()).is_ok());
+    ///
+    /// assert!(registry.register("other-name", RegistryEntry {
+    ///     owner: String::from("second-launcher"),
+    ///     ..entry
+    /// }).is_err());
+    /// ```
+    pub fn register<T: Into<String>>(&mut self, name: T, entry: RegistryEntry) -> Result<()> {
+        let name = name.into();
+
+        if let Some(registered) = self.prefixes.get(&name) {
+            if registered.owner != entry.owner {
+                return Err(Error::new(ErrorKind::AlreadyExists, format!("Prefix {name} is already registered by {}", registered.owner)));
+            }
+        }
+
+        if let Some((registered_name, registered)) = self.find_by_path(&entry.path) {
+            if registered_name != name || registered.owner != entry.owner {
+                return Err(Error::new(ErrorKind::AlreadyExists, format!(
+                    "Prefix {:?} is already registered as {registered_name} by {}",
+                    entry.path,
+                    registered.owner
+                )));
+            }
+        }
+
+        self.prefixes.insert(name, entry);
+
+        Ok(())
+    }
+
+    /// Remove prefix registered by the owner from the registry
+    pub fn unregister(&mut self, name: &str, owner: &str) -> Result<Option<RegistryEntry>> {
+        match self.prefixes.get(name) {
+            Some(entry) if entry.owner != owner => Err(Error::new(ErrorKind::PermissionDenied, format!("Prefix {name} is registered by {}", entry.owner))),
+            Some(_) => Ok(self.prefixes.remove(name)),
+            None => Ok(None)
+        }
+    }
+}
+
+impl PrefixManager {
+    /// Load prefixes registered by the owner in the default registry
+    ///
+    /// Environment variables are read from the prefixes' config files
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// let manager = PrefixManager::load("/path/to/prefixes", "my-launcher")
+    ///     .expect("Failed to load prefixes");
+    /// ```
+    pub fn load<T: Into<PathBuf>>(root: T, owner: &str) -> Result<Self> {
+        let mut manager = Self::new(root);
+
+        manager.load_registry(&PrefixRegistry::load_default()?, owner);
+
+        Ok(manager)
+    }
+
+    /// Save prefixes to the default registry under the owner's name
+    pub fn save(&self, owner: &str) -> Result<()> {
+        let mut registry = PrefixRegistry::load_default()?;
+
+        self.save_registry(&mut registry, owner)?;
+
+        registry.save_default()
+    }
+
+    /// Add prefixes registered by the owner to the manager
+    pub fn load_registry(&mut self, registry: &PrefixRegistry, owner: &str) {
+        for (name, entry) in &registry.prefixes {
+            if entry.owner == owner {
+                let mut prefix = ManagedPrefix::new(name, &entry.path);
+
+                prefix.runner = entry.runner.clone();
+                prefix.metadata = entry.metadata.clone();
+
+                if let Ok(config) = PrefixConfig::load(&entry.path) {
+                    prefix.env = config.env;
+                }
+
+                // Replace already managed prefix with the registered one
+                self.forget(name);
+
+                let _ = self.add(prefix);
+            }
+        }
+    }
+
+    /// Register all the managed prefixes under the owner's name
+    ///
+    /// Prefixes removed from the manager are unregistered
+    pub fn save_registry(&self, registry: &mut PrefixRegistry, owner: &str) -> Result<()> {
+        registry.prefixes.retain(|name, entry| entry.owner != owner || self.get(name).is_some());
+
+        for prefix in self.list() {
+            registry.register(&prefix.name, RegistryEntry {
+                path: prefix.path.clone(),
+                runner: prefix.runner.clone(),
+                owner: owner.to_string(),
+                metadata: prefix.metadata.clone()
+            })?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,81 @@
+use std::path::{Path, PathBuf};
+use std::io::{ErrorKind, Result};
+
+use crate::lnk::windows_to_unix_path;
+
+use super::RegFile;
+
+/// Known folder id of the "Saved Games" folder
+const SAVED_GAMES_ID: &str = "{4C5C32FF-BB9D-43b0-B5B4-2D72E54EAAA4}";
+
+/// Unix paths to the user folders of the wine prefix
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShellFolders {
+    /// `Documents` folder
+    pub documents: PathBuf,
+
+    /// `AppData\Roaming` folder
+    pub app_data: PathBuf,
+
+    /// `AppData\Local` folder
+    pub local_app_data: PathBuf,
+
+    /// `Saved Games` folder
+    pub saved_games: PathBuf
+}
+
+impl ShellFolders {
+    /// Resolve user folders of the wine prefix from the `Shell Folders` registry key
+    ///
+    /// Folders not listed in the registry are taken from the user's profile folder
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// let folders = ShellFolders::resolve("/path/to/prefix")
+    ///     .expect("Failed to resolve shell folders");
+    ///
+    /// println!("Documents: {:?}", folders.documents);
+    /// ```
+    pub fn resolve<T: AsRef<Path>>(prefix: T) -> Result<Self> {
+        let prefix = prefix.as_ref();
+
+        let registry = match RegFile::open(prefix.join("user.reg")) {
+            Ok(registry) => registry,
+
+            Err(err) if err.kind() == ErrorKind::NotFound => RegFile::default(),
+            Err(err) => return Err(err)
+        };
+
+        let key = registry.key("Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\Shell Folders");
+
+        let folder = |name: &str| key.and_then(|key| key.string(name))
+            .and_then(|path| windows_to_unix_path(prefix, path));
+
+        let profile = Self::profile_folder(prefix);
+
+        Ok(Self {
+            documents: folder("Personal").unwrap_or_else(|| profile.join("Documents")),
+            app_data: folder("AppData").unwrap_or_else(|| profile.join("AppData/Roaming")),
+            local_app_data: folder("Local AppData").unwrap_or_else(|| profile.join("AppData/Local")),
+            saved_games: folder(SAVED_GAMES_ID).unwrap_or_else(|| profile.join("Saved Games"))
+        })
+    }
+
+    /// Find user's profile folder: `drive_c/users/<name>`, skipping the `Public` one
+    fn profile_folder(prefix: &Path) -> PathBuf {
+        let users = prefix.join("drive_c/users");
+
+        let user = std::env::var("USER").ok()
+            .filter(|user| users.join(user).exists())
+            .or_else(|| {
+                users.read_dir().ok()?
+                    .flatten()
+                    .map(|entry| entry.file_name().to_string_lossy().to_string())
+                    .find(|name| name != "Public")
+            })
+            .unwrap_or_else(|| String::from("steamuser"));
+
+        users.join(user)
+    }
+}
@@ -0,0 +1,180 @@
+use std::path::{Path, PathBuf};
+use std::os::unix::fs::MetadataExt;
+use std::os::unix::net::UnixStream;
+
+use crate::wine::Wine;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrefixState {
+    /// Prefix folder doesn't exist or is empty
+    Uninitialized,
+
+    /// Prefix is being created right now
+    Initializing,
+
+    /// Prefix is created and nothing runs in it
+    Ready,
+
+    /// Prefix is created and some processes run in it
+    Running {
+        n_processes: usize
+    },
+
+    /// Prefix folder has some files but misses important ones
+    Corrupted
+}
+
+impl PrefixState {
+    /// Query state of the wine prefix
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// match PrefixState::query("/path/to/prefix") {
+    ///     PrefixState::Running { n_processes } => println!("{n_processes} processes are running"),
+    ///     state => println!("Prefix state: {:?}", state)
+    /// }
+    /// ```
+    pub fn query<T: AsRef<Path>>(prefix: T) -> Self {
+        let prefix = prefix.as_ref();
+
+        let is_empty = match prefix.read_dir() {
+            Ok(mut files) => files.next().is_none(),
+            Err(_) => true
+        };
+
+        if is_empty {
+            return Self::Uninitialized;
+        }
+
+        let processes = prefix_processes(prefix).len();
+        let running = processes > 0 || is_server_running(prefix);
+
+        let created = [
+            "system.reg",
+            "user.reg",
+            "userdef.reg",
+            "drive_c/windows/system32"
+        ].iter().all(|path| prefix.join(path).exists());
+
+        // Wine creates this file when prefix update is finished
+        let updated = prefix.join(".update-timestamp").exists();
+
+        match (created && updated, running) {
+            (true, false) => Self::Ready,
+            (true, true) => Self::Running {
+                n_processes: processes
+            },
+
+            (false, true) => Self::Initializing,
+            (false, false) => Self::Corrupted
+        }
+    }
+}
+
+/// Get path to the wine prefix used when `WINEPREFIX` variable is not specified
+pub fn default_prefix() -> PathBuf {
+    match std::env::var_os("WINEPREFIX") {
+        Some(prefix) => PathBuf::from(prefix),
+        None => crate::xdg::home().join(".wine")
+    }
+}
+
+/// Get path to the wineserver socket of the prefix
+///
+/// Wine stores it in `/tmp/.wine-<uid>/server-<dev>-<inode>/socket`
+pub fn server_socket<T: AsRef<Path>>(prefix: T) -> Option<PathBuf> {
+    let metadata = prefix.as_ref().metadata().ok()?;
+    let uid = unsafe { libc::getuid() };
+
+    Some(PathBuf::from(format!("/tmp/.wine-{uid}/server-{:x}-{:x}/socket", metadata.dev(), metadata.ino())))
+}
+
+/// Check if wineserver of the prefix is running by connecting to its socket
+pub fn is_server_running<T: AsRef<Path>>(prefix: T) -> bool {
+    match server_socket(prefix) {
+        Some(socket) => UnixStream::connect(socket).is_ok(),
+        None => false
+    }
+}
+
+/// Get (pid, is wineserver) pairs of the processes running in the prefix
+fn find_processes(prefix: &Path) -> Vec<(u32, bool)> {
+    let Ok(prefix) = prefix.canonicalize() else {
+        return Vec::new();
+    };
+
+    let Ok(entries) = std::fs::read_dir("/proc") else {
+        return Vec::new();
+    };
+
+    let mut processes = Vec::new();
+
+    for entry in entries.flatten() {
+        let Some(pid) = entry.file_name().to_str().and_then(|pid| pid.parse::<u32>().ok()) else {
+            continue;
+        };
+
+        let Ok(environ) = std::fs::read(entry.path().join("environ")) else {
+            continue;
+        };
+
+        let process_prefix = environ.split(|byte| *byte == 0)
+            .find_map(|var| var.strip_prefix(b"WINEPREFIX="))
+            .map(|path| PathBuf::from(String::from_utf8_lossy(path).to_string()));
+
+        if let Some(process_prefix) = process_prefix {
+            if process_prefix.canonicalize().map(|path| path == prefix).unwrap_or(false) {
+                let comm = std::fs::read_to_string(entry.path().join("comm")).unwrap_or_default();
+
+                processes.push((pid, comm.trim_end() == "wineserver"));
+            }
+        }
+    }
+
+    processes
+}
+
+/// Get ids of the processes running in the prefix (besides wineserver itself)
+///
+/// Processes are found by their `WINEPREFIX` variable, so only processes of the current user can be found
+pub fn prefix_processes<T: AsRef<Path>>(prefix: T) -> Vec<u32> {
+    find_processes(prefix.as_ref()).into_iter()
+        .filter(|(_, is_server)| !is_server)
+        .map(|(pid, _)| pid)
+        .collect()
+}
+
+/// Get id of the wineserver process of the prefix
+///
+/// Wineserver is found by its `WINEPREFIX` variable, so it can't be found
+/// if it was started without this variable specified
+pub fn server_process<T: AsRef<Path>>(prefix: T) -> Option<u32> {
+    find_processes(prefix.as_ref()).into_iter()
+        .find(|(_, is_server)| *is_server)
+        .map(|(pid, _)| pid)
+}
+
+impl Wine {
+    /// Get path to the wine prefix used by this struct
+    ///
+    /// If prefix is not specified, then `$WINEPREFIX` or `$HOME/.wine` is returned
+    pub fn prefix_path(&self) -> PathBuf {
+        self.prefix.clone().unwrap_or_else(default_prefix)
+    }
+
+    /// Query state of the wine prefix used by this struct
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// let wine = Wine::default().with_prefix("/path/to/prefix");
+    ///
+    /// if wine.prefix_state() == PrefixState::Uninitialized {
+    ///     wine.update_prefix("/path/to/prefix").expect("Failed to create prefix");
+    /// }
+    /// ```
+    pub fn prefix_state(&self) -> PrefixState {
+        PrefixState::query(self.prefix_path())
+    }
+}
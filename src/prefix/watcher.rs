@@ -0,0 +1,269 @@
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, Weak};
+use std::sync::mpsc::Receiver;
+use std::time::Duration;
+use std::io::{Error, Result};
+
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use notify::event::{ModifyKind, RenameMode};
+
+use serde::{Serialize, Deserialize};
+
+use super::PrefixDiff;
+
+/// Time given to the watcher to receive remaining filesystem events
+const SETTLE_TIME: Duration = Duration::from_millis(200);
+
+/// Files changed inside of the wine prefix while it was watched
+///
+/// All the paths are relative to the prefix root
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PrefixChanges {
+    /// Files and folders which didn't exist before
+    pub created: BTreeSet<PathBuf>,
+
+    /// Existing files and folders which were changed
+    pub modified: BTreeSet<PathBuf>,
+
+    /// Existing files and folders which were removed
+    pub removed: BTreeSet<PathBuf>
+}
+
+impl PrefixChanges {
+    pub fn is_empty(&self) -> bool {
+        self.created.is_empty() && self.modified.is_empty() && self.removed.is_empty()
+    }
+
+    fn create(&mut self, path: PathBuf) {
+        // Removed and then created again
+        if self.removed.remove(&path) {
+            self.modified.insert(path);
+        }
+
+        else if !self.modified.contains(&path) {
+            self.created.insert(path);
+        }
+    }
+
+    fn modify(&mut self, path: PathBuf) {
+        if !self.created.contains(&path) {
+            self.modified.insert(path);
+        }
+    }
+
+    fn remove(&mut self, path: PathBuf) {
+        // Temporary files don't change the prefix
+        if !self.created.remove(&path) {
+            self.modified.remove(&path);
+            self.removed.insert(path);
+        }
+    }
+
+    fn apply(&mut self, prefix: &Path, event: Event) {
+        let mut paths = event.paths.into_iter()
+            .filter_map(|path| path.strip_prefix(prefix).ok().map(Path::to_path_buf))
+            .filter(|path| !path.as_os_str().is_empty());
+
+        match event.kind {
+            EventKind::Create(_) |
+            EventKind::Modify(ModifyKind::Name(RenameMode::To)) => paths.for_each(|path| self.create(path)),
+
+            EventKind::Remove(_) |
+            EventKind::Modify(ModifyKind::Name(RenameMode::From)) => paths.for_each(|path| self.remove(path)),
+
+            EventKind::Modify(ModifyKind::Name(RenameMode::Both)) => {
+                if let Some(from) = paths.next() {
+                    self.remove(from);
+                }
+
+                if let Some(to) = paths.next() {
+                    self.create(to);
+                }
+            }
+
+            EventKind::Modify(_) => paths.for_each(|path| self.modify(path)),
+
+            _ => ()
+        }
+    }
+}
+
+impl From<PrefixChanges> for PrefixDiff {
+    fn from(changes: PrefixChanges) -> Self {
+        Self {
+            changed: changes.created.into_iter().chain(changes.modified).collect(),
+            removed: changes.removed.into_iter().collect()
+        }
+    }
+}
+
+/// Watcher recording changes made to the wine prefix files
+///
+/// Keep in mind that wineserver flushes registry files when it's stopped,
+/// so stop it before finishing the watcher if registry changes are needed
+///
+/// ```no_run
+/// use wincompatlib::prelude::*;
+///
+/// let wine = Wine::from_binary("/path/to/wine")
+///     .with_prefix("/path/to/prefix");
+///
+/// let watcher = PrefixWatcher::new("/path/to/prefix")
+///     .expect("Failed to watch prefix");
+///
+/// wine.run("/path/to/setup.exe")
+///     .and_then(|mut child| Ok(child.wait()?))
+///     .expect("Failed to run installer");
+///
+/// wine.stop_processes(false).expect("Failed to stop wineserver");
+///
+/// for path in watcher.finish().created {
+///     println!("Installed {:?}", path);
+/// }
+/// ```
+pub struct PrefixWatcher {
+    prefix: PathBuf,
+    watcher: Arc<Mutex<RecommendedWatcher>>,
+    changes: Arc<Mutex<PrefixChanges>>
+}
+
+/// Watch the folder and all its subfolders without following symlinks
+///
+/// Prefixes link `dosdevices/z:` to `/` and user folders to `$HOME`,
+/// so recursive watchers would walk through the whole host filesystem.
+/// If `changes` are given, then all the found entries are recorded as created
+fn watch_folder(watcher: &mut RecommendedWatcher, folder: &Path, prefix: &Path, mut changes: Option<&mut PrefixChanges>) -> Result<()> {
+    watcher.watch(folder, RecursiveMode::NonRecursive)
+        .map_err(Error::other)?;
+
+    for entry in folder.read_dir()?.flatten() {
+        let path = entry.path();
+
+        if let Some(changes) = changes.as_deref_mut() {
+            if let Ok(relative) = path.strip_prefix(prefix) {
+                changes.create(relative.to_path_buf());
+            }
+        }
+
+        // Entry's file type is not resolved through the symlinks
+        if entry.file_type().is_ok_and(|kind| kind.is_dir()) {
+            watch_folder(watcher, &path, prefix, changes.as_deref_mut())?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Watch folders created after the watcher was started
+///
+/// Watches can't be added from the events handler itself, so it sends new folders here.
+/// Stops once the watcher is dropped
+fn watch_created_folders(watcher: Weak<Mutex<RecommendedWatcher>>, changes: Arc<Mutex<PrefixChanges>>, prefix: PathBuf, receiver: Receiver<PathBuf>) {
+    std::thread::spawn(move || {
+        while let Ok(folder) = receiver.recv() {
+            let Some(watcher) = watcher.upgrade() else {
+                break;
+            };
+
+            let Ok(mut watcher) = watcher.lock() else {
+                break;
+            };
+
+            // Files created before the watch was added won't produce events
+            let mut created = PrefixChanges::default();
+
+            if watch_folder(&mut watcher, &folder, &prefix, Some(&mut created)).is_ok() {
+                if let Ok(mut changes) = changes.lock() {
+                    created.created.into_iter().for_each(|path| changes.create(path));
+                }
+            }
+        }
+    });
+}
+
+impl PrefixWatcher {
+    /// Start watching the prefix folder and its subfolders
+    ///
+    /// Symlinks are not followed, so changes made through them
+    /// (e.g. in `dosdevices/z:`) are not recorded
+    pub fn new<T: Into<PathBuf>>(prefix: T) -> Result<Self> {
+        let prefix: PathBuf = prefix.into();
+
+        // Events contain canonical paths
+        let canonical = prefix.canonicalize()?;
+
+        let changes = Arc::new(Mutex::new(PrefixChanges::default()));
+
+        let (created_folders, receiver) = std::sync::mpsc::channel();
+
+        let watcher = {
+            let changes = changes.clone();
+            let prefix = canonical.clone();
+
+            notify::recommended_watcher(move |event: notify::Result<Event>| {
+                if let Ok(event) = event {
+                    if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(ModifyKind::Name(RenameMode::To | RenameMode::Both))) {
+                        for path in &event.paths {
+                            if path.symlink_metadata().is_ok_and(|metadata| metadata.is_dir()) {
+                                let _ = created_folders.send(path.clone());
+                            }
+                        }
+                    }
+
+                    if let Ok(mut changes) = changes.lock() {
+                        changes.apply(&prefix, event);
+                    }
+                }
+            }).map_err(Error::other)?
+        };
+
+        let watcher = Arc::new(Mutex::new(watcher));
+
+        watch_created_folders(Arc::downgrade(&watcher), changes.clone(), canonical.clone(), receiver);
+
+        if let Ok(mut watcher) = watcher.lock() {
+            watch_folder(&mut watcher, &canonical, &canonical, None)?;
+        }
+
+        Ok(Self {
+            prefix,
+            watcher,
+            changes
+        })
+    }
+
+    #[inline]
+    pub fn prefix(&self) -> &Path {
+        &self.prefix
+    }
+
+    /// Get changes recorded so far
+    pub fn changes(&self) -> PrefixChanges {
+        self.changes.lock()
+            .map(|changes| changes.clone())
+            .unwrap_or_default()
+    }
+
+    /// Stop watching the prefix and get all the recorded changes
+    pub fn finish(self) -> PrefixChanges {
+        std::thread::sleep(SETTLE_TIME);
+
+        // Dropping the watcher removes all its watches
+        drop(self.watcher);
+
+        self.changes.lock()
+            .map(|changes| changes.clone())
+            .unwrap_or_default()
+    }
+}
+
+impl std::fmt::Debug for PrefixWatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PrefixWatcher")
+            .field("prefix", &self.prefix)
+            .field("changes", &self.changes())
+            .finish()
+    }
+}
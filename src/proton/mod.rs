@@ -0,0 +1,9 @@
+mod user_settings;
+mod vr;
+mod tracked_files;
+mod version;
+
+pub use user_settings::*;
+pub use vr::*;
+pub use tracked_files::*;
+pub use version::*;
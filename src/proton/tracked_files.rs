@@ -0,0 +1,124 @@
+use std::path::{Path, PathBuf};
+use crate::error::Result;
+
+use crate::wine::Wine;
+
+/// Name of the file listing files proton copied to the prefix,
+/// stored next to the `pfx` folder
+pub const TRACKED_FILES_FILE: &str = "tracked_files";
+
+/// List of files proton manages in its prefix
+///
+/// Proton removes and recreates tracked files when it updates the prefix,
+/// so files replaced by other tools must be untracked to survive updates
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrackedFiles {
+    path: PathBuf,
+    prefix: PathBuf,
+
+    /// Paths relative to the prefix, e.g. `drive_c/windows/system32/d3d11.dll`
+    pub files: Vec<String>
+}
+
+impl TrackedFiles {
+    /// Get path to the `tracked_files` of the proton prefix (`compatdata/<appid>/pfx`)
+    pub fn path<T: AsRef<Path>>(prefix: T) -> Option<PathBuf> {
+        prefix.as_ref().parent().map(|parent| parent.join(TRACKED_FILES_FILE))
+    }
+
+    /// Read tracked files of the proton prefix
+    ///
+    /// Returns `Ok(None)` if the prefix is not managed by proton
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// if let Some(tracked) = TrackedFiles::open("/path/to/compatdata/570/pfx").unwrap() {
+    ///     println!("Proton manages {} files", tracked.files.len());
+    /// }
+    /// ```
+    pub fn open<T: AsRef<Path>>(prefix: T) -> Result<Option<Self>> {
+        let prefix = prefix.as_ref();
+
+        let Some(path) = Self::path(prefix).filter(|path| path.exists()) else {
+            return Ok(None);
+        };
+
+        let files = std::fs::read_to_string(&path)?
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(String::from)
+            .collect();
+
+        Ok(Some(Self {
+            path,
+            prefix: prefix.to_path_buf(),
+            files
+        }))
+    }
+
+    /// Convert path of the prefix's file to the tracked files format
+    fn relative(&self, file: &Path) -> Option<String> {
+        // File itself may not exist, and system folders are often accessed through dosdevices
+        let file = file.parent()?.canonicalize().ok()?.join(file.file_name()?);
+
+        let prefix = self.prefix.canonicalize().ok()?;
+
+        Some(file.strip_prefix(prefix).ok()?.to_string_lossy().to_string())
+    }
+
+    pub fn contains(&self, file: &Path) -> bool {
+        self.relative(file).map(|file| self.files.contains(&file)).unwrap_or(false)
+    }
+
+    /// Stop tracking the file so proton doesn't replace it. Returns `true` if it was tracked
+    pub fn untrack(&mut self, file: &Path) -> bool {
+        let Some(file) = self.relative(file) else {
+            return false;
+        };
+
+        let len = self.files.len();
+
+        self.files.retain(|tracked| tracked != &file);
+
+        self.files.len() != len
+    }
+
+    /// Let proton manage the file again
+    pub fn track(&mut self, file: &Path) {
+        if let Some(file) = self.relative(file) {
+            if !self.files.contains(&file) {
+                self.files.push(file);
+            }
+        }
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let mut content = self.files.join("\n");
+
+        content.push('\n');
+
+        Ok(std::fs::write(&self.path, content)?)
+    }
+}
+
+impl Wine {
+    /// Untrack (or track back if `tracked = true`) file installed to the proton prefix
+    ///
+    /// Does nothing for prefixes not managed by proton
+    pub(crate) fn set_proton_tracked(&self, file: &Path, tracked: bool) -> Result<()> {
+        let Some(mut tracked_files) = TrackedFiles::open(self.prefix_path())? else {
+            return Ok(());
+        };
+
+        if tracked {
+            tracked_files.track(file);
+        }
+
+        else if !tracked_files.untrack(file) {
+            return Ok(());
+        }
+
+        tracked_files.save()
+    }
+}
@@ -0,0 +1,201 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::io::ErrorKind;
+
+use crate::error::{Error, Result};
+
+use crate::wine::{LogLevel, DllOverrides};
+
+/// Name of the settings file read by the `proton` script from its own folder
+pub const USER_SETTINGS_FILE: &str = "user_settings.py";
+
+/// Default environment variables of the proton install, stored in its `user_settings.py` file
+///
+/// ```
+/// use wincompatlib::prelude::*;
+///
+/// let settings = UserSettings::default()
+///     .with_log_level(LogLevel::Debug)
+///     .with("PROTON_USE_WINED3D", "1");
+///
+/// assert!(settings.to_string().contains("\"PROTON_USE_WINED3D\": \"1\","));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct UserSettings {
+    pub settings: BTreeMap<String, String>
+}
+
+/// Quote string as python string literal
+///
+/// Control characters are escaped, so multiline values stay on one line of the file
+fn quote(value: &str) -> String {
+    let mut quoted = String::from("\"");
+
+    for char in value.chars() {
+        match char {
+            '\\' => quoted.push_str("\\\\"),
+            '"'  => quoted.push_str("\\\""),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+
+            char if char.is_ascii_control() => quoted.push_str(&format!("\\x{:02x}", char as u32)),
+
+            char => quoted.push(char)
+        }
+    }
+
+    quoted.push('"');
+
+    quoted
+}
+
+/// Read python string literal from the beginning of the value
+///
+/// Returns string and the rest of the value
+fn unquote(value: &str) -> Option<(String, &str)> {
+    let mut chars = value.char_indices();
+
+    let (_, quote) = chars.next().filter(|(_, char)| matches!(char, '"' | '\''))?;
+
+    let mut string = String::new();
+
+    while let Some((i, char)) = chars.next() {
+        match char {
+            '\\' => match chars.next()?.1 {
+                'n' => string.push('\n'),
+                'r' => string.push('\r'),
+                't' => string.push('\t'),
+
+                'x' => {
+                    let code = [chars.next()?.1, chars.next()?.1].into_iter().collect::<String>();
+
+                    string.push(char::from(u8::from_str_radix(&code, 16).ok()?));
+                }
+
+                char => string.push(char)
+            }
+            char if char == quote => return Some((string, &value[i + 1..])),
+            char => string.push(char)
+        }
+    }
+
+    None
+}
+
+impl UserSettings {
+    /// Get path to the settings file of the proton install
+    ///
+    /// ```
+    /// use wincompatlib::prelude::*;
+    ///
+    /// use std::path::PathBuf;
+    ///
+    /// assert_eq!(UserSettings::path("/path/to/Proton 8.0"), PathBuf::from("/path/to/Proton 8.0/user_settings.py"));
+    /// ```
+    pub fn path<T: AsRef<Path>>(proton_dir: T) -> PathBuf {
+        proton_dir.as_ref().join(USER_SETTINGS_FILE)
+    }
+
+    pub fn with<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
+        self.settings.insert(key.into(), value.into());
+
+        self
+    }
+
+    /// Set proton, wine, DXVK and VKD3D log variables
+    pub fn with_log_level(mut self, log_level: LogLevel) -> Self {
+        for (key, value) in log_level.get_envs() {
+            self.settings.insert(key.to_string(), value.to_string());
+        }
+
+        self
+    }
+
+    /// Set `WINEDLLOVERRIDES` variable
+    ///
+    /// ```
+    /// use wincompatlib::prelude::*;
+    ///
+    /// let settings = UserSettings::default()
+    ///     .with_dll_overrides(&DllOverrides::new().with("d3d9", OverrideMode::NativeBuiltin));
+    ///
+    /// assert_eq!(settings.settings["WINEDLLOVERRIDES"], "d3d9=native,builtin");
+    /// ```
+    pub fn with_dll_overrides(self, overrides: &DllOverrides) -> Self {
+        self.with("WINEDLLOVERRIDES", overrides.to_string())
+    }
+
+    /// Parse `"KEY": "VALUE",` entries of the settings file
+    ///
+    /// Anything else (comments, python code) is ignored
+    pub fn parse(content: &str) -> Self {
+        let mut settings = BTreeMap::new();
+
+        for line in content.lines() {
+            let line = line.trim();
+
+            let Some((key, rest)) = unquote(line) else {
+                continue;
+            };
+
+            let Some(rest) = rest.trim_start().strip_prefix(':') else {
+                continue;
+            };
+
+            if let Some((value, _)) = unquote(rest.trim_start()) {
+                settings.insert(key, value);
+            }
+        }
+
+        Self { settings }
+    }
+
+    /// Load settings of the proton install
+    ///
+    /// Returns `ErrorKind::NotFound` error if there's no settings file
+    pub fn load<T: AsRef<Path>>(proton_dir: T) -> Result<Self> {
+        let proton_dir = proton_dir.as_ref();
+
+        if !proton_dir.join("proton").exists() {
+            return Err(Error::new(ErrorKind::InvalidInput, format!("{:?} is not a proton install", proton_dir)));
+        }
+
+        Ok(Self::parse(&std::fs::read_to_string(Self::path(proton_dir))?))
+    }
+
+    /// Write settings next to the `proton` script
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// UserSettings::default()
+    ///     .with_log_level(LogLevel::Silent)
+    ///     .save("/path/to/Proton 8.0")
+    ///     .expect("Failed to save proton settings");
+    /// ```
+    pub fn save<T: AsRef<Path>>(&self, proton_dir: T) -> Result<()> {
+        let proton_dir = proton_dir.as_ref();
+
+        if !proton_dir.join("proton").exists() {
+            return Err(Error::new(ErrorKind::InvalidInput, format!("{:?} is not a proton install", proton_dir)));
+        }
+
+        Ok(std::fs::write(Self::path(proton_dir), self.to_string())?)
+    }
+}
+
+impl std::fmt::Display for UserSettings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "#!/usr/bin/env python3")?;
+        writeln!(f, "# Generated by wincompatlib")?;
+        writeln!(f)?;
+        writeln!(f, "user_settings = {{")?;
+
+        for (key, value) in &self.settings {
+            writeln!(f, "    {}: {},", quote(key), quote(value))?;
+        }
+
+        writeln!(f, "}}")
+    }
+}
@@ -0,0 +1,106 @@
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use crate::error::{Error, Result};
+
+/// Name of the file with build timestamp and version shipped in every proton build
+pub const VERSION_FILE: &str = "version";
+
+/// Version of the proton build
+///
+/// ```
+/// use wincompatlib::prelude::*;
+///
+/// let version = ProtonVersion::parse("1689245485 proton-8.0-3");
+///
+/// assert_eq!(version.name, "proton-8.0-3");
+/// assert_eq!(version.timestamp, Some(1689245485));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ProtonVersion {
+    /// Content of the version file or the `--version` output
+    pub raw: String,
+
+    /// Version name, e.g. `proton-8.0-3` or `GE-Proton8-25`
+    pub name: String,
+
+    /// Unix timestamp of the build
+    pub timestamp: Option<u64>
+}
+
+impl ProtonVersion {
+    /// Parse `<timestamp> <name>` string. Timestamp is optional
+    pub fn parse<T: AsRef<str>>(raw: T) -> Self {
+        let raw = raw.as_ref().trim();
+
+        let (timestamp, name) = match raw.split_once(char::is_whitespace) {
+            Some((timestamp, name)) => match timestamp.parse() {
+                Ok(timestamp) => (Some(timestamp), name.trim()),
+                Err(_) => (None, raw)
+            },
+
+            None => (None, raw)
+        };
+
+        Self {
+            raw: raw.to_string(),
+            name: name.to_string(),
+            timestamp
+        }
+    }
+
+    /// Read version file of the proton folder
+    pub fn from_folder<T: AsRef<Path>>(folder: T) -> Result<Self> {
+        let raw = std::fs::read_to_string(folder.as_ref().join(VERSION_FILE))?;
+
+        let version = Self::parse(raw);
+
+        if version.name.is_empty() {
+            return Err(Error::VersionParse(version.raw));
+        }
+
+        Ok(version)
+    }
+
+    /// Get version of the proton build using its `proton` script or wine binary
+    ///
+    /// Version file is searched in the binary's folder and its parents
+    /// (`files/bin/wine` is stored deeper than the file). If there's no version file
+    /// the binary is run with `--version` flag
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// let version = ProtonVersion::from_binary("/path/to/proton/files/bin/wine")
+    ///     .expect("Failed to get proton version");
+    ///
+    /// println!("Proton version: {}", version.name);
+    /// ```
+    pub fn from_binary<T: Into<PathBuf>>(binary: T) -> Result<Self> {
+        let binary = binary.into();
+
+        for folder in binary.ancestors().skip(1).take(3) {
+            if folder.join(VERSION_FILE).is_file() {
+                return Self::from_folder(folder);
+            }
+        }
+
+        let output = Command::new(&binary)
+            .arg("--version")
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .output()
+            .map_err(|err| Error::from_spawn(&binary, err))?;
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+
+        let version = Self::parse(stdout.lines().next().unwrap_or_default());
+
+        if !output.status.success() || version.name.is_empty() {
+            return Err(Error::VersionParse(version.raw));
+        }
+
+        Ok(version)
+    }
+}
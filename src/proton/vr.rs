@@ -0,0 +1,115 @@
+use std::path::{Path, PathBuf};
+use std::io::ErrorKind;
+
+use crate::error::{Error, Result};
+
+use serde::Deserialize;
+
+use crate::wine::{Wine, WineRegExt};
+use crate::xdg::{home, config_home};
+
+/// Content of the `~/.config/openvr/openvrpaths.vrpath` file
+#[derive(Deserialize)]
+struct OpenVrPaths {
+    #[serde(default)]
+    runtime: Vec<PathBuf>
+}
+
+/// Host VR runtimes used by proton's `vrclient` and `wineopenxr`
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct VrRuntime {
+    /// Path to the SteamVR install
+    pub steamvr: Option<PathBuf>,
+
+    /// Path to the active OpenXR runtime manifest
+    pub openxr_runtime: Option<PathBuf>
+}
+
+impl VrRuntime {
+    /// Find SteamVR install and active OpenXR runtime of the host system
+    ///
+    /// SteamVR is taken from the OpenVR paths file registered by SteamVR itself,
+    /// or from the default steam library
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// let runtime = VrRuntime::detect();
+    ///
+    /// println!("SteamVR: {:?}, OpenXR: {:?}", runtime.steamvr, runtime.openxr_runtime);
+    /// ```
+    pub fn detect() -> Self {
+        let openvr_paths = std::fs::read(config_home().join("openvr/openvrpaths.vrpath")).ok()
+            .and_then(|paths| serde_json::from_slice::<OpenVrPaths>(&paths).ok())
+            .map(|paths| paths.runtime)
+            .unwrap_or_default();
+
+        let steamvr = openvr_paths.into_iter()
+            .chain([
+                home().join(".steam/steam/steamapps/common/SteamVR"),
+                home().join(".local/share/Steam/steamapps/common/SteamVR")
+            ])
+            .find(|path| path.join("bin/linux64/vrclient.so").exists());
+
+        let openxr_runtime = std::env::var_os("XR_RUNTIME_JSON")
+            .map(PathBuf::from)
+            .into_iter()
+            .chain([
+                config_home().join("openxr/1/active_runtime.json"),
+                PathBuf::from("/etc/xdg/openxr/1/active_runtime.json")
+            ])
+            .find(|path| path.exists());
+
+        Self {
+            steamvr,
+            openxr_runtime
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steamvr.is_none() && self.openxr_runtime.is_none()
+    }
+
+    /// Get environment variables pointing VR clients to the host runtimes
+    pub fn get_envs(&self) -> Vec<(&'static str, &Path)> {
+        let mut envs = Vec::new();
+
+        if let Some(steamvr) = &self.steamvr {
+            envs.push(("PROTON_VR_RUNTIME", steamvr.as_path()));
+            envs.push(("VR_OVERRIDE", steamvr.as_path()));
+        }
+
+        if let Some(openxr_runtime) = &self.openxr_runtime {
+            envs.push(("XR_RUNTIME_JSON", openxr_runtime.as_path()));
+        }
+
+        envs
+    }
+
+    /// Write runtime paths to the `HKCU\Software\Wine\VR` key of the prefix,
+    /// where they're read by proton's `vrclient` and `wineopenxr`
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// let wine = Wine::from_binary("/path/to/proton/files/bin/wine")
+    ///     .with_prefix("/path/to/prefix");
+    ///
+    /// VrRuntime::detect().install(&wine).expect("Failed to setup VR");
+    /// ```
+    pub fn install(&self, wine: &Wine) -> Result<()> {
+        if self.is_empty() {
+            return Err(Error::new(ErrorKind::NotFound, "No VR runtime found"));
+        }
+
+        if let Some(steamvr) = &self.steamvr {
+            wine.reg_add("HKEY_CURRENT_USER\\Software\\Wine\\VR", "openvr_rt", steamvr.join("bin/linux64/vrclient.so").to_string_lossy().to_string())?;
+        }
+
+        if let Some(openxr_runtime) = &self.openxr_runtime {
+            wine.reg_add("HKEY_CURRENT_USER\\Software\\Wine\\VR", "openxr_rt", openxr_runtime.to_string_lossy().to_string())?;
+        }
+
+        Ok(())
+    }
+}
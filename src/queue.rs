@@ -0,0 +1,229 @@
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, Condvar};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Sender, RecvTimeoutError, TryRecvError};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::time::Duration;
+use crate::error::Result;
+
+pub type JobId = u64;
+
+type Job = Box<dyn FnOnce() -> Result<()> + Send>;
+
+/// Workers are stopped after this time without jobs
+const WORKER_IDLE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Number of done jobs which statuses are kept, older ones are forgotten
+pub const JOBS_HISTORY_SIZE: usize = 256;
+
+/// Prefix workers: (prefix, (worker id, jobs sender))
+type Workers = Mutex<HashMap<PathBuf, (u64, Sender<(JobId, Job)>)>>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Finished,
+
+    /// Job has returned an error or panicked
+    Failed(String)
+}
+
+impl JobStatus {
+    pub fn is_done(&self) -> bool {
+        matches!(self, Self::Finished | Self::Failed(_))
+    }
+}
+
+#[derive(Default)]
+struct JobsHistory {
+    statuses: HashMap<JobId, JobStatus>,
+
+    /// Done jobs, from the oldest one
+    done: VecDeque<JobId>
+}
+
+#[derive(Default)]
+struct Statuses {
+    history: Mutex<JobsHistory>,
+    updated: Condvar
+}
+
+impl Statuses {
+    fn set(&self, id: JobId, status: JobStatus) {
+        if let Ok(mut history) = self.history.lock() {
+            if status.is_done() {
+                history.done.push_back(id);
+
+                while history.done.len() > JOBS_HISTORY_SIZE {
+                    if let Some(old) = history.done.pop_front() {
+                        history.statuses.remove(&old);
+                    }
+                }
+            }
+
+            history.statuses.insert(id, status);
+        }
+
+        self.updated.notify_all();
+    }
+}
+
+/// Queue of mutating prefix operations (installs, boots, registry edits)
+///
+/// Operations on the same prefix are run one by one in the order they were pushed,
+/// while operations on different prefixes run in parallel. Statuses of only
+/// the last `JOBS_HISTORY_SIZE` done jobs are kept
+///
+/// ```no_run
+/// use wincompatlib::prelude::*;
+///
+/// let queue = OperationQueue::new();
+/// let wine = Wine::default().with_prefix("/path/to/prefix");
+///
+/// let update = {
+///     let wine = wine.clone();
+///
+///     queue.push("/path/to/prefix", move || wine.update_prefix("/path/to/prefix").map(|_| ()))
+/// };
+///
+/// let dxvk = queue.push("/path/to/prefix", move || wine.install_dxvk("/path/to/dxvk", InstallParams::default()));
+///
+/// println!("Prefix update status: {:?}", queue.status(update));
+/// println!("DXVK installation status: {:?}", queue.wait(dxvk));
+/// ```
+#[derive(Default)]
+pub struct OperationQueue {
+    workers: Arc<Workers>,
+    statuses: Arc<Statuses>,
+    next_id: AtomicU64,
+    next_worker_id: AtomicU64
+}
+
+/// Run job, converting its panic into an error
+fn run_job(job: Job) -> JobStatus {
+    match catch_unwind(AssertUnwindSafe(job)) {
+        Ok(Ok(())) => JobStatus::Finished,
+        Ok(Err(err)) => JobStatus::Failed(err.to_string()),
+
+        Err(panic) => {
+            let message = panic.downcast_ref::<&str>().map(|message| message.to_string())
+                .or_else(|| panic.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| String::from("unknown reason"));
+
+            JobStatus::Failed(format!("Job panicked: {message}"))
+        }
+    }
+}
+
+impl OperationQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Push job operating on the prefix to the queue
+    pub fn push<T, F>(&self, prefix: T, job: F) -> JobId
+    where
+        T: AsRef<Path>,
+        F: FnOnce() -> Result<()> + Send + 'static
+    {
+        let prefix = prefix.as_ref();
+        let prefix = prefix.canonicalize().unwrap_or_else(|_| prefix.to_path_buf());
+
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+
+        self.statuses.set(id, JobStatus::Queued);
+
+        let mut workers = self.workers.lock().unwrap_or_else(|err| err.into_inner());
+
+        let mut job: Job = Box::new(job);
+
+        // Try to send job to the existing worker. It could have exited
+        // if the receiver was dropped, so then we'll start new one
+        if let Some((_, worker)) = workers.get(&prefix) {
+            match worker.send((id, job)) {
+                Ok(()) => return id,
+                Err(mpsc::SendError((_, returned))) => job = returned
+            }
+        }
+
+        let (sender, receiver) = mpsc::channel::<(JobId, Job)>();
+
+        let worker_id = self.next_worker_id.fetch_add(1, Ordering::Relaxed);
+        let statuses = self.statuses.clone();
+        let all_workers = self.workers.clone();
+        let worker_prefix = prefix.clone();
+
+        std::thread::spawn(move || loop {
+            let (id, job) = match receiver.recv_timeout(WORKER_IDLE_TIMEOUT) {
+                Ok(job) => job,
+                Err(RecvTimeoutError::Disconnected) => break,
+
+                Err(RecvTimeoutError::Timeout) => {
+                    // Jobs are sent under the lock, so nothing can be pushed
+                    // to this worker after the check below
+                    let mut workers = all_workers.lock().unwrap_or_else(|err| err.into_inner());
+
+                    match receiver.try_recv() {
+                        Ok(job) => job,
+
+                        Err(TryRecvError::Empty | TryRecvError::Disconnected) => {
+                            if workers.get(&worker_prefix).map(|(id, _)| *id) == Some(worker_id) {
+                                workers.remove(&worker_prefix);
+                            }
+
+                            break;
+                        }
+                    }
+                }
+            };
+
+            statuses.set(id, JobStatus::Running);
+            statuses.set(id, run_job(job));
+        });
+
+        // Receiver can't be dropped before the message is read
+        let _ = sender.send((id, job));
+
+        workers.insert(prefix, (worker_id, sender));
+
+        id
+    }
+
+    /// Get status of the job
+    ///
+    /// Returns `None` for unknown jobs and done jobs which are not in the history anymore
+    pub fn status(&self, id: JobId) -> Option<JobStatus> {
+        self.statuses.history.lock().ok()?.statuses.get(&id).cloned()
+    }
+
+    /// Block until the job is done and return its final status
+    pub fn wait(&self, id: JobId) -> Option<JobStatus> {
+        let mut history = self.statuses.history.lock().ok()?;
+
+        loop {
+            match history.statuses.get(&id) {
+                Some(status) if status.is_done() => return Some(status.clone()),
+                Some(_) => history = self.statuses.updated.wait(history).ok()?,
+                None => return None
+            }
+        }
+    }
+
+    /// Get ids of the jobs which are not done yet
+    pub fn pending(&self) -> Vec<JobId> {
+        let Ok(history) = self.statuses.history.lock() else {
+            return Vec::new();
+        };
+
+        let mut pending = history.statuses.iter()
+            .filter(|(_, status)| !status.is_done())
+            .map(|(id, _)| *id)
+            .collect::<Vec<_>>();
+
+        pending.sort();
+
+        pending
+    }
+}
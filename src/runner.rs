@@ -0,0 +1,83 @@
+use std::collections::HashMap;
+use std::ffi::{OsStr, OsString};
+use std::path::PathBuf;
+use std::process::{Child, Output};
+use crate::error::Result;
+
+use crate::wine::*;
+
+/// Common interface of the wine based runners
+///
+/// Lets launchers accept any runner implementation, e.g. `&dyn WineRunner`
+///
+/// ```no_run
+/// use wincompatlib::prelude::*;
+///
+/// fn launch_game(runner: &dyn WineRunner) -> std::io::Result<()> {
+///     if runner.prefix_state() == PrefixState::Uninitialized {
+///         runner.boot_prefix()?;
+///     }
+///
+///     runner.run_program(&["/path/to/game.exe".as_ref()])?.wait()?;
+///
+///     Ok(())
+/// }
+///
+/// launch_game(&Wine::default().with_prefix("/path/to/prefix")).expect("Failed to launch game");
+/// ```
+pub trait WineRunner {
+    /// Path to the runner binary
+    fn binary(&self) -> PathBuf;
+
+    /// Path to the wine prefix used by the runner
+    fn prefix_path(&self) -> PathBuf;
+
+    /// Architecture of the wine prefix, if it's specified
+    fn arch(&self) -> Option<WineArch>;
+
+    /// Environment variables set for the started processes
+    fn get_envs(&self) -> HashMap<&str, OsString>;
+
+    /// Run program in the prefix
+    fn run_program(&self, args: &[&OsStr]) -> Result<Child>;
+
+    /// Create or update the prefix
+    fn boot_prefix(&self) -> Result<Output>;
+
+    /// Query state of the prefix
+    fn prefix_state(&self) -> crate::prefix::PrefixState {
+        crate::prefix::PrefixState::query(self.prefix_path())
+    }
+}
+
+impl WineRunner for Wine {
+    #[inline]
+    fn binary(&self) -> PathBuf {
+        Wine::binary(self)
+    }
+
+    #[inline]
+    fn prefix_path(&self) -> PathBuf {
+        Wine::prefix_path(self)
+    }
+
+    #[inline]
+    fn arch(&self) -> Option<WineArch> {
+        self.arch
+    }
+
+    #[inline]
+    fn get_envs(&self) -> HashMap<&str, OsString> {
+        Wine::get_envs(self)
+    }
+
+    #[inline]
+    fn run_program(&self, args: &[&OsStr]) -> Result<Child> {
+        self.run_args(args)
+    }
+
+    #[inline]
+    fn boot_prefix(&self) -> Result<Output> {
+        self.update_prefix(Wine::prefix_path(self))
+    }
+}
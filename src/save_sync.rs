@@ -0,0 +1,103 @@
+use std::io::Result;
+
+use crate::wine::Wine;
+use crate::prefix::ShellFolders;
+
+type Hook = Box<dyn Fn(&ShellFolders) -> Result<()> + Send + Sync>;
+
+/// Hooks running before and after the game to synchronize its saves,
+/// e.g. with a cloud storage
+///
+/// ```no_run
+/// use wincompatlib::prelude::*;
+///
+/// let wine = Wine::from_binary("/path/to/wine")
+///     .with_prefix("/path/to/prefix");
+///
+/// let sync = SaveSync::new()
+///     .with_pre(|folders| {
+///         println!("Downloading saves to {:?}", folders.saved_games);
+///
+///         Ok(())
+///     })
+///     .with_post(|folders| {
+///         println!("Uploading saves from {:?}", folders.saved_games);
+///
+///         Ok(())
+///     });
+///
+/// sync.run(&wine, |wine| {
+///     wine.run("/path/to/game.exe")?.wait()?;
+///
+///     Ok(())
+/// }).expect("Failed to run the game");
+/// ```
+#[derive(Default)]
+pub struct SaveSync {
+    pre: Option<Hook>,
+    post: Option<Hook>
+}
+
+impl SaveSync {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set hook running before the game. The game is not started if it fails
+    pub fn with_pre<F>(self, hook: F) -> Self
+    where
+        F: Fn(&ShellFolders) -> Result<()> + Send + Sync + 'static
+    {
+        Self {
+            pre: Some(Box::new(hook)),
+            ..self
+        }
+    }
+
+    /// Set hook running after the game, even if the game failed
+    pub fn with_post<F>(self, hook: F) -> Self
+    where
+        F: Fn(&ShellFolders) -> Result<()> + Send + Sync + 'static
+    {
+        Self {
+            post: Some(Box::new(hook)),
+            ..self
+        }
+    }
+
+    /// Resolve prefix's user folders and run the game between the hooks
+    ///
+    /// The game closure should wait until the game exits
+    pub fn run<T, F>(&self, wine: &Wine, game: F) -> Result<T>
+    where
+        F: FnOnce(&Wine) -> Result<T>
+    {
+        let folders = ShellFolders::resolve(wine.prefix_path())?;
+
+        if let Some(pre) = &self.pre {
+            pre(&folders)?;
+        }
+
+        let result = game(wine);
+
+        if let Some(post) = &self.post {
+            // Game's error is more important than the sync one
+            let synced = post(&folders);
+
+            if result.is_ok() {
+                synced?;
+            }
+        }
+
+        result
+    }
+}
+
+impl std::fmt::Debug for SaveSync {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SaveSync")
+            .field("pre", &self.pre.is_some())
+            .field("post", &self.post.is_some())
+            .finish()
+    }
+}
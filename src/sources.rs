@@ -0,0 +1,241 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::io::{Error, ErrorKind, Result};
+
+use serde::{Serialize, Deserialize};
+
+/// Downloadable component, e.g. wine build or DXVK release
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Source {
+    /// URLs of the component's archive, tried in order
+    ///
+    /// `{version}` is replaced with the requested version
+    pub urls: Vec<String>
+}
+
+impl Source {
+    pub fn new<T: Into<String>>(url: T) -> Self {
+        Self {
+            urls: vec![url.into()]
+        }
+    }
+}
+
+/// Set of download sources of the components
+///
+/// Default sources are the official GitHub releases, which can be replaced or extended
+/// with mirrors and custom sources, e.g. self-hosted DXVK builds
+///
+/// ```
+/// use wincompatlib::prelude::*;
+///
+/// let sources = Sources::default()
+///     .with_mirror("dxvk", "https://mirror.example.com/dxvk/dxvk-{version}.tar.gz");
+///
+/// assert_eq!(sources.urls("dxvk", "2.1").unwrap(), [
+///     "https://mirror.example.com/dxvk/dxvk-2.1.tar.gz",
+///     "https://github.com/doitsujin/dxvk/releases/download/v2.1/dxvk-2.1.tar.gz"
+/// ]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Sources {
+    pub sources: BTreeMap<String, Source>,
+
+    /// Proxy used for downloading, e.g. `http://proxy.example.com:3128`
+    pub proxy: Option<String>
+}
+
+impl Default for Sources {
+    fn default() -> Self {
+        let sources = [
+            ("wine-ge", "https://github.com/GloriousEggroll/wine-ge-custom/releases/download/{version}/wine-lutris-{version}-x86_64.tar.xz"),
+            ("soda", "https://github.com/bottlesdevs/wine/releases/download/soda-{version}/soda-{version}-x86_64.tar.xz"),
+            ("caffe", "https://github.com/bottlesdevs/wine/releases/download/caffe-{version}/caffe-{version}-x86_64.tar.xz"),
+            ("dxvk", "https://github.com/doitsujin/dxvk/releases/download/v{version}/dxvk-{version}.tar.gz"),
+            ("vkd3d-proton", "https://github.com/HansKristian-Work/vkd3d-proton/releases/download/v{version}/vkd3d-proton-{version}.tar.zst"),
+            ("dxvk-nvapi", "https://github.com/jp7677/dxvk-nvapi/releases/download/v{version}/dxvk-nvapi-v{version}.tar.gz")
+        ];
+
+        Self {
+            sources: sources.into_iter()
+                .map(|(name, url)| (name.to_string(), Source::new(url)))
+                .collect(),
+
+            proxy: None
+        }
+    }
+}
+
+impl Sources {
+    /// Load sources from the TOML file
+    pub fn load<T: AsRef<Path>>(path: T) -> Result<Self> {
+        let sources = std::fs::read_to_string(path)?;
+
+        toml::from_str(&sources).map_err(|err| Error::new(ErrorKind::InvalidData, err))
+    }
+
+    /// Save sources to the TOML file
+    pub fn save<T: AsRef<Path>>(&self, path: T) -> Result<()> {
+        let sources = toml::to_string_pretty(self)
+            .map_err(|err| Error::new(ErrorKind::InvalidData, err))?;
+
+        std::fs::write(path, sources)
+    }
+
+    /// Add or replace the component's source
+    pub fn with_source<T: Into<String>>(mut self, name: T, source: Source) -> Self {
+        self.sources.insert(name.into(), source);
+
+        self
+    }
+
+    /// Add URL which is tried before the other URLs of the component
+    pub fn with_mirror<N: Into<String>, U: Into<String>>(mut self, name: N, url: U) -> Self {
+        self.sources.entry(name.into())
+            .or_default()
+            .urls
+            .insert(0, url.into());
+
+        self
+    }
+
+    pub fn with_proxy<T: Into<String>>(self, proxy: T) -> Self {
+        Self {
+            proxy: Some(proxy.into()),
+            ..self
+        }
+    }
+
+    /// Get download URLs of the component's version
+    pub fn urls(&self, name: &str, version: &str) -> Option<Vec<String>> {
+        self.sources.get(name).map(|source| {
+            source.urls.iter()
+                .map(|url| url.replace("{version}", version))
+                .collect()
+        })
+    }
+
+    /// Download component's archive using `curl`, trying all its URLs
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// Sources::default()
+    ///     .download("dxvk", "2.1", "/tmp/dxvk-2.1.tar.gz")
+    ///     .expect("Failed to download DXVK");
+    /// ```
+    pub fn download<T: AsRef<Path>>(&self, name: &str, version: &str, path: T) -> Result<()> {
+        let path = path.as_ref();
+
+        let Some(urls) = self.urls(name, version) else {
+            return Err(Error::new(ErrorKind::NotFound, format!("Unknown source: {name}")));
+        };
+
+        let mut errors = Vec::new();
+
+        for url in urls {
+            let mut command = Command::new("curl");
+
+            command.args(["-L", "-f", "-s", "-S", "-o"])
+                .arg(path)
+                .arg(&url)
+                .stdin(Stdio::null());
+
+            if let Some(proxy) = &self.proxy {
+                command.arg("--proxy").arg(proxy);
+            }
+
+            let output = command.output()?;
+
+            if output.status.success() {
+                return Ok(());
+            }
+
+            errors.push(format!("{url}: {}", String::from_utf8_lossy(&output.stderr).trim()));
+        }
+
+        Err(Error::other(format!("Failed to download {name} {version}: {}", errors.join("; "))))
+    }
+
+    /// Download component's archive and extract it to the folder using `tar`
+    pub fn download_and_extract<T: AsRef<Path>>(&self, name: &str, version: &str, folder: T) -> Result<()> {
+        let folder = folder.as_ref();
+
+        std::fs::create_dir_all(folder)?;
+
+        let archive = folder.join(format!(".{name}-{version}.download"));
+
+        self.download(name, version, &archive)?;
+
+        let output = Command::new("tar")
+            .arg("-xf")
+            .arg(&archive)
+            .current_dir(folder)
+            .output();
+
+        std::fs::remove_file(&archive)?;
+
+        let output = output?;
+
+        match output.status.success() {
+            true  => Ok(()),
+            false => Err(Error::other(format!("Failed to extract {name} {version}: {}", String::from_utf8_lossy(&output.stderr).trim())))
+        }
+    }
+
+    /// Get path to the build downloaded by `Sources::download_build`: `<builds>/<name>-<version>`
+    pub fn build_folder<T: AsRef<Path>>(builds: T, name: &str, version: &str) -> PathBuf {
+        builds.as_ref().join(format!("{name}-{version}"))
+    }
+
+    /// Download component's build to the `<builds>/<name>-<version>` folder and return its path
+    ///
+    /// If the archive contains a single folder, then its content is stored there,
+    /// so e.g. DXVK libraries are placed in `<builds>/dxvk-<version>/x64`.
+    /// Already downloaded builds are skipped
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// let dxvk = Sources::default()
+    ///     .download_build("dxvk", "2.1", "/path/to/builds")
+    ///     .expect("Failed to download DXVK");
+    ///
+    /// assert!(dxvk.join("x64/d3d11.dll").exists());
+    /// ```
+    pub fn download_build<T: AsRef<Path>>(&self, name: &str, version: &str, builds: T) -> Result<PathBuf> {
+        let folder = Self::build_folder(&builds, name, version);
+
+        if folder.exists() {
+            return Ok(folder);
+        }
+
+        let extracted = builds.as_ref().join(format!(".{name}-{version}.extract"));
+
+        if extracted.exists() {
+            std::fs::remove_dir_all(&extracted)?;
+        }
+
+        if let Err(err) = self.download_and_extract(name, version, &extracted) {
+            std::fs::remove_dir_all(&extracted)?;
+
+            return Err(err);
+        }
+
+        let entries = std::fs::read_dir(&extracted)?.collect::<Result<Vec<_>>>()?;
+
+        match entries.as_slice() {
+            [entry] if entry.file_type()?.is_dir() => {
+                std::fs::rename(entry.path(), &folder)?;
+                std::fs::remove_dir(&extracted)?;
+            }
+
+            _ => std::fs::rename(&extracted, &folder)?
+        }
+
+        Ok(folder)
+    }
+}
@@ -0,0 +1,135 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::io::ErrorKind;
+
+use crate::error::{Error, Result};
+
+use super::{Vdf, VdfObject, is_steam_running};
+
+const COMPAT_TOOL_MAPPING: &[&str] = &["InstallConfigStore", "Software", "Valve", "Steam", "CompatToolMapping"];
+
+/// Compatibility tool assigned to the game by Steam
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompatTool {
+    /// Internal name of the tool, e.g. `proton_8` or `GE-Proton9-4`
+    pub name: String,
+
+    pub config: String,
+    pub priority: u32
+}
+
+/// Steam's `config/config.vdf` file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SteamConfig {
+    path: PathBuf,
+    root: VdfObject
+}
+
+impl SteamConfig {
+    /// Read config of the Steam installation, see `steam_root`
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// let config = SteamConfig::open(steam_root().unwrap())
+    ///     .expect("Failed to read steam config");
+    ///
+    /// for (appid, tool) in config.compat_tools() {
+    ///     println!("{appid}: {}", tool.name);
+    /// }
+    /// ```
+    pub fn open<T: AsRef<Path>>(steam_root: T) -> Result<Self> {
+        let path = steam_root.as_ref().join("config/config.vdf");
+
+        Ok(Self {
+            root: VdfObject::parse(&std::fs::read_to_string(&path)?)?,
+            path
+        })
+    }
+
+    #[inline]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    #[inline]
+    pub fn vdf(&self) -> &VdfObject {
+        &self.root
+    }
+
+    /// Get compatibility tools assigned to the games
+    ///
+    /// Appid `0` stores the tool used by default for all the games
+    pub fn compat_tools(&self) -> BTreeMap<String, CompatTool> {
+        let Some(mapping) = self.root.get_path(COMPAT_TOOL_MAPPING).and_then(Vdf::as_object) else {
+            return BTreeMap::new();
+        };
+
+        mapping.entries.iter()
+            .filter_map(|(appid, tool)| {
+                let tool = tool.as_object()?;
+
+                let name = tool.get("name").and_then(Vdf::as_str).unwrap_or_default();
+
+                // Steam keeps empty entries for reset tools
+                if name.is_empty() {
+                    return None;
+                }
+
+                Some((appid.clone(), CompatTool {
+                    name: name.to_string(),
+                    config: tool.get("config").and_then(Vdf::as_str).unwrap_or_default().to_string(),
+                    priority: tool.get("priority").and_then(Vdf::as_str).and_then(|priority| priority.parse().ok()).unwrap_or_default()
+                }))
+            })
+            .collect()
+    }
+
+    /// Get compatibility tool assigned to the game
+    pub fn compat_tool(&self, appid: &str) -> Option<CompatTool> {
+        self.compat_tools().remove(appid)
+    }
+
+    /// Assign compatibility tool to the game. Use `save` to apply changes
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// let mut config = SteamConfig::open(steam_root().unwrap())
+    ///     .expect("Failed to read steam config");
+    ///
+    /// // Switch all the games to GE-Proton
+    /// for appid in config.compat_tools().into_keys() {
+    ///     config.set_compat_tool(&appid, "GE-Proton9-4");
+    /// }
+    ///
+    /// config.save().expect("Failed to save steam config");
+    /// ```
+    pub fn set_compat_tool(&mut self, appid: &str, name: &str) {
+        let tool = self.root.get_path_mut(COMPAT_TOOL_MAPPING).get_path_mut(&[appid]);
+
+        tool.insert("name", Vdf::String(name.to_string()));
+        tool.insert("config", Vdf::String(String::new()));
+        tool.insert("priority", Vdf::String(String::from("250")));
+    }
+
+    /// Let Steam decide which compatibility tool the game uses
+    pub fn remove_compat_tool(&mut self, appid: &str) -> Option<CompatTool> {
+        let tool = self.compat_tool(appid);
+
+        self.root.get_path_mut(COMPAT_TOOL_MAPPING).remove(appid);
+
+        tool
+    }
+
+    /// Write config back to the file
+    ///
+    /// Fails if Steam is running because it would overwrite changes on exit
+    pub fn save(&self) -> Result<()> {
+        if is_steam_running() {
+            return Err(Error::new(ErrorKind::ResourceBusy, "Steam must be closed before changing its config"));
+        }
+
+        Ok(std::fs::write(&self.path, self.root.to_string())?)
+    }
+}
@@ -0,0 +1,46 @@
+use std::path::PathBuf;
+
+mod vdf;
+mod config;
+mod tool;
+
+pub use vdf::*;
+pub use config::*;
+pub use tool::*;
+
+use crate::xdg::home;
+
+/// Find root folder of the Steam installation
+///
+/// Native, Debian-style and flatpak installs are checked
+///
+/// ```no_run
+/// use wincompatlib::prelude::*;
+///
+/// if let Some(steam) = steam_root() {
+///     println!("Steam is installed to {:?}", steam);
+/// }
+/// ```
+pub fn steam_root() -> Option<PathBuf> {
+    [
+        home().join(".steam/steam"),
+        home().join(".local/share/Steam"),
+        home().join(".steam/debian-installation"),
+        home().join(".var/app/com.valvesoftware.Steam/.local/share/Steam")
+    ].into_iter().find(|path| path.join("config/config.vdf").exists())
+}
+
+/// Check if Steam client is running
+///
+/// Steam overwrites its config files on exit, so they must not be changed while it's running
+pub fn is_steam_running() -> bool {
+    let Ok(processes) = std::fs::read_dir("/proc") else {
+        return false;
+    };
+
+    processes.flatten().any(|process| {
+        std::fs::read_to_string(process.path().join("comm"))
+            .map(|comm| comm.trim() == "steam")
+            .unwrap_or(false)
+    })
+}
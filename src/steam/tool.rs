@@ -0,0 +1,133 @@
+use std::path::{Path, PathBuf};
+use std::io::ErrorKind;
+
+use crate::error::{Error, Result};
+use crate::proton::{ProtonVersion, VERSION_FILE};
+
+use super::{Vdf, VdfObject};
+
+/// Name of the manifest file describing Steam's compatibility tool
+pub const TOOL_MANIFEST_FILE: &str = "toolmanifest.vdf";
+
+/// Content of the `toolmanifest.vdf` file
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ToolManifest {
+    /// Version of the manifest format
+    pub manifest_version: Option<String>,
+
+    /// Command Steam runs the tool with, e.g. `/proton %verb%`
+    pub commandline: Option<String>,
+
+    /// Appid of the tool this one runs inside of, e.g. Steam Linux Runtime
+    pub require_tool_appid: Option<String>,
+
+    /// Name of the compatibility layer, e.g. `proton`
+    pub compatmanager_layer_name: Option<String>
+}
+
+impl ToolManifest {
+    pub fn parse(content: &str) -> Result<Self> {
+        let root = VdfObject::parse(content)?;
+
+        let Some(manifest) = root.get("manifest").and_then(Vdf::as_object) else {
+            return Err(Error::new(ErrorKind::InvalidData, "Tool manifest doesn't contain manifest object"));
+        };
+
+        let get = |key: &str| manifest.get(key)
+            .and_then(Vdf::as_str)
+            .map(String::from);
+
+        Ok(Self {
+            manifest_version: get("version"),
+            commandline: get("commandline"),
+            require_tool_appid: get("require_tool_appid"),
+            compatmanager_layer_name: get("compatmanager_layer_name")
+        })
+    }
+}
+
+/// Compatibility tool installed to Steam, e.g. Proton build or Steam Linux Runtime
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SteamTool {
+    folder: PathBuf,
+
+    pub manifest: ToolManifest,
+
+    /// Content of the `version` file. Steam Linux Runtime doesn't have it
+    pub version_file: Option<ProtonVersion>
+}
+
+impl SteamTool {
+    /// Read manifest and version of the tool's install folder
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// let tool = SteamTool::open("/path/to/steam/steamapps/common/Proton 8.0")
+    ///     .expect("Failed to read compatibility tool");
+    ///
+    /// println!("Tool version: {:?}", tool.version().expect("Unknown tool version"));
+    /// ```
+    pub fn open<T: Into<PathBuf>>(folder: T) -> Result<Self> {
+        let folder = folder.into();
+
+        let manifest = folder.join(TOOL_MANIFEST_FILE);
+
+        if !manifest.is_file() {
+            return Err(Error::new(ErrorKind::InvalidData, format!("{folder:?} is not a steam compatibility tool: {TOOL_MANIFEST_FILE} is missing")));
+        }
+
+        let manifest = ToolManifest::parse(&std::fs::read_to_string(manifest)?)?;
+
+        let version_file = match folder.join(VERSION_FILE).is_file() {
+            true  => Some(ProtonVersion::from_folder(&folder)?),
+            false => None
+        };
+
+        Ok(Self {
+            folder,
+            manifest,
+            version_file
+        })
+    }
+
+    /// Find tools installed to the `compatibilitytools.d` and `steamapps/common` folders
+    /// of the Steam installation, see `steam_root`
+    pub fn find_all<T: AsRef<Path>>(steam_root: T) -> Vec<Self> {
+        let steam_root = steam_root.as_ref();
+
+        let mut tools = Vec::new();
+
+        for folder in [steam_root.join("compatibilitytools.d"), steam_root.join("steamapps/common")] {
+            let Ok(entries) = folder.read_dir() else {
+                continue;
+            };
+
+            let mut folders = entries.flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.join(TOOL_MANIFEST_FILE).is_file())
+                .collect::<Vec<_>>();
+
+            folders.sort();
+
+            tools.extend(folders.into_iter().filter_map(|folder| Self::open(folder).ok()));
+        }
+
+        tools
+    }
+
+    #[inline]
+    pub fn folder(&self) -> &Path {
+        &self.folder
+    }
+
+    /// Get version name of the tool, e.g. `proton-8.0-3`
+    ///
+    /// Returns `Error::VersionParse` if the tool doesn't have a version file
+    pub fn version(&self) -> Result<&str> {
+        match &self.version_file {
+            Some(version) => Ok(&version.name),
+            None => Err(Error::VersionParse(format!("{:?} doesn't have {VERSION_FILE} file", self.folder)))
+        }
+    }
+}
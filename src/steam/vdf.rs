@@ -0,0 +1,256 @@
+use std::io::ErrorKind;
+
+use crate::error::{Error, Result};
+
+/// Value of the Valve's text KeyValues (VDF) file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Vdf {
+    String(String),
+    Object(VdfObject)
+}
+
+impl Vdf {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(value) => Some(value),
+            Self::Object(_) => None
+        }
+    }
+
+    pub fn as_object(&self) -> Option<&VdfObject> {
+        match self {
+            Self::Object(object) => Some(object),
+            Self::String(_) => None
+        }
+    }
+
+    pub fn as_object_mut(&mut self) -> Option<&mut VdfObject> {
+        match self {
+            Self::Object(object) => Some(object),
+            Self::String(_) => None
+        }
+    }
+}
+
+/// Object of the VDF file. Keys are case insensitive and their order is preserved
+///
+/// ```
+/// use wincompatlib::prelude::*;
+///
+/// let vdf = VdfObject::parse(r#"
+///     "CompatToolMapping"
+///     {
+///         "570" { "name" "proton_8" }
+///     }
+/// "#).unwrap();
+///
+/// assert_eq!(vdf.get_path(&["compattoolmapping", "570", "name"]).and_then(Vdf::as_str), Some("proton_8"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct VdfObject {
+    pub entries: Vec<(String, Vdf)>
+}
+
+/// Read next token: string (quoted or not), `{` or `}`
+fn next_token(chars: &mut std::iter::Peekable<std::str::Chars>) -> Result<Option<(String, bool)>> {
+    loop {
+        match chars.peek() {
+            None => return Ok(None),
+
+            Some(char) if char.is_whitespace() => {
+                chars.next();
+            }
+
+            // Comments
+            Some('/') => {
+                chars.next();
+
+                if chars.peek() != Some(&'/') {
+                    return Err(Error::new(ErrorKind::InvalidData, "Unexpected '/' in VDF file"));
+                }
+
+                for char in chars.by_ref() {
+                    if char == '\n' {
+                        break;
+                    }
+                }
+            }
+
+            // Platform conditions like [$WIN32]
+            Some('[') => {
+                for char in chars.by_ref() {
+                    if char == ']' {
+                        break;
+                    }
+                }
+            }
+
+            Some('{') | Some('}') => {
+                let char = chars.next().unwrap_or_default();
+
+                return Ok(Some((char.to_string(), false)));
+            }
+
+            Some('"') => {
+                chars.next();
+
+                let mut value = String::new();
+
+                loop {
+                    match chars.next() {
+                        Some('"') => return Ok(Some((value, true))),
+
+                        Some('\\') => match chars.next() {
+                            Some('n') => value.push('\n'),
+                            Some('t') => value.push('\t'),
+                            Some(char) => value.push(char),
+                            None => break
+                        }
+
+                        Some(char) => value.push(char),
+                        None => break
+                    }
+                }
+
+                return Err(Error::new(ErrorKind::InvalidData, "Unterminated string in VDF file"));
+            }
+
+            Some(_) => {
+                let mut value = String::new();
+
+                while let Some(char) = chars.peek() {
+                    if char.is_whitespace() || matches!(char, '"' | '{' | '}') {
+                        break;
+                    }
+
+                    value.push(*char);
+                    chars.next();
+                }
+
+                return Ok(Some((value, true)));
+            }
+        }
+    }
+}
+
+fn parse_object(chars: &mut std::iter::Peekable<std::str::Chars>, nested: bool) -> Result<VdfObject> {
+    let mut object = VdfObject::default();
+
+    loop {
+        let key = match next_token(chars)? {
+            Some((key, true)) => key,
+
+            Some((token, false)) if token == "}" && nested => return Ok(object),
+            None if !nested => return Ok(object),
+
+            Some((token, _)) => return Err(Error::new(ErrorKind::InvalidData, format!("Unexpected '{token}' in VDF file"))),
+            None => return Err(Error::new(ErrorKind::InvalidData, "Unexpected end of VDF file"))
+        };
+
+        let value = match next_token(chars)? {
+            Some((value, true)) => Vdf::String(value),
+            Some((token, false)) if token == "{" => Vdf::Object(parse_object(chars, true)?),
+
+            _ => return Err(Error::new(ErrorKind::InvalidData, format!("Missing value of the '{key}' key in VDF file")))
+        };
+
+        object.entries.push((key, value));
+    }
+}
+
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+impl VdfObject {
+    pub fn parse(content: &str) -> Result<Self> {
+        parse_object(&mut content.chars().peekable(), false)
+    }
+
+    pub fn get(&self, key: &str) -> Option<&Vdf> {
+        self.entries.iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(key))
+            .map(|(_, value)| value)
+    }
+
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut Vdf> {
+        self.entries.iter_mut()
+            .find(|(name, _)| name.eq_ignore_ascii_case(key))
+            .map(|(_, value)| value)
+    }
+
+    /// Get value by the list of nested keys
+    pub fn get_path(&self, path: &[&str]) -> Option<&Vdf> {
+        let (last, path) = path.split_last()?;
+
+        let mut object = self;
+
+        for key in path {
+            object = object.get(key)?.as_object()?;
+        }
+
+        object.get(last)
+    }
+
+    /// Get nested object, creating missing ones
+    ///
+    /// String values on the way are replaced by objects
+    pub fn get_path_mut(&mut self, path: &[&str]) -> &mut VdfObject {
+        let mut object = self;
+
+        for key in path {
+            if object.get(key).and_then(Vdf::as_object).is_none() {
+                object.insert(*key, Vdf::Object(VdfObject::default()));
+            }
+
+            object = object.get_mut(key)
+                .and_then(Vdf::as_object_mut)
+                .expect("Object was just inserted");
+        }
+
+        object
+    }
+
+    /// Insert or replace value
+    pub fn insert<T: Into<String>>(&mut self, key: T, value: Vdf) {
+        let key = key.into();
+
+        match self.get_mut(&key) {
+            Some(current) => *current = value,
+            None => self.entries.push((key, value))
+        }
+    }
+
+    pub fn remove(&mut self, key: &str) -> Option<Vdf> {
+        let index = self.entries.iter().position(|(name, _)| name.eq_ignore_ascii_case(key))?;
+
+        Some(self.entries.remove(index).1)
+    }
+
+    fn write(&self, f: &mut std::fmt::Formatter<'_>, depth: usize) -> std::fmt::Result {
+        let indent = "\t".repeat(depth);
+
+        for (key, value) in &self.entries {
+            match value {
+                Vdf::String(value) => writeln!(f, "{indent}\"{}\"\t\t\"{}\"", escape(key), escape(value))?,
+
+                Vdf::Object(object) => {
+                    writeln!(f, "{indent}\"{}\"", escape(key))?;
+                    writeln!(f, "{indent}{{")?;
+
+                    object.write(f, depth + 1)?;
+
+                    writeln!(f, "{indent}}}")?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for VdfObject {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.write(f, 0)
+    }
+}
@@ -0,0 +1,362 @@
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+use std::path::Path;
+use std::process::{Command, Stdio, Output};
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::io::{Read, Seek, SeekFrom, Result};
+
+use crate::checksum::crc32;
+use crate::events::{CommandInfo, Events};
+use crate::prefix::{PrefixConfig, PrefixState};
+use crate::wine::Wine;
+
+/// Only the end of the log files is stored in the bundle
+const MAX_LOG_SIZE: u64 = 512 * 1024;
+
+/// Archive with everything needed to debug the user's problem:
+/// versions, resolved environment, prefix manifest, host GPU info, logs and the failed command
+///
+/// Content of all the files is redacted using secrets of the wine struct the bundle was collected for
+///
+/// ```no_run
+/// use wincompatlib::prelude::*;
+///
+/// let wine = Wine::from_binary("/path/to/wine")
+///     .with_prefix("/path/to/prefix");
+///
+/// SupportBundle::collect(&wine)
+///     .with_log("/path/to/game_d3d11.log")
+///     .save("/path/to/bundle.zip")
+///     .expect("Failed to create support bundle");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SupportBundle {
+    /// Files of the archive: (name, content)
+    pub files: BTreeMap<String, Vec<u8>>,
+
+    /// Secrets to redact
+    events: Events
+}
+
+/// Read the command output, ignoring all the errors
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+
+    Some(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Get description of the host system and its GPUs
+fn host_info() -> String {
+    let mut info = String::new();
+
+    if let Ok(os_release) = std::fs::read_to_string("/etc/os-release") {
+        if let Some(name) = os_release.lines().find_map(|line| line.strip_prefix("PRETTY_NAME=")) {
+            info += &format!("OS: {}\n", name.trim_matches('"'));
+        }
+    }
+
+    if let Ok(kernel) = std::fs::read_to_string("/proc/version") {
+        info += &format!("Kernel: {}\n", kernel.trim());
+    }
+
+    if let Ok(cards) = std::fs::read_dir("/sys/class/drm") {
+        let mut cards = cards.flatten()
+            .map(|card| card.path())
+            .filter(|card| card.join("device/vendor").exists())
+            .collect::<Vec<_>>();
+
+        cards.sort();
+
+        for card in cards {
+            let read = |file: &str| std::fs::read_to_string(card.join(file))
+                .map(|value| value.trim().to_string())
+                .unwrap_or_default();
+
+            let driver = std::fs::read_link(card.join("device/driver")).ok()
+                .and_then(|driver| driver.file_name().map(|name| name.to_string_lossy().to_string()))
+                .unwrap_or_default();
+
+            info += &format!("GPU: {} {}:{} ({driver})\n",
+                card.file_name().unwrap_or_default().to_string_lossy(),
+                read("device/vendor"),
+                read("device/device")
+            );
+        }
+    }
+
+    if let Ok(nvidia) = std::fs::read_to_string("/proc/driver/nvidia/version") {
+        if let Some(version) = nvidia.lines().next() {
+            info += &format!("Nvidia: {}\n", version.trim());
+        }
+    }
+
+    info
+}
+
+/// Read the end of the file
+fn read_tail(path: &Path, size: u64) -> Result<Vec<u8>> {
+    let mut file = std::fs::File::open(path)?;
+
+    let len = file.metadata()?.len();
+
+    if len > size {
+        file.seek(SeekFrom::Start(len - size))?;
+    }
+
+    let mut content = Vec::new();
+
+    file.read_to_end(&mut content)?;
+
+    Ok(content)
+}
+
+/// Convert unix timestamp to the MS-DOS (time, date) pair used by zip
+fn dos_datetime(timestamp: u64) -> (u16, u16) {
+    let days = (timestamp / 86400) as i64;
+    let seconds = timestamp % 86400;
+
+    // Civil date from the days since unix epoch
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    // DOS dates start at 1980
+    if year < 1980 {
+        return (0, 0x21);
+    }
+
+    let time = ((seconds / 3600) << 11) | (((seconds % 3600) / 60) << 5) | ((seconds % 60) / 2);
+    let date = ((year - 1980) << 9) | (month << 5) | day;
+
+    (time as u16, date as u16)
+}
+
+impl SupportBundle {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Collect versions, environment, prefix manifest, host info,
+    /// last failed command and log of the last detached process (see `Events::history`)
+    ///
+    /// Secrets of the wine struct's events are redacted
+    pub fn collect(wine: &Wine) -> Self {
+        // Taken before running wine to get its version, which can fail as well
+        let history = wine.events.history();
+
+        let prefix = wine.prefix_path();
+
+        let mut versions = format!("wincompatlib: {}\n", env!("CARGO_PKG_VERSION"));
+
+        versions += &format!("Wine binary: {:?}\n", wine.binary());
+
+        match wine.version() {
+            Ok(version) => versions += &format!("Wine: {}\n", version.to_string_lossy().trim()),
+            Err(err) => versions += &format!("Wine: {err}\n")
+        }
+
+        versions += &format!("Prefix: {prefix:?}\n");
+        versions += &format!("Prefix state: {:?}\n", PrefixState::query(&prefix));
+
+        let mut env = CommandInfo {
+            program: OsString::new(),
+            args: Vec::new(),
+            envs: wine.get_os_envs().into_iter()
+                .map(|(key, value)| (key, Some(value)))
+                .collect()
+        };
+
+        env.envs.sort();
+
+        wine.events.redact(&mut env);
+
+        let env = env.envs.into_iter()
+            .filter_map(|(key, value)| Some(format!("{}={}\n", key.to_string_lossy(), value?.to_string_lossy())))
+            .collect::<String>();
+
+        let mut bundle = Self { events: wine.events.clone(), ..Self::new() }
+            .with_file("versions.txt", versions)
+            .with_file("env.txt", env)
+            .with_file("host.txt", host_info());
+
+        if PrefixConfig::path(&prefix).exists() {
+            // Values of the secret variables are only hidden by `CommandInfo::redact`,
+            // invalid config is stored as is and only redacted by `with_file`
+            let config = PrefixConfig::load(&prefix).ok()
+                .and_then(|mut config| {
+                    let mut env = CommandInfo {
+                        program: OsString::new(),
+                        args: Vec::new(),
+                        envs: config.env.iter()
+                            .map(|(key, value)| (OsString::from(key), Some(OsString::from(value))))
+                            .collect()
+                    };
+
+                    wine.events.redact(&mut env);
+
+                    config.env = env.envs.into_iter()
+                        .filter_map(|(key, value)| Some((key.to_string_lossy().to_string(), value?.to_string_lossy().to_string())))
+                        .collect();
+
+                    toml::to_string_pretty(&config).ok()
+                })
+                .map(String::into_bytes)
+                .or_else(|| std::fs::read(PrefixConfig::path(&prefix)).ok());
+
+            if let Some(config) = config {
+                bundle = bundle.with_file("prefix/wincompatlib.toml", config);
+            }
+        }
+
+        if let Some(vulkan) = command_output("vulkaninfo", &["--summary"]) {
+            bundle = bundle.with_file("vulkaninfo.txt", vulkan);
+        }
+
+        if let Some((command, output)) = &history.failed_command {
+            bundle = bundle.with_failed_command(command, output);
+        }
+
+        if let Some(log_file) = &history.log_file {
+            bundle = bundle.with_log(log_file);
+        }
+
+        bundle
+    }
+
+    /// Add (or replace) file of the archive
+    pub fn with_file<N: Into<String>, T: Into<Vec<u8>>>(mut self, name: N, content: T) -> Self {
+        let content = self.events.redact_bytes(&content.into());
+
+        self.files.insert(name.into(), content);
+
+        self
+    }
+
+    /// Add the end of the log file to the `logs` folder of the archive
+    ///
+    /// If the file can't be read, the error is stored instead
+    pub fn with_log<T: AsRef<Path>>(self, path: T) -> Self {
+        let path = path.as_ref();
+
+        let name = path.file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| String::from("log"));
+
+        let content = read_tail(path, MAX_LOG_SIZE)
+            .unwrap_or_else(|err| format!("Failed to read {path:?}: {err}").into_bytes());
+
+        self.with_file(format!("logs/{name}"), content)
+    }
+
+    /// Add the failed command and its output
+    ///
+    /// Last command failed inside the library is added by `collect` automatically
+    pub fn with_failed_command(self, command: &CommandInfo, output: &Output) -> Self {
+        let mut command = command.clone();
+
+        self.events.redact(&mut command);
+
+        let content = format!("Command: {command}\nStatus: {}\n\nStdout:\n{}\n\nStderr:\n{}\n",
+            output.status,
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        self.with_file("failed_command.txt", content)
+    }
+
+    /// Encode bundle as uncompressed zip archive
+    pub fn to_zip(&self) -> Vec<u8> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+
+        let (time, date) = dos_datetime(timestamp);
+
+        let mut zip = Vec::new();
+        let mut central = Vec::new();
+
+        for (name, content) in &self.files {
+            let offset = zip.len() as u32;
+            let crc = crc32(content);
+
+            // Version 2.0, UTF-8 names, stored without compression
+            let mut header = Vec::new();
+
+            header.extend_from_slice(&20u16.to_le_bytes());
+            header.extend_from_slice(&0x0800u16.to_le_bytes());
+            header.extend_from_slice(&0u16.to_le_bytes());
+            header.extend_from_slice(&time.to_le_bytes());
+            header.extend_from_slice(&date.to_le_bytes());
+            header.extend_from_slice(&crc.to_le_bytes());
+            header.extend_from_slice(&(content.len() as u32).to_le_bytes());
+            header.extend_from_slice(&(content.len() as u32).to_le_bytes());
+            header.extend_from_slice(&(name.len() as u16).to_le_bytes());
+            header.extend_from_slice(&0u16.to_le_bytes());
+
+            zip.extend_from_slice(&0x04034b50u32.to_le_bytes());
+            zip.extend_from_slice(&header);
+            zip.extend_from_slice(name.as_bytes());
+            zip.extend_from_slice(content);
+
+            // Central directory header additionally stores "version made by",
+            // comment length, disk number, attributes and offset of the local header
+            central.extend_from_slice(&0x02014b50u32.to_le_bytes());
+            central.extend_from_slice(&20u16.to_le_bytes());
+            central.extend_from_slice(&header);
+            central.extend_from_slice(&[0; 10]);
+            central.extend_from_slice(&offset.to_le_bytes());
+            central.extend_from_slice(name.as_bytes());
+        }
+
+        let central_offset = zip.len() as u32;
+
+        zip.extend_from_slice(&central);
+
+        zip.extend_from_slice(&0x06054b50u32.to_le_bytes());
+        zip.extend_from_slice(&[0; 4]);
+        zip.extend_from_slice(&(self.files.len() as u16).to_le_bytes());
+        zip.extend_from_slice(&(self.files.len() as u16).to_le_bytes());
+        zip.extend_from_slice(&(central.len() as u32).to_le_bytes());
+        zip.extend_from_slice(&central_offset.to_le_bytes());
+        zip.extend_from_slice(&0u16.to_le_bytes());
+
+        zip
+    }
+
+    /// Save bundle as zip archive
+    pub fn save<T: AsRef<Path>>(&self, path: T) -> Result<()> {
+        std::fs::write(path, self.to_zip())
+    }
+}
+
+impl Wine {
+    /// Collect support bundle and save it as zip archive, see `SupportBundle`
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// Wine::from_binary("/path/to/wine")
+    ///     .with_prefix("/path/to/prefix")
+    ///     .support_bundle("/path/to/bundle.zip")
+    ///     .expect("Failed to create support bundle");
+    /// ```
+    pub fn support_bundle<T: AsRef<Path>>(&self, path: T) -> Result<()> {
+        SupportBundle::collect(self).save(path)
+    }
+}
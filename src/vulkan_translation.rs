@@ -0,0 +1,229 @@
+use std::path::{Path, PathBuf};
+use std::io::{Error, ErrorKind, Result};
+
+use crate::wine::*;
+use crate::dxvk::{Arch, get_install_folders};
+use crate::prefix::PrefixConfig;
+use crate::sources::Sources;
+
+/// Name of the `PrefixConfig` component storing installed profile name
+pub const VULKAN_TRANSLATION_COMPONENT: &str = "vulkan-translation";
+
+/// Set of DXVK, VKD3D-Proton and dxvk-nvapi versions known to work together
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct VulkanProfile {
+    pub name: String,
+    pub dxvk: String,
+    pub vkd3d_proton: String,
+
+    /// `None` if NVAPI shouldn't be installed
+    pub dxvk_nvapi: Option<String>
+}
+
+/// Component of the profile: (name, version, x64 libraries, x32 libraries)
+type Component<'a> = (&'static str, &'a str, &'static [&'static str], &'static [&'static str]);
+
+impl VulkanProfile {
+    /// Get list of tested profiles, from the oldest one
+    pub fn known() -> Vec<Self> {
+        [
+            ("2023.12", "2.3", "2.11", "0.6.4"),
+            ("2024.03", "2.3.1", "2.12", "0.7.0"),
+            ("2024.09", "2.4.1", "2.13", "0.7.1")
+        ].into_iter().map(|(name, dxvk, vkd3d_proton, dxvk_nvapi)| Self {
+            name: name.to_string(),
+            dxvk: dxvk.to_string(),
+            vkd3d_proton: vkd3d_proton.to_string(),
+            dxvk_nvapi: Some(dxvk_nvapi.to_string())
+        }).collect()
+    }
+
+    /// Get the newest tested profile
+    pub fn latest() -> Self {
+        Self::known().pop().expect("Known profiles list is empty")
+    }
+
+    /// Find tested profile by its name
+    ///
+    /// ```
+    /// use wincompatlib::prelude::*;
+    ///
+    /// assert_eq!(VulkanProfile::get("2024.03").unwrap().dxvk, "2.3.1");
+    /// ```
+    pub fn get(name: &str) -> Option<Self> {
+        Self::known().into_iter().find(|profile| profile.name == name)
+    }
+
+    /// Don't install NVAPI libraries
+    pub fn without_nvapi(self) -> Self {
+        Self {
+            dxvk_nvapi: None,
+            ..self
+        }
+    }
+
+    fn components(&self) -> Vec<Component<'_>> {
+        let mut components: Vec<Component<'_>> = vec![
+            ("dxvk", &self.dxvk, &["dxgi", "d3d9", "d3d10core", "d3d11"], &["dxgi", "d3d9", "d3d10core", "d3d11"]),
+            ("vkd3d-proton", &self.vkd3d_proton, &["d3d12", "d3d12core"], &["d3d12", "d3d12core"])
+        ];
+
+        if let Some(version) = &self.dxvk_nvapi {
+            components.push(("dxvk-nvapi", version, &["nvapi64", "nvofapi64"], &["nvapi"]));
+        }
+
+        components
+    }
+
+    /// Download builds of the profile components to the given folder
+    ///
+    /// Builds are stored using `Sources::download_build`, already downloaded ones are skipped
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// VulkanProfile::latest()
+    ///     .download(&Sources::default(), "/path/to/builds")
+    ///     .expect("Failed to download profile");
+    /// ```
+    pub fn download<T: AsRef<Path>>(&self, sources: &Sources, builds: T) -> Result<()> {
+        for (name, version, _, _) in self.components() {
+            sources.download_build(name, version, builds.as_ref())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Find folder with libraries of given arch inside of the build
+///
+/// Some archives contain the build folder itself, others store libraries in the root
+fn find_dlls_folder(build: &Path, names: &[&str]) -> Option<PathBuf> {
+    let roots = std::iter::once(build.to_path_buf())
+        .chain(build.read_dir().ok()?.flatten().map(|entry| entry.path()));
+
+    for root in roots {
+        for name in names {
+            if root.join(name).is_dir() {
+                return Some(root.join(name));
+            }
+        }
+    }
+
+    None
+}
+
+/// Install library replacing wine builtin one, if it exists
+fn install_library(wine: &Wine, system: &Path, dlls_folder: &Path, dll: &str) -> Result<()> {
+    let source = dlls_folder.join(format!("{dll}.dll"));
+    let target = system.join(format!("{dll}.dll"));
+    let backup = system.join(format!("{dll}.dll.old"));
+
+    if !source.exists() {
+        return Err(Error::new(ErrorKind::NotFound, format!("Build doesn't contain library: {source:?}")));
+    }
+
+    // Original library is already persisted or not provided by wine
+    if target.exists() && !backup.exists() {
+        std::fs::rename(&target, &backup)?;
+    }
+
+    std::fs::copy(&source, &target)?;
+
+    wine.set_proton_tracked(&target, false)?;
+
+    Ok(wine.set_dll_override(dll, OverrideMode::Native)?)
+}
+
+pub struct VulkanTranslation;
+
+impl VulkanTranslation {
+    /// Install DXVK, VKD3D-Proton and dxvk-nvapi versions of the profile to the wine prefix
+    ///
+    /// Builds are taken from the folder filled by `VulkanProfile::download`.
+    /// Installed versions are recorded in the prefix's `PrefixConfig`. If NVAPI is installed,
+    /// then `DXVK_ENABLE_NVAPI` variable is stored there as well
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// let wine = Wine::from_binary("/path/to/wine")
+    ///     .with_prefix("/path/to/prefix");
+    ///
+    /// let profile = VulkanProfile::latest();
+    ///
+    /// profile.download(&Sources::default(), "/path/to/builds")
+    ///     .expect("Failed to download profile");
+    ///
+    /// VulkanTranslation::install(&wine, &profile, "/path/to/builds")
+    ///     .expect("Failed to install profile");
+    /// ```
+    pub fn install<T: AsRef<Path>>(wine: &Wine, profile: &VulkanProfile, builds: T) -> Result<()> {
+        wine.check_writable()?;
+
+        let prefix = wine.prefix_path();
+
+        if !prefix.join("system.reg").exists() {
+            return Err(Error::other(prefix.to_string_lossy() + " is not a valid wine prefix"));
+        }
+
+        let archs = match WineArch::from_prefix(&prefix)?.or(wine.arch).unwrap_or(WineArch::Win64) {
+            WineArch::Win64 => vec![Arch::Win64, Arch::Win32],
+            WineArch::Win32 => vec![Arch::Win32]
+        };
+
+        let components = profile.components();
+
+        let total = components.len() as u64;
+
+        for (i, (name, version, x64, x32)) in components.iter().enumerate() {
+            wine.events.progress(i as u64, total, &format!("Installing {name} {version}"));
+
+            let build = Sources::build_folder(builds.as_ref(), name, version);
+
+            for arch in &archs {
+                let (system, _) = get_install_folders(wine, &prefix, *arch)?;
+
+                let (folders, dlls): (&[&str], _) = match arch {
+                    Arch::Win64 => (&["x64"], x64),
+                    Arch::Win32 => (&["x32", "x86"], x32)
+                };
+
+                let dlls_folder = find_dlls_folder(&build, folders)
+                    .ok_or_else(|| Error::new(ErrorKind::NotFound, format!("{name} {version} build doesn't contain {arch:?} libraries")))?;
+
+                let system = wine.winepath(system)?;
+
+                for dll in dlls.iter() {
+                    install_library(wine, &system, &dlls_folder, dll)?;
+                }
+            }
+        }
+
+        let mut config = PrefixConfig::load_or_default(&prefix)?;
+
+        for (name, version, _, _) in &components {
+            config.components.insert(name.to_string(), version.to_string());
+        }
+
+        if profile.dxvk_nvapi.is_some() {
+            config.env.insert(String::from("DXVK_ENABLE_NVAPI"), String::from("1"));
+        } else {
+            config.components.remove("dxvk-nvapi");
+            config.env.remove("DXVK_ENABLE_NVAPI");
+        }
+
+        config.components.insert(VULKAN_TRANSLATION_COMPONENT.to_string(), profile.name.clone());
+
+        config.save(&prefix)?;
+
+        wine.events.progress(total, total, "Vulkan translation installed");
+
+        Ok(())
+    }
+
+    /// Get name of the profile installed to the wine prefix
+    pub fn installed_profile<T: AsRef<Path>>(prefix: T) -> Result<Option<String>> {
+        Ok(PrefixConfig::load_or_default(prefix)?.components.remove(VULKAN_TRANSLATION_COMPONENT))
+    }
+}
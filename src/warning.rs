@@ -0,0 +1,151 @@
+use std::sync::{Arc, Mutex};
+use std::path::PathBuf;
+use crate::error::Result;
+
+use crate::wine::{Wine, WineArch, Wow64Warning};
+use crate::events::EventSink;
+
+/// Non-fatal issue found by the library
+///
+/// Warnings are reported to the event sinks (see `EventSink::on_typed_warning`),
+/// and can be collected together with the operation's result using `Wine::with_warnings`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Warning {
+    /// Wine prefix doesn't specify its architecture so the assumed one is used
+    UnknownPrefixArch {
+        assumed: WineArch
+    },
+
+    /// 32 bit programs won't work in the win64 prefix
+    Wow64(Wow64Warning),
+
+    /// Open files limit is too low for esync, so wine will disable it
+    LowFileLimit {
+        current: u64,
+        recommended: u64
+    },
+
+    /// Disc image was mounted using the fallback method
+    DiscMountFallback {
+        reason: String
+    },
+
+    /// Files of the folder differ only by case, so windows programs can't distinguish them
+    CaseConflicts {
+        folder: PathBuf,
+        conflicts: Vec<Vec<PathBuf>>
+    },
+
+    /// Prefix or game is stored on the filesystem mounted improperly for wine
+    UnsuitableMount {
+        path: PathBuf,
+        fs_type: String,
+        remediation: String
+    },
+
+    Other(String)
+}
+
+impl From<Wow64Warning> for Warning {
+    #[inline]
+    fn from(warning: Wow64Warning) -> Self {
+        Self::Wow64(warning)
+    }
+}
+
+impl From<String> for Warning {
+    #[inline]
+    fn from(message: String) -> Self {
+        Self::Other(message)
+    }
+}
+
+impl From<&str> for Warning {
+    #[inline]
+    fn from(message: &str) -> Self {
+        Self::Other(message.to_string())
+    }
+}
+
+impl std::fmt::Display for Warning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownPrefixArch { assumed } => write!(f, "Wine prefix doesn't specify its architecture, assuming {}", assumed.to_str()),
+            Self::Wow64(warning) => write!(f, "{warning}"),
+            Self::LowFileLimit { current, recommended } => write!(f, "esync disabled: open files limit is too low ({current}, {recommended} recommended)"),
+            Self::DiscMountFallback { reason } => write!(f, "Failed to mount disc image using fuseiso, trying udisks: {reason}"),
+            Self::CaseConflicts { folder, conflicts } => write!(f, "{} files in {folder:?} differ only by case and can't be distinguished by windows programs", conflicts.len()),
+            Self::UnsuitableMount { path, fs_type, remediation } => write!(f, "{path:?} is stored on unsuitable {fs_type} mount: {remediation}"),
+            Self::Other(message) => write!(f, "{message}")
+        }
+    }
+}
+
+/// Result of the operation together with warnings it reported
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Warned<T> {
+    pub value: T,
+    pub warnings: Vec<Warning>
+}
+
+impl<T> Warned<T> {
+    #[inline]
+    pub fn has_warnings(&self) -> bool {
+        !self.warnings.is_empty()
+    }
+
+    #[inline]
+    pub fn into_value(self) -> T {
+        self.value
+    }
+}
+
+/// Sink collecting typed warnings
+struct Collector(Mutex<Vec<Warning>>);
+
+impl EventSink for Collector {
+    fn on_typed_warning(&self, warning: &Warning) {
+        if let Ok(mut warnings) = self.0.lock() {
+            warnings.push(warning.clone());
+        }
+    }
+}
+
+impl Wine {
+    /// Run operation and collect all the warnings it reported
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// let wine = Wine::from_binary("/path/to/wine")
+    ///     .with_prefix("/path/to/prefix");
+    ///
+    /// let result = wine.with_warnings(|wine| wine.check_wow64())
+    ///     .expect("Failed to check prefix");
+    ///
+    /// for warning in result.warnings {
+    ///     eprintln!("Warning: {warning}");
+    /// }
+    /// ```
+    pub fn with_warnings<T, F>(&self, operation: F) -> Result<Warned<T>>
+    where
+        F: FnOnce(&Wine) -> Result<T>
+    {
+        let collector = Arc::new(Collector(Mutex::new(Vec::new())));
+
+        let mut wine = self.clone();
+
+        wine.events.subscribe(collector.clone());
+
+        let value = operation(&wine)?;
+
+        let warnings = collector.0.lock()
+            .map(|warnings| warnings.clone())
+            .unwrap_or_default();
+
+        Ok(Warned {
+            value,
+            warnings
+        })
+    }
+}
@@ -0,0 +1,239 @@
+use std::path::{Path, PathBuf};
+
+use super::*;
+use super::wow64::resolve_binary;
+
+/// Windows components wine installs to the prefix on its creation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AddonKind {
+    /// Wine Gecko, Internet Explorer's engine replacement
+    Gecko,
+
+    /// Wine Mono, .NET Framework replacement
+    Mono
+}
+
+impl AddonKind {
+    /// Name of the folder in `share/wine` and prefix of the addon files
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Gecko => "gecko",
+            Self::Mono  => "mono"
+        }
+    }
+}
+
+/// Addon bundled with the wine build which is used instead of downloading it for each prefix
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SharedAddon {
+    pub kind: AddonKind,
+
+    /// Path to the extracted addon folder or its msi installer
+    pub path: PathBuf,
+
+    /// Version taken from the file name, e.g. `2.47.4`
+    pub version: Option<String>
+}
+
+impl SharedAddon {
+    /// Parse addon file name like `wine-gecko-2.47.4-x86_64` or `wine-mono-8.1.0-x86.msi`
+    fn from_path(kind: AddonKind, path: PathBuf) -> Option<Self> {
+        let name = path.file_name()?.to_string_lossy().to_string();
+
+        let version = name.strip_prefix(&format!("wine-{}-", kind.name()))?;
+
+        let version = version.strip_suffix(".msi").unwrap_or(version);
+        let version = version.strip_suffix(".tar.xz").unwrap_or(version);
+
+        let version = version.strip_suffix("-x86_64")
+            .or_else(|| version.strip_suffix("-x86"))
+            .unwrap_or(version);
+
+        Some(Self {
+            kind,
+            version: (!version.is_empty()).then(|| version.to_string()),
+            path
+        })
+    }
+
+    /// Find addons stored in the `share/wine` folder
+    fn find(share: &Path) -> Vec<Self> {
+        let mut addons = Vec::new();
+
+        for kind in [AddonKind::Gecko, AddonKind::Mono] {
+            let Ok(entries) = share.join(kind.name()).read_dir() else {
+                continue;
+            };
+
+            addons.extend(entries.flatten().filter_map(|entry| Self::from_path(kind, entry.path())));
+        }
+
+        addons.sort_by(|a, b| a.path.cmp(&b.path));
+
+        addons
+    }
+}
+
+impl Wine {
+    /// Find Gecko and Mono addons bundled with the wine build in its `share/wine` folder
+    ///
+    /// Wine uses them when creating prefixes instead of asking to download them
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// for addon in Wine::from_binary("/path/to/wine").shared_addons() {
+    ///     println!("{:?} {:?}: {:?}", addon.kind, addon.version, addon.path);
+    /// }
+    /// ```
+    pub fn shared_addons(&self) -> Vec<SharedAddon> {
+        let Some(root) = resolve_binary(&self.binary).and_then(|binary| Some(binary.parent()?.parent()?.to_path_buf())) else {
+            return Vec::new();
+        };
+
+        SharedAddon::find(&root.join("share/wine"))
+    }
+
+    /// Check if the wine build bundles addon of the given kind
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// if !Wine::from_binary("/path/to/wine").has_shared_addon(AddonKind::Mono) {
+    ///     println!("Wine Mono will be installed to each prefix");
+    /// }
+    /// ```
+    pub fn has_shared_addon(&self, kind: AddonKind) -> bool {
+        self.shared_addons().iter().any(|addon| addon.kind == kind)
+    }
+}
+
+/// What to do with addons which are neither bundled with the wine build nor cached
+/// when the prefix is created
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum MissingAddons {
+    /// Let wine show the "Wine Mono is not installed" dialog
+    #[default]
+    Prompt,
+
+    /// Don't show the dialog and don't install missing addons
+    Skip,
+
+    /// Don't show the dialog and install addons from the given msi files afterwards
+    Install(Vec<PathBuf>)
+}
+
+/// Find wine's download cache folder
+fn cache_folder() -> Option<PathBuf> {
+    std::env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+        .map(|cache| cache.join("wine"))
+}
+
+impl Wine {
+    /// Get addons which wine will ask to download when creating the prefix
+    ///
+    /// Addons are looked up in the build's and system `share/wine` folders and in wine's download cache
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// for addon in Wine::from_binary("/path/to/wine").unavailable_addons() {
+    ///     println!("{} is not available", addon.name());
+    /// }
+    /// ```
+    pub fn unavailable_addons(&self) -> Vec<AddonKind> {
+        let mut available = self.shared_addons();
+
+        for share in ["/usr/share/wine", "/usr/local/share/wine", "/opt/wine/share/wine"] {
+            available.extend(SharedAddon::find(Path::new(share)));
+        }
+
+        if let Some(Ok(entries)) = cache_folder().map(|cache| cache.read_dir()) {
+            for entry in entries.flatten() {
+                for kind in [AddonKind::Gecko, AddonKind::Mono] {
+                    available.extend(SharedAddon::from_path(kind, entry.path()));
+                }
+            }
+        }
+
+        [AddonKind::Gecko, AddonKind::Mono].into_iter()
+            .filter(|kind| !available.iter().any(|addon| addon.kind == *kind))
+            .collect()
+    }
+
+    /// Get `WINEDLLOVERRIDES` value disabling installation of missing addons
+    ///
+    /// Addons overrides are appended to the configured value, and libraries
+    /// already overridden there are kept as is
+    pub(crate) fn addons_overrides(&self) -> Option<OsString> {
+        if self.missing_addons == MissingAddons::Prompt && !self.unattended {
+            return None;
+        }
+
+        let missing = self.unavailable_addons();
+
+        if missing.is_empty() {
+            return None;
+        }
+
+        let mut overrides = self.get_os_envs()
+            .remove(OsStr::new("WINEDLLOVERRIDES"))
+            .or_else(|| std::env::var_os("WINEDLLOVERRIDES"))
+            .unwrap_or_default();
+
+        let overridden = overrides.to_string_lossy()
+            .split(';')
+            .flat_map(|entry| entry.split('=').next().unwrap_or_default().split(','))
+            .map(|dll| dll.trim().to_ascii_lowercase())
+            .collect::<Vec<_>>();
+
+        let dlls = missing.into_iter()
+            .map(|kind| match kind {
+                AddonKind::Gecko => "mshtml",
+                AddonKind::Mono  => "mscoree"
+            })
+            .filter(|dll| !overridden.iter().any(|overridden| overridden == dll))
+            .collect::<Vec<_>>();
+
+        if dlls.is_empty() {
+            return None;
+        }
+
+        for dll in dlls {
+            if !overrides.is_empty() {
+                overrides.push(";");
+            }
+
+            overrides.push(dll);
+            overrides.push("=");
+        }
+
+        Some(overrides)
+    }
+
+    /// Install addons from the msi files specified by `MissingAddons::Install`
+    pub(crate) fn install_addons(&self, prefix: &Path) -> Result<()> {
+        let MissingAddons::Install(installers) = &self.missing_addons else {
+            return Ok(());
+        };
+
+        let wine = self.clone().with_prefix(prefix);
+
+        for installer in installers {
+            let output = wine.run_args_output([
+                OsString::from("msiexec"),
+                OsString::from("/i"),
+                super::dll_ext::unix_to_windows_path(&installer.canonicalize()?)?,
+                OsString::from("/qn")
+            ])?;
+
+            if !output.status.success() {
+                return Err(Error::other(format!("Failed to install {installer:?}: msiexec exited with code {}", output.status.code().unwrap_or(-1))));
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -13,6 +13,11 @@ pub trait WineBootExt {
 impl WineBootExt for Wine {
     /// Create (or update existing) wine prefix. Runs `wineboot -u` command
     /// 
+    /// Missing Gecko and Mono addons are handled according to `Wine::missing_addons`.
+    /// Unattended structs also disable the crash dialog in the prefix
+    /// 
+    /// Returns `Error::WinebootFailed` if wineboot finished with error
+    /// 
     /// ```no_run
     /// use wincompatlib::prelude::*;
     /// 
@@ -21,14 +26,51 @@ impl WineBootExt for Wine {
     ///     .expect("Failed to update prefix");
     /// ```
     fn update_prefix<T: Into<PathBuf>>(&self, path: T) -> Result<Output> {
-        Command::new(self.wineboot())
-            .arg("-u")
-            .envs(self.get_envs())
-            .env("WINEPREFIX", path.into())
+        self.check_writable()?;
+
+        let path = path.into();
+
+        // Prefix folder may not exist yet, so create it to track its wineserver
+        std::fs::create_dir_all(&path)?;
+
+        let mut command = self.headless_command(self.wineboot());
+
+        command.arg("-u")
+            .envs(self.get_os_envs())
+            .env("WINEPREFIX", &path)
             .stdin(Stdio::null())
             .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .output()
+            .stderr(Stdio::piped());
+
+        // Disable missing addons so wine doesn't ask to download them
+        if let Some(overrides) = self.addons_overrides() {
+            command.env("WINEDLLOVERRIDES", overrides);
+        }
+
+        let output = self.track_server(&path, || {
+            self.events.output(&mut command)
+                .map_err(|err| Error::from_spawn(self.wineboot(), err))
+        })?;
+
+        if !output.status.success() {
+            return Err(Error::wineboot_failed(&output));
+        }
+
+        self.install_addons(&path)?;
+
+        if self.unattended {
+            self.clone().with_prefix(&path).set_crash_dialog(false)?;
+        }
+
+        if let Some(proxy) = &self.proxy {
+            self.clone().with_prefix(&path).set_proxy(Some(proxy))?;
+        }
+
+        if let Some(codepage) = self.codepage {
+            self.clone().with_prefix(&path).set_codepage(codepage)?;
+        }
+
+        Ok(output)
     }
 
     /// Stop running processes. Runs `wineboot -k` command, or `wineboot -f` if `force = true`
@@ -41,13 +83,15 @@ impl WineBootExt for Wine {
     ///     .expect("Failed to update prefix");
     /// ```
     fn stop_processes(&self, force: bool) -> Result<Output> {
-        Command::new(self.wineboot())
-            .arg(if force { "-f" } else { "-k" })
-            .envs(self.get_envs())
-            .stdin(Stdio::null())
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .output()
+        self.track_server(&self.prefix_path(), || {
+            self.events.output(Command::new(self.wineboot())
+                .arg(if force { "-f" } else { "-k" })
+                .envs(self.get_os_envs())
+                .stdin(Stdio::null())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped()))
+                .map_err(|err| Error::from_spawn(self.wineboot(), err))
+        })
     }
 
     /// Imitate windows restart. Runs `wineboot -r` command
@@ -61,13 +105,15 @@ impl WineBootExt for Wine {
     ///     .expect("Failed to restart");
     /// ```
     fn restart(&self) -> Result<Output> {
-        Command::new(self.wineboot())
-            .arg("-r")
-            .envs(self.get_envs())
-            .stdin(Stdio::null())
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .output()
+        self.track_server(&self.prefix_path(), || {
+            self.events.output(Command::new(self.wineboot())
+                .arg("-r")
+                .envs(self.get_os_envs())
+                .stdin(Stdio::null())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped()))
+                .map_err(|err| Error::from_spawn(self.wineboot(), err))
+        })
     }
 
     /// Imitate windows shutdown. Runs `wineboot -s` command
@@ -81,13 +127,15 @@ impl WineBootExt for Wine {
     ///     .expect("Failed to shutdown");
     /// ```
     fn shutdown(&self) -> Result<Output> {
-        Command::new(self.wineboot())
-            .arg("-s")
-            .envs(self.get_envs())
-            .stdin(Stdio::null())
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .output()
+        self.track_server(&self.prefix_path(), || {
+            self.events.output(Command::new(self.wineboot())
+                .arg("-s")
+                .envs(self.get_os_envs())
+                .stdin(Stdio::null())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped()))
+                .map_err(|err| Error::from_spawn(self.wineboot(), err))
+        })
     }
 
     /// End wineboot session. Runs `wineboot -e` command
@@ -101,12 +149,14 @@ impl WineBootExt for Wine {
     ///     .expect("Failed to shutdown");
     /// ```
     fn end_session(&self) -> Result<Output> {
-        Command::new(self.wineboot())
-            .arg("-e")
-            .envs(self.get_envs())
-            .stdin(Stdio::null())
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .output()
+        self.track_server(&self.prefix_path(), || {
+            self.events.output(Command::new(self.wineboot())
+                .arg("-e")
+                .envs(self.get_os_envs())
+                .stdin(Stdio::null())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped()))
+                .map_err(|err| Error::from_spawn(self.wineboot(), err))
+        })
     }
 }
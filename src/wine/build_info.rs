@@ -0,0 +1,145 @@
+use std::path::Path;
+
+use super::*;
+use super::wow64::resolve_binary;
+
+/// Information about the wine build and options it was compiled with
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct WineBuildInfo {
+    /// Numeric version reported by `wine --version`, e.g. `8.0`
+    pub version: String,
+
+    pub variant: WineVariant,
+
+    /// Content of the build's `VERSION` file, if it exists
+    pub build_version: Option<String>,
+
+    /// Options given to the configure script, taken from the build's `config.log`
+    pub configure_options: Vec<String>
+}
+
+impl WineBuildInfo {
+    /// Parse `wine --version` output, see `WineVersion::parse`
+    ///
+    /// ```
+    /// use wincompatlib::prelude::*;
+    ///
+    /// let info = WineBuildInfo::from_version("wine-8.0 (Staging)\n");
+    ///
+    /// assert_eq!(info.version, "8.0");
+    /// assert_eq!(info.variant, WineVariant::Staging);
+    ///
+    /// let info = WineBuildInfo::from_version("GE-Proton8-26");
+    ///
+    /// assert_eq!(info.version, "8.26");
+    /// assert_eq!(info.variant, WineVariant::GE);
+    /// ```
+    pub fn from_version(output: &str) -> Self {
+        match WineVersion::parse(output) {
+            Ok(version) => Self {
+                version: version.components.iter()
+                    .map(u64::to_string)
+                    .collect::<Vec<_>>()
+                    .join("."),
+
+                variant: version.variant,
+                ..Self::default()
+            },
+
+            Err(_) => Self {
+                version: output.trim().to_string(),
+                ..Self::default()
+            }
+        }
+    }
+
+    /// Read configure options from the `config.log` file
+    ///
+    /// Options are taken from the `$ ./configure ...` line in its beginning
+    pub fn parse_config_log(config_log: &str) -> Vec<String> {
+        config_log.lines()
+            .find_map(|line| line.trim().strip_prefix("$ ").filter(|command| command.contains("configure")))
+            .map(|command| {
+                command.split_whitespace()
+                    .filter(|arg| arg.starts_with("--"))
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Check if the build was compiled with the given feature, e.g. `wayland` or `x`
+    ///
+    /// Returns `None` if configure options are unknown or don't mention the feature
+    ///
+    /// ```
+    /// use wincompatlib::prelude::*;
+    ///
+    /// let info = WineBuildInfo {
+    ///     configure_options: vec![String::from("--with-wayland"), String::from("--without-x")],
+    ///     ..WineBuildInfo::default()
+    /// };
+    ///
+    /// assert_eq!(info.has_feature("wayland"), Some(true));
+    /// assert_eq!(info.has_feature("x"), Some(false));
+    /// assert_eq!(info.has_feature("cups"), None);
+    /// ```
+    pub fn has_feature(&self, feature: &str) -> Option<bool> {
+        // Later options override earlier ones
+        self.configure_options.iter().rev().find_map(|option| {
+            let option = option.split('=').next().unwrap_or(option);
+
+            match option.strip_prefix("--") {
+                Some(name) if name == format!("with-{feature}") || name == format!("enable-{feature}") => Some(true),
+                Some(name) if name == format!("without-{feature}") || name == format!("disable-{feature}") => Some(false),
+
+                _ => None
+            }
+        })
+    }
+
+    /// Read `VERSION` and `config.log` files from the build's root folder
+    fn read_metadata(&mut self, root: &Path) {
+        for name in ["VERSION", "version"] {
+            if let Ok(version) = std::fs::read_to_string(root.join(name)) {
+                self.build_version = Some(version.trim().to_string());
+
+                break;
+            }
+        }
+
+        for path in [root.join("config.log"), root.join("share/wine/config.log")] {
+            if let Ok(config_log) = std::fs::read_to_string(path) {
+                self.configure_options = Self::parse_config_log(&config_log);
+
+                break;
+            }
+        }
+    }
+}
+
+impl Wine {
+    /// Get wine version and compilation metadata of the build
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// let info = Wine::from_binary("/path/to/wine").build_info()
+    ///     .expect("Failed to get build info");
+    ///
+    /// if info.has_feature("wayland") == Some(true) {
+    ///     println!("Wine {} supports wayland", info.version);
+    /// }
+    /// ```
+    pub fn build_info(&self) -> Result<WineBuildInfo> {
+        let version = self.version()?;
+
+        let mut info = WineBuildInfo::from_version(&version.to_string_lossy());
+
+        if let Some(root) = resolve_binary(&self.binary).and_then(|binary| Some(binary.parent()?.parent()?.to_path_buf())) {
+            info.read_metadata(&root);
+        }
+
+        Ok(info)
+    }
+}
@@ -0,0 +1,111 @@
+use std::path::Path;
+
+use super::*;
+use super::wow64::resolve_binary;
+
+/// Program implemented by wine itself
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum BuiltinTool {
+    Winecfg,
+    Regedit,
+    Explorer,
+    Winedbg,
+    Msiexec,
+    Taskmgr,
+    Control,
+    Uninstaller,
+    Notepad,
+    Cmd
+}
+
+impl BuiltinTool {
+    pub const ALL: [Self; 10] = [
+        Self::Winecfg,
+        Self::Regedit,
+        Self::Explorer,
+        Self::Winedbg,
+        Self::Msiexec,
+        Self::Taskmgr,
+        Self::Control,
+        Self::Uninstaller,
+        Self::Notepad,
+        Self::Cmd
+    ];
+
+    /// Name of the program's executable
+    pub fn exe_name(&self) -> &str {
+        match self {
+            Self::Winecfg     => "winecfg.exe",
+            Self::Regedit     => "regedit.exe",
+            Self::Explorer    => "explorer.exe",
+            Self::Winedbg     => "winedbg.exe",
+            Self::Msiexec     => "msiexec.exe",
+            Self::Taskmgr     => "taskmgr.exe",
+            Self::Control     => "control.exe",
+            Self::Uninstaller => "uninstaller.exe",
+            Self::Notepad     => "notepad.exe",
+            Self::Cmd         => "cmd.exe"
+        }
+    }
+}
+
+/// Check if wine libraries folder contains the program
+fn has_builtin(wine: &Path, exe: &str) -> bool {
+    // Wine 5.7+ puts PE programs into arch specific folders,
+    // older builds store them as .so libraries or in the fakedlls folder
+    ["x86_64-windows", "i386-windows", "aarch64-windows", "fakedlls"].into_iter()
+        .any(|folder| wine.join(folder).join(exe).is_file())
+        || wine.join(format!("{exe}.so")).is_file()
+}
+
+impl Wine {
+    /// Get programs available in the wine build
+    ///
+    /// Some builds (e.g. stripped down proton ones) don't ship all of them,
+    /// so UI features relying on them can be disabled accordingly.
+    /// Returns `None` if libraries folder of the build wasn't found
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// let builtins = Wine::from_binary("/path/to/wine")
+    ///     .available_builtins()
+    ///     .unwrap_or_default();
+    ///
+    /// if !builtins.contains(&BuiltinTool::Winecfg) {
+    ///     println!("Wine configuration is not available");
+    /// }
+    /// ```
+    pub fn available_builtins(&self) -> Option<Vec<BuiltinTool>> {
+        let root = resolve_binary(&self.binary)?
+            .parent()?
+            .parent()?
+            .to_path_buf();
+
+        // `lib*/wine` of the wine builds, and `lib*/<triplet>/wine` of the multiarch distro packages
+        let libs = root.read_dir().ok()?
+            .flatten()
+            .filter(|entry| entry.file_name().to_string_lossy().starts_with("lib"))
+            .map(|entry| entry.path())
+            .collect::<Vec<_>>();
+
+        let mut folders = libs.iter()
+            .flat_map(|lib| lib.read_dir().into_iter().flatten().flatten())
+            .map(|entry| entry.path().join("wine"))
+            .chain(libs.iter().map(|lib| lib.join("wine")))
+            .filter(|wine| wine.is_dir())
+            .collect::<Vec<_>>();
+
+        folders.sort();
+
+        if folders.is_empty() {
+            return None;
+        }
+
+        let builtins = BuiltinTool::ALL.into_iter()
+            .filter(|tool| folders.iter().any(|wine| has_builtin(wine, tool.exe_name())))
+            .collect();
+
+        Some(builtins)
+    }
+}
@@ -0,0 +1,139 @@
+/// Windows ANSI codepage expected by the application
+///
+/// Legacy games often assume the codepage of the system they were made for,
+/// e.g. japanese games show garbage text or crash without CP932
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Codepage {
+    /// CP874
+    Thai,
+
+    /// CP932 (Shift-JIS)
+    Japanese,
+
+    /// CP936 (GBK)
+    SimplifiedChinese,
+
+    /// CP949
+    Korean,
+
+    /// CP950 (Big5)
+    TraditionalChinese,
+
+    /// CP1250
+    CentralEuropean,
+
+    /// CP1251
+    Cyrillic,
+
+    /// CP1252
+    Western,
+
+    /// CP1253
+    Greek,
+
+    /// CP1254
+    Turkish,
+
+    /// CP1255
+    Hebrew,
+
+    /// CP1256
+    Arabic,
+
+    /// CP1257
+    Baltic,
+
+    /// CP1258
+    Vietnamese
+}
+
+impl Codepage {
+    /// Get codepage by its ANSI codepage number
+    ///
+    /// ```
+    /// use wincompatlib::prelude::*;
+    ///
+    /// assert_eq!(Codepage::from_ansi(932), Some(Codepage::Japanese));
+    /// assert_eq!(Codepage::from_ansi(65001), None);
+    /// ```
+    pub fn from_ansi(codepage: u16) -> Option<Self> {
+        match codepage {
+            874  => Some(Self::Thai),
+            932  => Some(Self::Japanese),
+            936  => Some(Self::SimplifiedChinese),
+            949  => Some(Self::Korean),
+            950  => Some(Self::TraditionalChinese),
+            1250 => Some(Self::CentralEuropean),
+            1251 => Some(Self::Cyrillic),
+            1252 => Some(Self::Western),
+            1253 => Some(Self::Greek),
+            1254 => Some(Self::Turkish),
+            1255 => Some(Self::Hebrew),
+            1256 => Some(Self::Arabic),
+            1257 => Some(Self::Baltic),
+            1258 => Some(Self::Vietnamese),
+
+            _ => None
+        }
+    }
+
+    /// ANSI codepage number used by GUI applications
+    pub fn ansi(&self) -> u16 {
+        match self {
+            Self::Thai               => 874,
+            Self::Japanese           => 932,
+            Self::SimplifiedChinese  => 936,
+            Self::Korean             => 949,
+            Self::TraditionalChinese => 950,
+            Self::CentralEuropean    => 1250,
+            Self::Cyrillic           => 1251,
+            Self::Western            => 1252,
+            Self::Greek              => 1253,
+            Self::Turkish            => 1254,
+            Self::Hebrew             => 1255,
+            Self::Arabic             => 1256,
+            Self::Baltic             => 1257,
+            Self::Vietnamese         => 1258
+        }
+    }
+
+    /// OEM codepage number used by console applications
+    pub fn oem(&self) -> u16 {
+        match self {
+            Self::Thai               => 874,
+            Self::Japanese           => 932,
+            Self::SimplifiedChinese  => 936,
+            Self::Korean             => 949,
+            Self::TraditionalChinese => 950,
+            Self::CentralEuropean    => 852,
+            Self::Cyrillic           => 866,
+            Self::Western            => 437,
+            Self::Greek              => 737,
+            Self::Turkish            => 857,
+            Self::Hebrew             => 862,
+            Self::Arabic             => 720,
+            Self::Baltic             => 775,
+            Self::Vietnamese         => 1258
+        }
+    }
+
+    /// Unix locale making wine use this codepage
+    pub fn locale(&self) -> &str {
+        match self {
+            Self::Thai               => "th_TH.UTF-8",
+            Self::Japanese           => "ja_JP.UTF-8",
+            Self::SimplifiedChinese  => "zh_CN.UTF-8",
+            Self::Korean             => "ko_KR.UTF-8",
+            Self::TraditionalChinese => "zh_TW.UTF-8",
+            Self::CentralEuropean    => "pl_PL.UTF-8",
+            Self::Cyrillic           => "ru_RU.UTF-8",
+            Self::Western            => "en_US.UTF-8",
+            Self::Greek              => "el_GR.UTF-8",
+            Self::Turkish            => "tr_TR.UTF-8",
+            Self::Hebrew             => "he_IL.UTF-8",
+            Self::Arabic             => "ar_SA.UTF-8",
+            Self::Baltic             => "lt_LT.UTF-8",
+            Self::Vietnamese         => "vi_VN.UTF-8"
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+
+/// SDL HIDAPI driver of the specific controllers family
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum HidapiDriver {
+    Ps4,
+    Ps5,
+    Xbox,
+    Switch,
+    Steam
+}
+
+impl HidapiDriver {
+    /// Get name of the variable toggling the driver
+    pub fn env(&self) -> &'static str {
+        match self {
+            Self::Ps4    => "SDL_JOYSTICK_HIDAPI_PS4",
+            Self::Ps5    => "SDL_JOYSTICK_HIDAPI_PS5",
+            Self::Xbox   => "SDL_JOYSTICK_HIDAPI_XBOX",
+            Self::Switch => "SDL_JOYSTICK_HIDAPI_SWITCH",
+            Self::Steam  => "SDL_JOYSTICK_HIDAPI_STEAM"
+        }
+    }
+}
+
+/// Format devices list as `0xVID/0xPID,...`
+fn devices_list(devices: &[(u16, u16)]) -> OsString {
+    let list = devices.iter()
+        .map(|(vid, pid)| format!("0x{vid:04x}/0x{pid:04x}"))
+        .collect::<Vec<_>>()
+        .join(",");
+
+    OsString::from(list)
+}
+
+/// Controllers detection settings of SDL and proton
+///
+/// Empty and `None` values keep default behavior
+///
+/// ```
+/// use wincompatlib::prelude::*;
+///
+/// let wine = Wine::from_binary("wine")
+///     .with_controller(Controller {
+///         hidapi: Some(false),
+///         ignore_devices: vec![(0x28de, 0x11ff)],
+///         ..Controller::default()
+///     });
+///
+/// assert_eq!(wine.get_envs()["SDL_JOYSTICK_HIDAPI"], "0");
+/// assert_eq!(wine.get_envs()["SDL_GAMECONTROLLER_IGNORE_DEVICES"], "0x28de/0x11ff");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Controller {
+    /// Controller mappings in SDL format, specified by `SDL_GAMECONTROLLERCONFIG` variable
+    pub mappings: Vec<String>,
+
+    /// Use HIDAPI drivers instead of the generic ones, specifies `SDL_JOYSTICK_HIDAPI` variable
+    pub hidapi: Option<bool>,
+
+    /// Toggles of HIDAPI drivers for specific controllers
+    pub hidapi_drivers: BTreeMap<HidapiDriver, bool>,
+
+    /// Devices (vendor id, product id) ignored by SDL, specifies `SDL_GAMECONTROLLER_IGNORE_DEVICES` variable
+    pub ignore_devices: Vec<(u16, u16)>,
+
+    /// Make SDL see the virtual gamepad created by Steam Input,
+    /// specifies `SDL_GAMECONTROLLER_ALLOW_STEAM_VIRTUAL_GAMEPAD` variable
+    pub steam_virtual_gamepad: Option<bool>,
+
+    /// Make proton prefer SDL over raw HID access, specifies `PROTON_PREFER_SDL` variable
+    pub prefer_sdl: bool,
+
+    /// Devices (vendor id, product id) proton exposes to the game as raw HID devices,
+    /// specifies `PROTON_ENABLE_HIDRAW` variable
+    pub hidraw_devices: Vec<(u16, u16)>
+}
+
+impl Controller {
+    /// Get environment variables of the controller settings
+    pub fn get_envs(&self) -> Vec<(&'static str, OsString)> {
+        let bool_env = |value: bool| OsString::from(if value { "1" } else { "0" });
+
+        let mut envs = Vec::new();
+
+        if !self.mappings.is_empty() {
+            envs.push(("SDL_GAMECONTROLLERCONFIG", OsString::from(self.mappings.join("\n"))));
+        }
+
+        if let Some(hidapi) = self.hidapi {
+            envs.push(("SDL_JOYSTICK_HIDAPI", bool_env(hidapi)));
+        }
+
+        for (driver, enabled) in &self.hidapi_drivers {
+            envs.push((driver.env(), bool_env(*enabled)));
+        }
+
+        if !self.ignore_devices.is_empty() {
+            envs.push(("SDL_GAMECONTROLLER_IGNORE_DEVICES", devices_list(&self.ignore_devices)));
+        }
+
+        if let Some(allow) = self.steam_virtual_gamepad {
+            envs.push(("SDL_GAMECONTROLLER_ALLOW_STEAM_VIRTUAL_GAMEPAD", bool_env(allow)));
+        }
+
+        if self.prefer_sdl {
+            envs.push(("PROTON_PREFER_SDL", bool_env(true)));
+        }
+
+        if !self.hidraw_devices.is_empty() {
+            envs.push(("PROTON_ENABLE_HIDRAW", devices_list(&self.hidraw_devices)));
+        }
+
+        envs
+    }
+}
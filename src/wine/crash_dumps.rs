@@ -0,0 +1,141 @@
+use std::path::{Path, PathBuf};
+
+use crate::prefix::RegFile;
+
+use super::*;
+
+/// Windows Error Reporting local dumps settings key
+const LOCAL_DUMPS_KEY: &str = "HKEY_LOCAL_MACHINE\\Software\\Microsoft\\Windows\\Windows Error Reporting\\LocalDumps";
+
+/// Settings of the debugger started by wine when a program crashes
+const AEDEBUG_KEY: &str = "HKEY_LOCAL_MACHINE\\Software\\Microsoft\\Windows NT\\CurrentVersion\\AeDebug";
+
+/// Debugger set by `wine.inf`, shows the crash dialog
+const DEFAULT_DEBUGGER: &str = "winedbg --auto %ld %ld";
+
+/// Folder in the `C:` drive where crash dumps of the prefix are stored
+pub const CRASH_DUMPS_FOLDER: &str = "CrashDumps";
+
+/// Content of the crash dumps, specified by `DumpType` value of the WER settings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DumpType {
+    /// Stack traces and modules list only
+    #[default]
+    Mini,
+
+    /// Whole memory of the crashed process
+    Full
+}
+
+impl DumpType {
+    pub fn to_dword(&self) -> u32 {
+        match self {
+            Self::Mini => 1,
+            Self::Full => 2
+        }
+    }
+}
+
+impl Wine {
+    /// Get unix path to the crash dumps folder of the prefix
+    pub fn crash_dumps_folder(&self) -> PathBuf {
+        self.prefix_path().join("drive_c").join(CRASH_DUMPS_FOLDER)
+    }
+
+    /// Enable crash dumps stored in the `C:\CrashDumps` folder
+    ///
+    /// Wine doesn't implement Windows Error Reporting, so its debugger (`AeDebug` key) is replaced
+    /// by `winedbg --minidump` writing `crash.dmp` file, which keeps only the last crash until it's collected.
+    /// Windows Error Reporting local dumps settings (`dump_type`, `count`) are written as well
+    /// for the runtimes implementing them.
+    /// Use `Limits::core_dump_size` to get core dumps of the wine processes themselves
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// let wine = Wine::default().with_prefix("/path/to/prefix");
+    ///
+    /// wine.enable_crash_dumps(DumpType::Mini, 10).expect("Failed to enable crash dumps");
+    ///
+    /// wine.run("/path/to/game.exe")
+    ///     .and_then(|mut game| Ok(game.wait()?))
+    ///     .expect("Failed to run game");
+    ///
+    /// for dump in wine.collect_crash_dumps("/path/to/report").expect("Failed to collect crash dumps") {
+    ///     println!("Collected {:?}", dump);
+    /// }
+    /// ```
+    pub fn enable_crash_dumps(&self, dump_type: DumpType, count: u32) -> Result<()> {
+        self.check_writable()?;
+
+        std::fs::create_dir_all(self.crash_dumps_folder())?;
+
+        self.reg_add(AEDEBUG_KEY, "Debugger", format!("winedbg --minidump C:\\{CRASH_DUMPS_FOLDER}\\crash.dmp %ld %ld"))?;
+
+        self.reg_add(LOCAL_DUMPS_KEY, "DumpFolder", format!("C:\\{CRASH_DUMPS_FOLDER}"))?;
+        self.reg_add(LOCAL_DUMPS_KEY, "DumpType", dump_type.to_dword())?;
+        self.reg_add(LOCAL_DUMPS_KEY, "DumpCount", count)
+    }
+
+    /// Disable crash dumps enabled by `enable_crash_dumps`, restoring the default wine debugger
+    ///
+    /// Values which are already removed are skipped
+    pub fn disable_crash_dumps(&self) -> Result<()> {
+        self.reg_add(AEDEBUG_KEY, "Debugger", DEFAULT_DEBUGGER)?;
+
+        for name in ["DumpFolder", "DumpType", "DumpCount"] {
+            if let Err(err) = self.reg_delete(LOCAL_DUMPS_KEY, name) {
+                // `reg delete` fails for missing values
+                let exists = RegFile::open(self.prefix_path().join("system.reg")).ok()
+                    .and_then(|registry| registry.key(LOCAL_DUMPS_KEY.trim_start_matches("HKEY_LOCAL_MACHINE\\"))
+                        .map(|key| key.values.contains_key(name)))
+                    .unwrap_or(false);
+
+                if exists {
+                    return Err(err);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Move `.dmp` files from the crash dumps folder of the prefix to the given folder
+    ///
+    /// Returns paths to the moved files
+    pub fn collect_crash_dumps<T: AsRef<Path>>(&self, folder: T) -> Result<Vec<PathBuf>> {
+        self.check_writable()?;
+
+        let folder = folder.as_ref();
+
+        let entries = match self.crash_dumps_folder().read_dir() {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err.into())
+        };
+
+        let mut dumps = Vec::new();
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+
+            if !path.extension().map(|ext| ext.eq_ignore_ascii_case("dmp")).unwrap_or(false) {
+                continue;
+            }
+
+            std::fs::create_dir_all(folder)?;
+
+            let target = folder.join(entry.file_name());
+
+            // Dumps folder can be on another filesystem
+            if std::fs::rename(&path, &target).is_err() {
+                std::fs::copy(&path, &target)?;
+                std::fs::remove_file(&path)?;
+            }
+
+            dumps.push(target);
+        }
+
+        Ok(dumps)
+    }
+}
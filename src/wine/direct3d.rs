@@ -0,0 +1,53 @@
+/// Graphics API used by wined3d
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Renderer {
+    /// OpenGL renderer (default)
+    Gl,
+
+    /// Vulkan renderer
+    Vulkan,
+
+    /// Software renderer without 3D support
+    Gdi,
+
+    /// Don't support 3D at all
+    No3d
+}
+
+impl Renderer {
+    pub fn to_str(&self) -> &str {
+        match self {
+            Self::Gl     => "gl",
+            Self::Vulkan => "vulkan",
+            Self::Gdi    => "gdi",
+            Self::No3d   => "no3d"
+        }
+    }
+}
+
+/// Shader backend used by wined3d
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ShaderBackend {
+    /// GLSL shaders for the OpenGL renderer (default)
+    Glsl,
+
+    /// ARB assembly shaders for the OpenGL renderer
+    Arb,
+
+    /// SPIR-V shaders for the Vulkan renderer
+    Spirv,
+
+    /// Disable shaders support
+    None
+}
+
+impl ShaderBackend {
+    pub fn to_str(&self) -> &str {
+        match self {
+            Self::Glsl  => "glsl",
+            Self::Arb   => "arb",
+            Self::Spirv => "spirv",
+            Self::None  => "none"
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use std::path::Path;
+
+use super::*;
+
+use crate::pe::PeFile;
+
+pub trait WineDllExt {
+    fn register_dll<T: AsRef<Path>>(&self, dll: T) -> Result<()>;
+    fn unregister_dll<T: AsRef<Path>>(&self, dll: T) -> Result<()>;
+}
+
+/// Convert absolute unix path to the windows one using `Z:` drive which is mapped to the root folder
+///
+/// Returns `ErrorKind::InvalidInput` error if the path is not valid UTF-8
+pub(crate) fn unix_to_windows_path(path: &Path) -> Result<OsString> {
+    let Some(path) = path.to_str() else {
+        return Err(Error::new(ErrorKind::InvalidInput, format!("Path is not valid UTF-8: {path:?}")));
+    };
+
+    Ok(OsString::from(format!("Z:{}", path.replace('/', "\\"))))
+}
+
+fn run_regsvr32(wine: &Wine, dll: &Path, unregister: bool) -> Result<()> {
+    let dll = dll.canonicalize()?;
+    let windows_path = unix_to_windows_path(&dll)?;
+
+    // 32 bit libraries must be registered by the 32 bit regsvr32 in 64 bit prefixes
+    let arch = WineArch::from_prefix(wine.prefix_path())?.or(wine.arch);
+
+    let regsvr32 = match (arch, PeFile::open(&dll)?.is_64bit) {
+        (Some(WineArch::Win64) | None, false) => "C:\\windows\\syswow64\\regsvr32.exe",
+        _ => "regsvr32"
+    };
+
+    let mut args = vec![OsString::from(regsvr32), OsString::from("/s")];
+
+    if unregister {
+        args.push(OsString::from("/u"));
+    }
+
+    args.push(windows_path);
+
+    let output = wine.run_args_output(args)?;
+
+    match output.status.success() {
+        true  => Ok(()),
+        false => Err(Error::other(format!(
+            "Failed to {} {:?}: regsvr32 exited with code {}",
+            if unregister { "unregister" } else { "register" },
+            dll,
+            output.status.code().unwrap_or(-1)
+        )))
+    }
+}
+
+impl WineDllExt for Wine {
+    /// Register COM library in the prefix. Runs `regsvr32 /s` command
+    ///
+    /// 32 bit libraries are registered by the 32 bit `regsvr32` in 64 bit prefixes
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// Wine::default()
+    ///     .with_prefix("/path/to/prefix")
+    ///     .register_dll("/path/to/prefix/drive_c/windows/system32/quartz.dll")
+    ///     .expect("Failed to register quartz");
+    /// ```
+    fn register_dll<T: AsRef<Path>>(&self, dll: T) -> Result<()> {
+        self.check_writable()?;
+
+        run_regsvr32(self, dll.as_ref(), false)
+    }
+
+    /// Unregister COM library in the prefix. Runs `regsvr32 /s /u` command
+    fn unregister_dll<T: AsRef<Path>>(&self, dll: T) -> Result<()> {
+        self.check_writable()?;
+
+        run_regsvr32(self, dll.as_ref(), true)
+    }
+}
@@ -0,0 +1,220 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Serialize, Deserialize};
+
+use crate::prefix::PrefixConfig;
+
+use super::*;
+
+/// Extension added to the game files replaced by the mod
+pub const DLL_MOD_BACKUP_EXT: &str = "wincompatlib-backup";
+
+/// Dll-based mod loader, e.g. Special K or an ASI loader using `dinput8.dll` proxy
+///
+/// ```
+/// use wincompatlib::prelude::*;
+///
+/// let special_k = DllMod::new("special-k")
+///     .with_file("dxgi.dll", "/path/to/SpecialK64.dll")
+///     .with_file("SpecialK.ini", "/path/to/SpecialK.ini");
+///
+/// assert_eq!(special_k.overrides(), vec!["dxgi"]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DllMod {
+    pub name: String,
+
+    /// Files copied to the game folder: name in the game folder -> source path
+    pub files: BTreeMap<String, PathBuf>
+}
+
+impl DllMod {
+    pub fn new<T: Into<String>>(name: T) -> Self {
+        Self {
+            name: name.into(),
+            files: BTreeMap::new()
+        }
+    }
+
+    pub fn with_file<N: Into<String>, S: Into<PathBuf>>(mut self, name: N, source: S) -> Self {
+        self.files.insert(name.into(), source.into());
+
+        self
+    }
+
+    /// Names of the libraries which need native override
+    pub fn overrides(&self) -> Vec<&str> {
+        self.files.keys()
+            .filter_map(|name| {
+                let path = Path::new(name);
+
+                match path.extension() {
+                    Some(ext) if ext.eq_ignore_ascii_case("dll") => path.file_stem().and_then(|stem| stem.to_str()),
+                    _ => None
+                }
+            })
+            .collect()
+    }
+}
+
+/// Mod installed by `Wine::install_dll_mod`, recorded in the prefix's `PrefixConfig`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InstalledDllMod {
+    pub name: String,
+
+    /// Path to the game executable
+    pub exe: PathBuf,
+
+    /// Files copied to the game folder
+    pub files: Vec<PathBuf>,
+
+    /// Original game files moved aside, restored on uninstall
+    #[serde(default)]
+    pub backups: Vec<PathBuf>,
+
+    /// Libraries overridden for the game executable
+    #[serde(default)]
+    pub overrides: Vec<String>
+}
+
+impl InstalledDllMod {
+    /// Remove mod files and restore original game files
+    fn revert_files(&self) -> Result<()> {
+        for file in &self.files {
+            match std::fs::remove_file(file) {
+                Err(err) if err.kind() != ErrorKind::NotFound => return Err(err.into()),
+                _ => ()
+            }
+        }
+
+        for backup in &self.backups {
+            std::fs::rename(backup, backup.with_extension(""))?;
+        }
+
+        Ok(())
+    }
+}
+
+fn exe_name(exe: &Path) -> Result<(&Path, String)> {
+    match (exe.parent(), exe.file_name()) {
+        (Some(folder), Some(name)) => Ok((folder, name.to_string_lossy().to_string())),
+        _ => Err(Error::new(ErrorKind::InvalidInput, format!("Invalid executable path: {exe:?}")))
+    }
+}
+
+impl Wine {
+    /// Install dll-based mod loader to the game folder
+    ///
+    /// Game files with the same names are moved aside and restored by `uninstall_dll_mod`.
+    /// Native overrides of the mod libraries are set for this executable only,
+    /// and installed files are recorded in the prefix's `PrefixConfig`
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// let asi_loader = DllMod::new("asi-loader")
+    ///     .with_file("dinput8.dll", "/path/to/dinput8.dll");
+    ///
+    /// Wine::default()
+    ///     .with_prefix("/path/to/prefix")
+    ///     .install_dll_mod(&asi_loader, "/path/to/game/game.exe")
+    ///     .expect("Failed to install mod loader");
+    /// ```
+    pub fn install_dll_mod<T: AsRef<Path>>(&self, dll_mod: &DllMod, exe: T) -> Result<InstalledDllMod> {
+        self.check_writable()?;
+
+        let exe = exe.as_ref();
+        let (folder, exe_name) = exe_name(exe)?;
+
+        let mut config = PrefixConfig::load_or_default(self.prefix_path())?;
+
+        if config.dll_mods.iter().any(|installed| installed.name == dll_mod.name && installed.exe == exe) {
+            return Err(Error::new(ErrorKind::AlreadyExists, format!("{} is already installed for {exe:?}", dll_mod.name)));
+        }
+
+        let mut installed = InstalledDllMod {
+            name: dll_mod.name.clone(),
+            exe: exe.to_path_buf(),
+            files: Vec::new(),
+            backups: Vec::new(),
+            overrides: Vec::new()
+        };
+
+        let mut install = || -> Result<()> {
+            for (name, source) in &dll_mod.files {
+                let target = folder.join(name);
+
+                if target.exists() {
+                    let backup = folder.join(format!("{name}.{DLL_MOD_BACKUP_EXT}"));
+
+                    if backup.exists() {
+                        return Err(Error::new(ErrorKind::AlreadyExists, format!("{target:?} is already replaced by another mod")));
+                    }
+
+                    std::fs::rename(&target, &backup)?;
+
+                    installed.backups.push(backup);
+                }
+
+                installed.files.push(target.clone());
+
+                std::fs::copy(source, target)?;
+            }
+
+            for dll in dll_mod.overrides() {
+                self.set_app_dll_override(&exe_name, dll, OverrideMode::NativeBuiltin)?;
+
+                installed.overrides.push(dll.to_string());
+            }
+
+            Ok(())
+        };
+
+        if let Err(err) = install() {
+            let _ = installed.revert_files();
+
+            for dll in &installed.overrides {
+                let _ = self.remove_app_dll_override(&exe_name, dll);
+            }
+
+            return Err(err);
+        }
+
+        config.dll_mods.push(installed.clone());
+        config.save(self.prefix_path())?;
+
+        Ok(installed)
+    }
+
+    /// Revert mod installed by `install_dll_mod`
+    ///
+    /// Returns `ErrorKind::NotFound` error if mod isn't recorded in the prefix's `PrefixConfig`
+    pub fn uninstall_dll_mod<T: AsRef<Path>>(&self, name: &str, exe: T) -> Result<()> {
+        self.check_writable()?;
+
+        let exe = exe.as_ref();
+        let (_, exe_name) = exe_name(exe)?;
+
+        let mut config = PrefixConfig::load_or_default(self.prefix_path())?;
+
+        let Some(index) = config.dll_mods.iter().position(|installed| installed.name == name && installed.exe == exe) else {
+            return Err(Error::new(ErrorKind::NotFound, format!("{name} is not installed for {exe:?}")));
+        };
+
+        let installed = config.dll_mods.remove(index);
+
+        installed.revert_files()?;
+
+        for dll in &installed.overrides {
+            self.remove_app_dll_override(&exe_name, dll)?;
+        }
+
+        Ok(config.save(self.prefix_path())?)
+    }
+
+    /// Get mods installed by `install_dll_mod`
+    pub fn installed_dll_mods(&self) -> Result<Vec<InstalledDllMod>> {
+        Ok(PrefixConfig::load_or_default(self.prefix_path())?.dll_mods)
+    }
+}
@@ -0,0 +1,308 @@
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::os::unix::ffi::OsStrExt;
+
+use super::*;
+
+use crate::checksum::crc32;
+use crate::warning::Warning;
+
+/// Drive type reported to windows programs, stored in the `HKLM\Software\Wine\Drives` key
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DriveType {
+    /// Local hard disk
+    Hd,
+    Network,
+    Floppy,
+    Cdrom
+}
+
+impl DriveType {
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(drive_type: &str) -> Option<Self> {
+        match drive_type {
+            "hd"      => Some(Self::Hd),
+            "network" => Some(Self::Network),
+            "floppy"  => Some(Self::Floppy),
+            "cdrom"   => Some(Self::Cdrom),
+
+            _ => None
+        }
+    }
+
+    pub fn to_str(&self) -> &str {
+        match self {
+            Self::Hd      => "hd",
+            Self::Network => "network",
+            Self::Floppy  => "floppy",
+            Self::Cdrom   => "cdrom"
+        }
+    }
+}
+
+/// How the disc image was mounted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiscMountKind {
+    /// Mounted by `fuseiso`
+    FuseIso,
+
+    /// Attached to the loop device and mounted by `udisksctl`
+    Udisks
+}
+
+/// Disc image attached to the drive letter of the wine prefix
+///
+/// The struct owns the mount, so it can't be cloned
+#[derive(Debug, PartialEq, Eq)]
+pub struct DiscMount {
+    pub letter: char,
+    pub kind: DiscMountKind,
+    pub mount_point: PathBuf,
+
+    /// Loop device the image is attached to, if mounted by `udisksctl`
+    pub loop_device: Option<PathBuf>,
+
+    wine: Wine
+}
+
+impl DiscMount {
+    /// Remove drive from the wine prefix, reset its type and unmount the disc image
+    pub fn unmount(self) -> Result<()> {
+        let dosdevices = self.wine.prefix_path().join("dosdevices");
+
+        let _ = std::fs::remove_file(dosdevices.join(format!("{}:", self.letter)));
+        let _ = std::fs::remove_file(dosdevices.join(format!("{}::", self.letter)));
+
+        // Type is only set after the drive is mapped
+        let drive_type = self.wine.reset_drive_type(self.letter);
+
+        match self.kind {
+            DiscMountKind::FuseIso => {
+                crate::prefix::fuse_unmount(&self.mount_point)?;
+
+                let _ = std::fs::remove_dir(&self.mount_point);
+            }
+
+            DiscMountKind::Udisks => {
+                if let Some(device) = &self.loop_device {
+                    run_tool(Command::new("udisksctl").arg("unmount").arg("-b").arg(device))?;
+                    run_tool(Command::new("udisksctl").arg("loop-delete").arg("-b").arg(device))?;
+                }
+            }
+        }
+
+        drive_type
+    }
+}
+
+pub trait WineDriveExt {
+    fn map_drive<T: AsRef<Path>>(&self, letter: char, path: T) -> Result<()>;
+    fn unmap_drive(&self, letter: char) -> Result<()>;
+
+    fn set_drive_type(&self, letter: char, drive_type: DriveType) -> Result<()>;
+    fn reset_drive_type(&self, letter: char) -> Result<()>;
+
+    fn mount_disc_image<T: AsRef<Path>>(&self, image: T, letter: char) -> Result<DiscMount>;
+}
+
+const DRIVES_KEY: &str = "HKEY_LOCAL_MACHINE\\Software\\Wine\\Drives";
+
+fn drive_letter(letter: char) -> Result<char> {
+    match letter.is_ascii_alphabetic() {
+        true  => Ok(letter.to_ascii_lowercase()),
+        false => Err(Error::new(ErrorKind::InvalidInput, format!("Invalid drive letter: {letter}")))
+    }
+}
+
+/// Run mount tool and return its stdout
+fn run_tool(command: &mut Command) -> Result<String> {
+    let output = command.stdin(Stdio::null()).output()?;
+
+    match output.status.success() {
+        true  => Ok(String::from_utf8_lossy(&output.stdout).to_string()),
+        false => Err(Error::other(format!("{:?} failed: {}", command.get_program(), String::from_utf8_lossy(&output.stderr).trim())))
+    }
+}
+
+/// Replace dosdevices entry with the symlink
+fn symlink(link: &Path, target: &Path) -> Result<()> {
+    if link.symlink_metadata().is_ok() {
+        std::fs::remove_file(link)?;
+    }
+
+    Ok(std::os::unix::fs::symlink(target, link)?)
+}
+
+/// Get value from the `udisksctl` output like `Mapped file image.iso as /dev/loop0.`
+fn udisks_path(output: &str, marker: &str) -> Option<PathBuf> {
+    // Image path can contain the marker too
+    let (_, path) = output.rsplit_once(marker)?;
+
+    Some(PathBuf::from(path.trim().trim_end_matches('.')))
+}
+
+impl WineDriveExt for Wine {
+    /// Map drive letter to the given folder by creating `dosdevices` symlink
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// Wine::default()
+    ///     .with_prefix("/path/to/prefix")
+    ///     .map_drive('g', "/path/to/games")
+    ///     .expect("Failed to map drive");
+    /// ```
+    fn map_drive<T: AsRef<Path>>(&self, letter: char, path: T) -> Result<()> {
+        self.check_writable()?;
+
+        let letter = drive_letter(letter)?;
+        let dosdevices = self.prefix_path().join("dosdevices");
+
+        std::fs::create_dir_all(&dosdevices)?;
+
+        symlink(&dosdevices.join(format!("{letter}:")), path.as_ref())
+    }
+
+    /// Remove drive letter mapping
+    fn unmap_drive(&self, letter: char) -> Result<()> {
+        self.check_writable()?;
+
+        let letter = drive_letter(letter)?;
+
+        match std::fs::remove_file(self.prefix_path().join("dosdevices").join(format!("{letter}:"))) {
+            Err(err) if err.kind() != ErrorKind::NotFound => Err(err.into()),
+            _ => Ok(())
+        }
+    }
+
+    /// Set type of the drive reported to windows programs
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// Wine::default()
+    ///     .with_prefix("/path/to/prefix")
+    ///     .set_drive_type('d', DriveType::Cdrom)
+    ///     .expect("Failed to set drive type");
+    /// ```
+    fn set_drive_type(&self, letter: char, drive_type: DriveType) -> Result<()> {
+        let letter = drive_letter(letter)?;
+
+        self.reg_add(DRIVES_KEY, &format!("{letter}:"), drive_type.to_str())
+    }
+
+    /// Let wine detect type of the drive automatically
+    fn reset_drive_type(&self, letter: char) -> Result<()> {
+        let letter = drive_letter(letter)?;
+
+        self.reg_delete(DRIVES_KEY, &format!("{letter}:"))
+    }
+
+    /// Mount disc image (ISO) and attach it to the drive letter marked as CD-ROM
+    ///
+    /// `fuseiso` is tried first, `udisksctl` is used as a fallback.
+    /// Image mounted by udisks is also exposed as a raw device (`x::` entry).
+    ///
+    /// Returns `ErrorKind::AlreadyExists` error if the drive letter is already mapped.
+    /// `DiscMount::unmount` removes the mapping and resets the drive type
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// let wine = Wine::from_binary("/path/to/wine")
+    ///     .with_prefix("/path/to/prefix");
+    ///
+    /// let disc = wine.mount_disc_image("/path/to/game.iso", 'd')
+    ///     .expect("Failed to mount disc image");
+    ///
+    /// wine.run("D:\\setup.exe")
+    ///     .and_then(|mut child| Ok(child.wait()?))
+    ///     .expect("Failed to run installer");
+    ///
+    /// disc.unmount().expect("Failed to unmount disc image");
+    /// ```
+    fn mount_disc_image<T: AsRef<Path>>(&self, image: T, letter: char) -> Result<DiscMount> {
+        self.check_writable()?;
+
+        let letter = drive_letter(letter)?;
+        let image = image.as_ref().canonicalize()?;
+
+        let dosdevices = self.prefix_path().join("dosdevices");
+
+        for entry in [format!("{letter}:"), format!("{letter}::")] {
+            if dosdevices.join(&entry).symlink_metadata().is_ok() {
+                return Err(Error::new(ErrorKind::AlreadyExists, format!("Drive {entry} is already mapped")));
+            }
+        }
+
+        // Same letter can be used by different prefixes at the same time
+        let prefix_hash = crc32(self.prefix_path().as_os_str().as_bytes());
+
+        let mount_point = std::env::temp_dir()
+            .join(format!("wincompatlib-disc-{}-{prefix_hash:08x}-{letter}", std::process::id()));
+
+        std::fs::create_dir_all(&mount_point)?;
+
+        let mount = match run_tool(Command::new("fuseiso").arg(&image).arg(&mount_point)) {
+            Ok(_) => DiscMount {
+                letter,
+                kind: DiscMountKind::FuseIso,
+                mount_point,
+                loop_device: None,
+                wine: self.clone()
+            },
+
+            Err(err) => {
+                let _ = std::fs::remove_dir(&mount_point);
+
+                self.events.warning(Warning::DiscMountFallback { reason: err.to_string() });
+
+                let output = run_tool(Command::new("udisksctl").args(["loop-setup", "-r", "-f"]).arg(&image))?;
+
+                let device = udisks_path(&output, " as ")
+                    .ok_or_else(|| Error::other(format!("Failed to parse udisksctl output: {output}")))?;
+
+                let output = match run_tool(Command::new("udisksctl").arg("mount").arg("-b").arg(&device)) {
+                    Ok(output) => output,
+                    Err(err) => {
+                        let _ = run_tool(Command::new("udisksctl").arg("loop-delete").arg("-b").arg(&device));
+
+                        return Err(err);
+                    }
+                };
+
+                let mount_point = udisks_path(&output, " at ")
+                    .ok_or_else(|| Error::other(format!("Failed to parse udisksctl output: {output}")))?;
+
+                DiscMount {
+                    letter,
+                    kind: DiscMountKind::Udisks,
+                    mount_point,
+                    loop_device: Some(device),
+                    wine: self.clone()
+                }
+            }
+        };
+
+        let attach = || -> Result<()> {
+            std::fs::create_dir_all(&dosdevices)?;
+
+            symlink(&dosdevices.join(format!("{letter}:")), &mount.mount_point)?;
+
+            if let Some(device) = &mount.loop_device {
+                symlink(&dosdevices.join(format!("{letter}::")), device)?;
+            }
+
+            // Copy protection checks require disc to be in the CD-ROM drive
+            self.set_drive_type(letter, DriveType::Cdrom)
+        };
+
+        if let Err(err) = attach() {
+            let _ = mount.unmount();
+
+            return Err(err);
+        }
+
+        Ok(mount)
+    }
+}
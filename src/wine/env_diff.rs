@@ -0,0 +1,95 @@
+use std::collections::BTreeMap;
+
+use super::*;
+
+/// Difference between environment variables of two runner configurations
+///
+/// Secrets registered in the events of both configurations are replaced with `***`
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct EnvDiff {
+    /// Variables set only by the other configuration
+    pub added: BTreeMap<String, OsString>,
+
+    /// Variables set only by this configuration
+    pub removed: BTreeMap<String, OsString>,
+
+    /// Variables set by both configurations to different values: (this, other)
+    pub changed: BTreeMap<String, (OsString, OsString)>
+}
+
+impl EnvDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+impl std::fmt::Display for EnvDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (key, value) in &self.added {
+            writeln!(f, "+ {key}={}", value.to_string_lossy())?;
+        }
+
+        for (key, value) in &self.removed {
+            writeln!(f, "- {key}={}", value.to_string_lossy())?;
+        }
+
+        for (key, (from, to)) in &self.changed {
+            writeln!(f, "~ {key}: {} -> {}", from.to_string_lossy(), to.to_string_lossy())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Wine {
+    /// Compare environment variables set by this and other configuration
+    ///
+    /// ```
+    /// use wincompatlib::prelude::*;
+    ///
+    /// let a = Wine::from_binary("wine").with_prefix("/path/to/a");
+    /// let b = Wine::from_binary("wine").with_prefix("/path/to/b").with_hdr(true);
+    ///
+    /// let diff = a.env_diff(&b);
+    ///
+    /// assert!(diff.added.contains_key("DXVK_HDR"));
+    /// assert!(diff.changed.contains_key("WINEPREFIX"));
+    ///
+    /// println!("{diff}");
+    /// ```
+    pub fn env_diff(&self, other: &Wine) -> EnvDiff {
+        // Secret of one configuration can be used by the other one as well
+        let redact = |key: &str, value: &OsString| {
+            let value = self.events.redact_env(OsStr::new(key), value);
+
+            other.events.redact_env(OsStr::new(key), &value)
+        };
+
+        let this = self.get_envs();
+        let other = other.get_envs();
+
+        let mut diff = EnvDiff::default();
+
+        for (key, value) in &this {
+            match other.get(key) {
+                Some(other_value) if other_value != value => {
+                    diff.changed.insert(key.to_string(), (redact(key, value), redact(key, other_value)));
+                }
+
+                Some(_) => (),
+
+                None => {
+                    diff.removed.insert(key.to_string(), redact(key, value));
+                }
+            }
+        }
+
+        for (key, value) in &other {
+            if !this.contains_key(key) {
+                diff.added.insert(key.to_string(), redact(key, value));
+            }
+        }
+
+        diff
+    }
+}
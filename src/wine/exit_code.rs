@@ -0,0 +1,120 @@
+use std::process::ExitStatus;
+
+/// Known windows exit codes: (code, name, description)
+const KNOWN_CODES: &[(u32, &str, &str)] = &[
+    (0,          "ERROR_SUCCESS",                 "The operation completed successfully"),
+    (1,          "ERROR_INVALID_FUNCTION",        "Generic failure"),
+    (1602,       "ERROR_INSTALL_USEREXIT",        "Installation was cancelled by the user"),
+    (1603,       "ERROR_INSTALL_FAILURE",         "Fatal error during installation"),
+    (1618,       "ERROR_INSTALL_ALREADY_RUNNING", "Another installation is already in progress"),
+    (1641,       "ERROR_SUCCESS_REBOOT_INITIATED", "Installation succeeded and the installer initiated a restart"),
+    (3010,       "ERROR_SUCCESS_REBOOT_REQUIRED", "Installation succeeded but a restart is required to complete it"),
+    (0x80000003, "STATUS_BREAKPOINT",             "A breakpoint was reached, likely an unhandled debug trap"),
+    (0xC0000005, "STATUS_ACCESS_VIOLATION",       "Access violation: the program tried to access invalid memory"),
+    (0xC0000017, "STATUS_NO_MEMORY",              "Not enough memory to complete the operation"),
+    (0xC000001D, "STATUS_ILLEGAL_INSTRUCTION",    "Illegal instruction: the program uses CPU instructions unsupported by the host"),
+    (0xC000007B, "STATUS_INVALID_IMAGE_FORMAT",   "Invalid image format: likely 32/64 bit mismatch of the program and its libraries"),
+    (0xC0000094, "STATUS_INTEGER_DIVIDE_BY_ZERO", "Integer division by zero"),
+    (0xC00000FD, "STATUS_STACK_OVERFLOW",         "Stack overflow"),
+    (0xC0000135, "STATUS_DLL_NOT_FOUND",          "A required DLL was not found"),
+    (0xC0000139, "STATUS_ENTRYPOINT_NOT_FOUND",   "A required function was not found in some DLL"),
+    (0xC000013A, "STATUS_CONTROL_C_EXIT",         "The program was terminated by Ctrl+C"),
+    (0xC0000142, "STATUS_DLL_INIT_FAILED",        "Some DLL failed to initialize"),
+    (0xC0000409, "STATUS_STACK_BUFFER_OVERRUN",   "Stack buffer overrun, or the program called fast fail"),
+    (0xE06D7363, "CPP_EXCEPTION",                 "Unhandled C++ exception")
+];
+
+/// Windows exit code of the program
+///
+/// Note that unix processes exit statuses are limited to 8 bits, so codes taken from `ExitStatus`
+/// of the wine process are truncated. Full codes can be obtained from windows side,
+/// e.g. by running `cmd /c "program.exe & echo %ERRORLEVEL%"`
+///
+/// ```
+/// use wincompatlib::prelude::*;
+///
+/// let code = ExitCode(0xC0000135);
+///
+/// assert_eq!(code.name(), Some("STATUS_DLL_NOT_FOUND"));
+/// assert_eq!(code.to_string(), "0xC0000135 (STATUS_DLL_NOT_FOUND): A required DLL was not found");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ExitCode(pub u32);
+
+impl ExitCode {
+    /// Get exit code from the process exit status
+    ///
+    /// Returns `None` if process was terminated by a signal
+    pub fn from_status(status: ExitStatus) -> Option<Self> {
+        status.code().map(Self::from)
+    }
+
+    pub fn code(&self) -> u32 {
+        self.0
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Check if installer asked to restart the system to finish installation (`msiexec` codes 1641 and 3010)
+    ///
+    /// Restart can be imitated with `Wine::restart`
+    ///
+    /// ```
+    /// use wincompatlib::prelude::*;
+    ///
+    /// assert!(ExitCode(3010).is_reboot_required());
+    /// ```
+    pub fn is_reboot_required(&self) -> bool {
+        self.0 == 1641 || self.0 == 3010
+    }
+
+    /// Check if the code is an NTSTATUS error, so the program has crashed
+    pub fn is_crash(&self) -> bool {
+        self.0 & 0xC0000000 == 0xC0000000 || self.0 == 0x80000003 || self.0 == 0xE06D7363
+    }
+
+    /// Get name of the known code
+    pub fn name(&self) -> Option<&'static str> {
+        KNOWN_CODES.iter()
+            .find(|(code, _, _)| *code == self.0)
+            .map(|(_, name, _)| *name)
+    }
+
+    /// Get human-readable description of the known code
+    pub fn description(&self) -> Option<&'static str> {
+        KNOWN_CODES.iter()
+            .find(|(code, _, _)| *code == self.0)
+            .map(|(_, _, description)| *description)
+    }
+}
+
+impl From<u32> for ExitCode {
+    fn from(code: u32) -> Self {
+        Self(code)
+    }
+}
+
+impl From<i32> for ExitCode {
+    /// Windows exit codes are often represented as negative numbers, e.g. `-1073741819` for `0xC0000005`
+    fn from(code: i32) -> Self {
+        Self(code as u32)
+    }
+}
+
+impl std::fmt::Display for ExitCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Large numbers are readable only in hex
+        if self.0 > 0xFFFF {
+            write!(f, "0x{:08X}", self.0)?;
+        } else {
+            write!(f, "{}", self.0)?;
+        }
+
+        match (self.name(), self.description()) {
+            (Some(name), Some(description)) => write!(f, " ({name}): {description}"),
+            _ => Ok(())
+        }
+    }
+}
@@ -0,0 +1,219 @@
+use std::path::Path;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::PermissionsExt;
+use super::*;
+
+use crate::events::CommandInfo;
+
+/// Quote string for POSIX shell so it's passed as a single argument
+///
+/// ```
+/// use wincompatlib::wine::shell_quote;
+///
+/// assert_eq!(shell_quote("it's a game.exe"), b"'it'\\''s a game.exe'");
+/// ```
+pub fn shell_quote<T: AsRef<OsStr>>(value: T) -> Vec<u8> {
+    let mut quoted = vec![b'\''];
+
+    for byte in value.as_ref().as_bytes() {
+        if *byte == b'\'' {
+            quoted.extend_from_slice(b"'\\''");
+        } else {
+            quoted.push(*byte);
+        }
+    }
+
+    quoted.push(b'\'');
+
+    quoted
+}
+
+/// Quote argument for the windows command line so it's parsed by
+/// `CommandLineToArgvW` and the MSVC runtime as a single argument
+///
+/// ```
+/// use wincompatlib::wine::windows_quote;
+///
+/// assert_eq!(windows_quote("C:\\Program Files\\Game\\"), "\"C:\\Program Files\\Game\\\\\"");
+/// assert_eq!(windows_quote("game.exe"), "game.exe");
+/// ```
+pub fn windows_quote<T: AsRef<str>>(value: T) -> String {
+    let value = value.as_ref();
+
+    if !value.is_empty() && !value.contains([' ', '\t', '\n', '\x0b', '"']) {
+        return value.to_string();
+    }
+
+    let mut quoted = String::from("\"");
+    let mut backslashes = 0;
+
+    for char in value.chars() {
+        match char {
+            '\\' => backslashes += 1,
+
+            '"' => {
+                // Escape all the preceding backslashes and the quote itself
+                quoted.push_str(&"\\".repeat(backslashes * 2 + 1));
+                quoted.push('"');
+
+                backslashes = 0;
+            }
+
+            _ => {
+                quoted.push_str(&"\\".repeat(backslashes));
+                quoted.push(char);
+
+                backslashes = 0;
+            }
+        }
+    }
+
+    // Trailing backslashes would escape the closing quote
+    quoted.push_str(&"\\".repeat(backslashes * 2));
+    quoted.push('"');
+
+    quoted
+}
+
+/// Split windows command line into arguments the way `CommandLineToArgvW` does
+///
+/// ```
+/// use wincompatlib::wine::windows_split;
+///
+/// assert_eq!(windows_split("\"C:\\Program Files\\Game\\uninstall.exe\" /S"), [
+///     "C:\\Program Files\\Game\\uninstall.exe", "/S"
+/// ]);
+/// ```
+pub fn windows_split<T: AsRef<str>>(command_line: T) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut chars = command_line.as_ref().chars().peekable();
+
+    loop {
+        while chars.next_if(|char| matches!(char, ' ' | '\t')).is_some() {}
+
+        if chars.peek().is_none() {
+            return args;
+        }
+
+        let mut arg = String::new();
+        let mut quoted = false;
+        let mut backslashes = 0;
+
+        while let Some(char) = chars.next() {
+            match char {
+                '\\' => backslashes += 1,
+
+                '"' => {
+                    arg.push_str(&"\\".repeat(backslashes / 2));
+
+                    // Odd number of backslashes escapes the quote,
+                    // and double quote inside of the quoted argument is a literal quote
+                    if backslashes % 2 == 1 || (quoted && chars.next_if_eq(&'"').is_some()) {
+                        arg.push('"');
+                    }
+
+                    else {
+                        quoted = !quoted;
+                    }
+
+                    backslashes = 0;
+                }
+
+                ' ' | '\t' if !quoted => break,
+
+                char => {
+                    arg.push_str(&"\\".repeat(backslashes));
+                    arg.push(char);
+
+                    backslashes = 0;
+                }
+            }
+        }
+
+        arg.push_str(&"\\".repeat(backslashes));
+
+        args.push(arg);
+    }
+}
+
+/// Render command as a shell script which reproduces it
+pub(crate) fn command_script(command: &CommandInfo, current_dir: Option<&Path>) -> Vec<u8> {
+    let mut script = b"#!/bin/sh\n# Generated by wincompatlib\n\n".to_vec();
+
+    let mut envs = command.envs.iter().collect::<Vec<_>>();
+
+    envs.sort();
+
+    for (key, value) in &envs {
+        match value {
+            Some(value) => {
+                script.extend_from_slice(b"export ");
+                script.extend_from_slice(key.as_bytes());
+                script.push(b'=');
+                script.extend(shell_quote(value));
+            }
+
+            None => {
+                script.extend_from_slice(b"unset ");
+                script.extend_from_slice(key.as_bytes());
+            }
+        }
+
+        script.push(b'\n');
+    }
+
+    if let Some(dir) = current_dir {
+        script.extend_from_slice(b"\ncd ");
+        script.extend(shell_quote(dir));
+        script.push(b'\n');
+    }
+
+    script.extend_from_slice(b"\nexec ");
+    script.extend(shell_quote(&command.program));
+
+    for arg in &command.args {
+        script.push(b' ');
+        script.extend(shell_quote(arg));
+    }
+
+    script.extend_from_slice(b" \"$@\"\n");
+
+    script
+}
+
+impl Wine {
+    /// Write shell script with the fully resolved environment and command line
+    /// this struct would run, so it can be reproduced outside of the application
+    ///
+    /// Additional arguments given to the script are passed to the command
+    ///
+    /// Secrets registered in the struct's events are replaced with `***`,
+    /// so the script must be edited to provide them before running
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// Wine::from_binary("/path/to/wine")
+    ///     .with_prefix("/path/to/prefix")
+    ///     .export_env_script("/path/to/run.sh", ["/path/to/game.exe"])
+    ///     .expect("Failed to export script");
+    /// ```
+    pub fn export_env_script<P, T, S>(&self, path: P, args: T) -> Result<()>
+    where
+        P: AsRef<Path>,
+        T: IntoIterator<Item = S>,
+        S: AsRef<OsStr>
+    {
+        let path = path.as_ref();
+
+        let command = self.command(args);
+        let mut info = CommandInfo::from_command(&command);
+
+        self.events.redact(&mut info);
+
+        std::fs::write(path, command_script(&info, command.get_current_dir()))?;
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755))?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,162 @@
+use std::path::PathBuf;
+
+use super::*;
+
+const FONT_LINK_KEY: &str = "HKEY_LOCAL_MACHINE\\Software\\Microsoft\\Windows NT\\CurrentVersion\\FontLink\\SystemLink";
+const FONT_REPLACEMENTS_KEY: &str = "HKEY_CURRENT_USER\\Software\\Wine\\Fonts\\Replacements";
+
+/// Fonts used by windows programs falling back to the linked CJK fonts
+const LINKED_FONTS: &[&str] = &[
+    "Tahoma",
+    "Microsoft Sans Serif",
+    "MS Sans Serif",
+    "MS Shell Dlg",
+    "MS Shell Dlg 2",
+    "Segoe UI",
+    "Arial",
+    "Lucida Sans Unicode"
+];
+
+/// Language of the CJK text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CjkLanguage {
+    Japanese,
+    SimplifiedChinese,
+    TraditionalChinese,
+    Korean
+}
+
+impl CjkLanguage {
+    /// Language name used by fontconfig
+    pub fn fc_lang(&self) -> &str {
+        match self {
+            Self::Japanese           => "ja",
+            Self::SimplifiedChinese  => "zh-cn",
+            Self::TraditionalChinese => "zh-tw",
+            Self::Korean             => "ko"
+        }
+    }
+
+    /// Names of the windows fonts replaced by the host font
+    ///
+    /// ```
+    /// use wincompatlib::prelude::*;
+    ///
+    /// assert!(CjkLanguage::Japanese.windows_fonts().contains(&"MS Gothic"));
+    /// ```
+    pub fn windows_fonts(&self) -> &[&str] {
+        match self {
+            Self::Japanese           => &["MS Gothic", "MS PGothic", "MS UI Gothic", "MS Mincho", "MS PMincho", "Meiryo", "Yu Gothic"],
+            Self::SimplifiedChinese  => &["SimSun", "NSimSun", "SimHei", "Microsoft YaHei"],
+            Self::TraditionalChinese => &["MingLiU", "PMingLiU", "Microsoft JhengHei"],
+            Self::Korean             => &["Gulim", "GulimChe", "Dotum", "DotumChe", "Batang", "Malgun Gothic"]
+        }
+    }
+}
+
+/// Font file installed in the host system
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HostFont {
+    pub path: PathBuf,
+    pub family: String
+}
+
+impl HostFont {
+    /// Find host font supporting the given language using `fc-list` command
+    ///
+    /// Regular style is preferred
+    pub fn find_cjk(language: CjkLanguage) -> Option<Self> {
+        let output = Command::new("fc-list")
+            .arg(format!(":lang={}", language.fc_lang()))
+            .arg("--format")
+            .arg("%{file}\t%{family[0]}\t%{style[0]}\n")
+            .output()
+            .ok()?;
+
+        let output = String::from_utf8_lossy(&output.stdout);
+
+        let fonts = output.lines()
+            .filter_map(|line| {
+                let mut parts = line.split('\t');
+
+                Some((parts.next()?, parts.next()?, parts.next().unwrap_or_default()))
+            })
+            .collect::<Vec<_>>();
+
+        let (path, family, _) = fonts.iter()
+            .find(|(_, _, style)| matches!(*style, "Regular" | "Book" | "Normal"))
+            .or(fonts.first())?;
+
+        Some(Self {
+            path: PathBuf::from(path),
+            family: family.to_string()
+        })
+    }
+
+    /// Get file name of the font
+    pub fn file_name(&self) -> String {
+        self.path.file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default()
+    }
+}
+
+impl Wine {
+    /// Link host CJK fonts into the prefix and register them, so CJK text is rendered instead of boxes
+    ///
+    /// Fonts are symlinked to the `C:\windows\Fonts` folder, used as replacements of the standard
+    /// windows CJK fonts and linked to the default UI fonts in the given languages order.
+    /// Languages without host fonts are skipped
+    ///
+    /// Returns installed fonts, or `ErrorKind::NotFound` error if no fonts were found
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// let fonts = Wine::default()
+    ///     .with_prefix("/path/to/prefix")
+    ///     .install_cjk_fonts(&[CjkLanguage::Japanese, CjkLanguage::SimplifiedChinese])
+    ///     .expect("Failed to install CJK fonts");
+    ///
+    /// for (language, font) in fonts {
+    ///     println!("{:?}: {}", language, font.family);
+    /// }
+    /// ```
+    pub fn install_cjk_fonts(&self, languages: &[CjkLanguage]) -> Result<Vec<(CjkLanguage, HostFont)>> {
+        self.check_writable()?;
+
+        let fonts = languages.iter()
+            .filter_map(|language| HostFont::find_cjk(*language).map(|font| (*language, font)))
+            .collect::<Vec<_>>();
+
+        if fonts.is_empty() {
+            return Err(Error::new(ErrorKind::NotFound, "No CJK fonts found in the system"));
+        }
+
+        let folder = self.prefix_path().join("drive_c/windows/Fonts");
+
+        std::fs::create_dir_all(&folder)?;
+
+        for (language, font) in &fonts {
+            let target = folder.join(font.file_name());
+
+            if target.symlink_metadata().is_err() {
+                std::os::unix::fs::symlink(&font.path, target)?;
+            }
+
+            for windows_font in language.windows_fonts() {
+                self.reg_add(FONT_REPLACEMENTS_KEY, windows_font, font.family.as_str())?;
+            }
+        }
+
+        let links = fonts.iter()
+            .map(|(_, font)| format!("{},{}", font.file_name(), font.family))
+            .collect::<Vec<_>>();
+
+        for linked_font in LINKED_FONTS {
+            self.reg_add(FONT_LINK_KEY, linked_font, links.clone())?;
+        }
+
+        Ok(fonts)
+    }
+}
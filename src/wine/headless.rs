@@ -0,0 +1,69 @@
+use std::ffi::OsStr;
+use std::process::Command;
+
+use super::*;
+
+/// Virtual display used to run wine without a real one, e.g. on CI servers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Headless {
+    /// `xvfb-run -a`, starts new X server for each command
+    Xvfb,
+
+    /// Headless weston compositor with xwayland
+    Weston
+}
+
+impl Headless {
+    /// Program and arguments placed before the wrapped command
+    pub fn wrapper(&self) -> (&str, &[&str]) {
+        match self {
+            Self::Xvfb   => ("xvfb-run", &["-a"]),
+            Self::Weston => ("weston", &["--backend=headless", "--xwayland", "--"])
+        }
+    }
+
+    /// Find available virtual display program, preferring Xvfb
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// match Headless::detect() {
+    ///     Some(headless) => println!("Using {:?}", headless),
+    ///     None => eprintln!("Neither xvfb-run nor weston is installed")
+    /// }
+    /// ```
+    pub fn detect() -> Option<Self> {
+        let paths = std::env::var_os("PATH")?;
+
+        [Self::Xvfb, Self::Weston].into_iter().find(|headless| {
+            let (program, _) = headless.wrapper();
+
+            std::env::split_paths(&paths).any(|folder| folder.join(program).exists())
+        })
+    }
+
+    /// Check if the current process has no display to show windows on
+    pub fn is_needed() -> bool {
+        ["DISPLAY", "WAYLAND_DISPLAY"].iter()
+            .all(|env| std::env::var_os(env).map(|value| value.is_empty()).unwrap_or(true))
+    }
+}
+
+impl Wine {
+    /// Create command running program in the virtual display, if it's specified
+    pub(crate) fn headless_command<T: AsRef<OsStr>>(&self, program: T) -> Command {
+        match self.headless {
+            Some(headless) => {
+                let (wrapper, args) = headless.wrapper();
+
+                let mut command = Command::new(wrapper);
+
+                command.args(args).arg(program);
+
+                command
+            }
+
+            None => Command::new(program)
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use std::io::{Error, Result};
+
+use crate::warning::Warning;
+
+use super::Wine;
+
+/// Resource limits applied to the spawned wine processes
+///
+/// `None` values keep limits of the current process
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Limits {
+    /// Maximal number of open file descriptors (`RLIMIT_NOFILE`)
+    pub open_files: Option<u64>,
+
+    /// Maximal size of core dump files in bytes (`RLIMIT_CORE`)
+    ///
+    /// Use `Limits::UNLIMITED` to enable core dumps for debugging, or `0` to disable them
+    pub core_dump_size: Option<u64>,
+
+    /// Maximal size of the virtual memory in bytes (`RLIMIT_AS`)
+    pub address_space: Option<u64>
+}
+
+impl Limits {
+    /// Value removing the limit
+    pub const UNLIMITED: u64 = libc::RLIM_INFINITY;
+
+    /// Open files limit needed by esync
+    pub const ESYNC_OPEN_FILES: u64 = 524288;
+
+    pub fn is_empty(&self) -> bool {
+        self.open_files.is_none() && self.core_dump_size.is_none() && self.address_space.is_none()
+    }
+
+    /// Apply limits to the current process
+    ///
+    /// Hard limit is raised as well if it's lower than the requested value, which requires privileges
+    pub fn apply(&self) -> Result<()> {
+        let limits = [
+            (libc::RLIMIT_NOFILE, self.open_files),
+            (libc::RLIMIT_CORE, self.core_dump_size),
+            (libc::RLIMIT_AS, self.address_space)
+        ];
+
+        for (resource, value) in limits {
+            let Some(value) = value else {
+                continue;
+            };
+
+            let mut limit = libc::rlimit {
+                rlim_cur: 0,
+                rlim_max: 0
+            };
+
+            unsafe {
+                if libc::getrlimit(resource, &mut limit) == -1 {
+                    return Err(Error::last_os_error());
+                }
+
+                limit.rlim_cur = value as libc::rlim_t;
+
+                if limit.rlim_max != libc::RLIM_INFINITY && (value == Self::UNLIMITED || limit.rlim_max < limit.rlim_cur) {
+                    limit.rlim_max = limit.rlim_cur;
+                }
+
+                if libc::setrlimit(resource, &limit) == -1 {
+                    return Err(Error::last_os_error());
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Write value to the `/proc/self/oom_score_adj` file
+///
+/// Only async-signal-safe functions are used so it can be called in `pre_exec`
+pub(crate) fn apply_oom_score_adj(value: &[u8]) -> Result<()> {
+    unsafe {
+        let file = libc::open(c"/proc/self/oom_score_adj".as_ptr(), libc::O_WRONLY);
+
+        if file == -1 {
+            return Err(Error::last_os_error());
+        }
+
+        let written = libc::write(file, value.as_ptr() as *const libc::c_void, value.len());
+
+        libc::close(file);
+
+        if written == -1 {
+            return Err(Error::last_os_error());
+        }
+    }
+
+    Ok(())
+}
+
+impl Wine {
+    /// Check that spawned processes can open enough files to use esync
+    ///
+    /// Wine raises the soft limit up to the hard one itself, so the hard limit is checked
+    /// unless `Limits::open_files` is specified. Found problem is reported as a warning
+    pub fn check_file_limit(&self) -> crate::error::Result<Option<Warning>> {
+        let current = match self.limits.open_files {
+            Some(limit) => limit,
+            None => {
+                let mut limit = libc::rlimit {
+                    rlim_cur: 0,
+                    rlim_max: 0
+                };
+
+                if unsafe { libc::getrlimit(libc::RLIMIT_NOFILE, &mut limit) } == -1 {
+                    return Err(Error::last_os_error().into());
+                }
+
+                limit.rlim_max
+            }
+        };
+
+        if current >= Limits::ESYNC_OPEN_FILES {
+            return Ok(None);
+        }
+
+        let warning = Warning::LowFileLimit {
+            current,
+            recommended: Limits::ESYNC_OPEN_FILES
+        };
+
+        self.events.warning(warning.clone());
+
+        Ok(Some(warning))
+    }
+}
@@ -0,0 +1,134 @@
+use std::path::{Path, PathBuf};
+use std::os::unix::ffi::OsStrExt;
+
+use super::*;
+
+/// Locale variables affecting characters encoding, from the most important one
+const CTYPE_VARIABLES: [&str; 3] = ["LC_ALL", "LC_CTYPE", "LANG"];
+
+/// Non-ASCII path used with non-UTF-8 locale
+///
+/// Wine converts unix file names using the locale's encoding, so such paths
+/// are translated into garbage and the game fails with "file not found" errors
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocaleIssue {
+    pub path: PathBuf,
+
+    /// Variable which specifies the locale, or empty string if no locale variable is set
+    pub variable: &'static str,
+
+    /// Used locale, `C` if no locale variable is set
+    pub locale: String,
+
+    /// UTF-8 version of the used locale
+    pub suggested: String
+}
+
+impl std::fmt::Display for LocaleIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.variable {
+            "" => write!(f, "{:?} contains non-ASCII characters but no locale is set, use {}", self.path, self.suggested),
+
+            variable => write!(f, "{:?} contains non-ASCII characters but {variable}={} is not a UTF-8 locale, use {}", self.path, self.locale, self.suggested)
+        }
+    }
+}
+
+/// Check if locale uses UTF-8 encoding
+///
+/// ```
+/// use wincompatlib::prelude::*;
+///
+/// assert!(is_utf8_locale("en_US.UTF-8"));
+/// assert!(is_utf8_locale("C.utf8"));
+/// assert!(!is_utf8_locale("de_DE.ISO-8859-1"));
+/// assert!(!is_utf8_locale("C"));
+/// ```
+pub fn is_utf8_locale(locale: &str) -> bool {
+    let locale = locale.to_ascii_lowercase();
+
+    locale.contains(".utf-8") || locale.contains(".utf8")
+}
+
+/// Get UTF-8 version of the locale keeping its language, territory and modifier
+///
+/// ```
+/// use wincompatlib::prelude::*;
+///
+/// assert_eq!(utf8_locale("de_DE.ISO-8859-1"), "de_DE.UTF-8");
+/// assert_eq!(utf8_locale("sr_RS@latin"), "sr_RS.UTF-8@latin");
+/// assert_eq!(utf8_locale("POSIX"), "C.UTF-8");
+/// ```
+pub fn utf8_locale(locale: &str) -> String {
+    let (locale, modifier) = match locale.split_once('@') {
+        Some((locale, modifier)) => (locale, Some(modifier)),
+        None => (locale, None)
+    };
+
+    let name = locale.split('.').next().unwrap_or_default();
+
+    let name = match name {
+        "" | "C" | "POSIX" => "C",
+        name => name
+    };
+
+    match modifier {
+        Some(modifier) => format!("{name}.UTF-8@{modifier}"),
+        None => format!("{name}.UTF-8")
+    }
+}
+
+impl Wine {
+    /// Get locale used by wine to encode file names: (variable, locale)
+    ///
+    /// Variables of this struct take priority over the host's ones.
+    /// Returns `("", "C")` if no locale variable is set
+    pub fn ctype_locale(&self) -> (&'static str, String) {
+        let envs = self.get_envs();
+
+        for variable in CTYPE_VARIABLES {
+            let locale = envs.get(variable)
+                .cloned()
+                .or_else(|| std::env::var_os(variable))
+                .filter(|locale| !locale.is_empty());
+
+            if let Some(locale) = locale {
+                return (variable, locale.to_string_lossy().to_string());
+            }
+        }
+
+        ("", String::from("C"))
+    }
+
+    /// Check that non-ASCII path will be passed to wine without mojibake
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// let wine = Wine::default();
+    ///
+    /// if let Some(issue) = wine.check_locale("/path/to/ゲーム/game.exe") {
+    ///     eprintln!("{issue}");
+    /// }
+    /// ```
+    pub fn check_locale<T: AsRef<Path>>(&self, path: T) -> Option<LocaleIssue> {
+        let path = path.as_ref();
+
+        if path.as_os_str().as_bytes().is_ascii() {
+            return None;
+        }
+
+        let (variable, locale) = self.ctype_locale();
+
+        if is_utf8_locale(&locale) {
+            return None;
+        }
+
+        Some(LocaleIssue {
+            path: path.to_path_buf(),
+            variable,
+            suggested: utf8_locale(&locale),
+            locale
+        })
+    }
+}
@@ -0,0 +1,60 @@
+/// Verbosity preset for the whole stack: wine, DXVK, VKD3D and proton
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum LogLevel {
+    /// Disable all the logs
+    Silent,
+
+    /// Errors and warnings only, without wine's fixme spam
+    Normal,
+
+    /// Useful information to debug common issues, e.g. loaded dlls and exceptions
+    Debug,
+
+    /// Everything which may help to debug the issue. Very slow
+    Trace
+}
+
+impl LogLevel {
+    /// Get environment variables configuring this log level
+    ///
+    /// ```
+    /// use wincompatlib::prelude::*;
+    ///
+    /// assert!(LogLevel::Silent.get_envs().contains(&("WINEDEBUG", "-all")));
+    /// ```
+    pub fn get_envs(&self) -> [(&'static str, &'static str); 5] {
+        match self {
+            Self::Silent => [
+                ("WINEDEBUG", "-all"),
+                ("DXVK_LOG_LEVEL", "none"),
+                ("VKD3D_DEBUG", "none"),
+                ("VKD3D_SHADER_DEBUG", "none"),
+                ("PROTON_LOG", "0")
+            ],
+
+            Self::Normal => [
+                ("WINEDEBUG", "fixme-all"),
+                ("DXVK_LOG_LEVEL", "warn"),
+                ("VKD3D_DEBUG", "warn"),
+                ("VKD3D_SHADER_DEBUG", "none"),
+                ("PROTON_LOG", "0")
+            ],
+
+            Self::Debug => [
+                ("WINEDEBUG", "+timestamp,+pid,+tid,+seh,+loaddll"),
+                ("DXVK_LOG_LEVEL", "info"),
+                ("VKD3D_DEBUG", "info"),
+                ("VKD3D_SHADER_DEBUG", "fixme"),
+                ("PROTON_LOG", "1")
+            ],
+
+            Self::Trace => [
+                ("WINEDEBUG", "+timestamp,+pid,+tid,+seh,+debugstr,+loaddll,+module,+relay"),
+                ("DXVK_LOG_LEVEL", "debug"),
+                ("VKD3D_DEBUG", "trace"),
+                ("VKD3D_SHADER_DEBUG", "trace"),
+                ("PROTON_LOG", "1")
+            ]
+        }
+    }
+}
@@ -1,20 +1,106 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, BTreeMap};
 use std::ffi::{OsString, OsStr};
 use std::os::unix::prelude::OsStringExt;
 use std::path::PathBuf;
-use std::io::{Error, ErrorKind, Result};
+use std::sync::{Arc, OnceLock};
+use std::io::ErrorKind;
+
+use crate::error::{Error, Result};
 use std::process::{Command, Stdio, Output};
 
 mod with_ext;
 mod boot_ext;
 mod run_ext;
+mod server_ext;
+mod reg_ext;
+mod dll_ext;
+mod drive_ext;
+mod service_ext;
+mod overrides;
+mod direct3d;
+mod export;
+mod process;
+mod exit_code;
+mod log_level;
+mod wine_debug;
+mod limits;
+mod priority;
+mod codepage;
+mod overlay;
+mod wrapper;
+mod controller;
+mod portable;
+mod env_diff;
+mod windows_version;
+mod vkd3d;
+mod wow64;
+mod addons;
+mod headless;
+mod build_info;
+mod post_processing;
+mod dll_mod;
+mod crash_dumps;
+mod fonts;
+mod run_summary;
+mod scope;
+mod network;
+mod version;
+mod preflight;
+mod locale;
+mod read_only;
+mod builtins;
+
+#[cfg(feature = "pty")]
+mod pty_ext;
 
 pub use with_ext::WineWithExt;
 pub use boot_ext::WineBootExt;
-pub use run_ext::WineRunExt;
+pub use run_ext::{WineRunExt, WineStdin};
+pub use server_ext::{WineServerExt, active_servers};
+pub use reg_ext::{WineRegExt, RegValue, app_defaults_key, wininet_proxy_server};
+pub use dll_ext::WineDllExt;
+pub use drive_ext::{WineDriveExt, DriveType, DiscMount, DiscMountKind};
+pub use service_ext::{WineServiceExt, Service, ServiceState};
+pub use overrides::{OverrideMode, DllOverrides};
+pub use direct3d::{Renderer, ShaderBackend};
+pub use export::{shell_quote, windows_quote, windows_split};
+pub use process::WineProcess;
+pub use exit_code::ExitCode;
+pub use log_level::LogLevel;
+pub use wine_debug::{WineDebug, WineDebugRule, WineDebugClass, WineDebugChannel};
+pub use limits::Limits;
+pub use priority::ServerPriority;
+pub use codepage::Codepage;
+pub use overlay::Overlay;
+pub use wrapper::Wrapper;
+pub use controller::{Controller, HidapiDriver};
+pub use portable::PortableRuntime;
+pub use env_diff::EnvDiff;
+pub use windows_version::WindowsVersion;
+pub use vkd3d::{Vkd3d, Vkd3dOption, Vkd3dFeatureLevel, Vkd3dDebugLevel};
+pub use wow64::Wow64Warning;
+pub use addons::{AddonKind, SharedAddon, MissingAddons};
+pub use headless::Headless;
+pub use build_info::WineBuildInfo;
+pub use post_processing::{VkBasalt, ReShadeApi, RESHADE_MOD};
+pub use dll_mod::{DllMod, InstalledDllMod, DLL_MOD_BACKUP_EXT};
+pub use crash_dumps::{DumpType, CRASH_DUMPS_FOLDER};
+pub use fonts::{CjkLanguage, HostFont};
+pub use run_summary::RunSummary;
+pub use scope::SystemdScope;
+pub use network::NetworkMode;
+pub use version::{WineVersion, WineVariant};
+pub use preflight::PreflightIssue;
+pub use builtins::BuiltinTool;
+pub use locale::{LocaleIssue, is_utf8_locale, utf8_locale};
+
+#[cfg(feature = "pty")]
+pub use pty_ext::{WinePtyExt, PtyProcess};
 
 pub use derive_builder::Builder;
 
+use crate::events::Events;
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum WineArch {
     Win32,
@@ -37,30 +123,73 @@ impl WineArch {
             Self::Win64 => "win64"
         }
     }
+
+    /// Try to detect wine prefix architecture from the `#arch` header of its `system.reg` file
+    ///
+    /// Returns `Ok(None)` if prefix doesn't specify its architecture (very old wine versions)
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// match WineArch::from_prefix("/path/to/prefix") {
+    ///     Ok(Some(arch)) => println!("Prefix architecture: {}", arch.to_str()),
+    ///     Ok(None) => println!("Prefix architecture is not specified"),
+    ///     Err(err) => eprintln!("Failed to read prefix: {}", err)
+    /// }
+    /// ```
+    pub fn from_prefix<T: Into<PathBuf>>(prefix: T) -> Result<Option<Self>> {
+        let system_reg = std::fs::read(prefix.into().join("system.reg"))?;
+
+        for line in String::from_utf8_lossy(&system_reg).lines() {
+            if let Some(arch) = line.strip_prefix("#arch=") {
+                return Ok(Self::from_str(arch.trim()));
+            }
+        }
+
+        Ok(None)
+    }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub enum WineLoader {
     /// Set `WINELOADER` variable as binary specified in `Wine` struct
     Current,
 
     /// Don't set `WINELOADER` variable, so wine will try to use system-wide binary
+    #[default]
     Default,
 
     /// Set custom `WINELOADER` variable
     Custom(PathBuf)
 }
 
-impl Default for WineLoader {
-    fn default() -> Self {
-        Self::Default
+/// Values computed from the wine binary, shared between clones of the struct
+#[derive(Debug, Default)]
+struct BinaryCache {
+    version: OnceLock<OsString>,
+    wineboot: OnceLock<PathBuf>,
+    wineserver: OnceLock<PathBuf>
+}
+
+// Cache doesn't affect the struct's behavior
+impl PartialEq for BinaryCache {
+    fn eq(&self, _other: &Self) -> bool {
+        true
     }
 }
 
+impl Eq for BinaryCache {}
+
+/// Wine runner
+///
+/// The struct is `Send + Sync` and cheap to clone: wine version and paths
+/// to its binaries are resolved once and shared between all the clones
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Wine {
     binary: PathBuf,
 
+    cache: Arc<BinaryCache>,
+
     /// Specifies `WINEPREFIX` variable
     pub prefix: Option<PathBuf>,
 
@@ -74,7 +203,87 @@ pub struct Wine {
     pub wineserver: Option<PathBuf>,
 
     /// Specifies `WINELOADER` variable
-    pub wineloader: WineLoader
+    pub wineloader: WineLoader,
+
+    /// Specifies `WINEDEBUG`, `DXVK_LOG_LEVEL`, `VKD3D_DEBUG` and `PROTON_LOG` variables
+    pub log_level: Option<LogLevel>,
+
+    /// Specifies `WINEDEBUG` variable, overriding the one set by `LogLevel`
+    pub wine_debug: Option<WineDebug>,
+
+    /// Resource limits of the spawned processes
+    pub limits: Limits,
+
+    /// OOM killer score adjustment of the spawned processes, from -1000 to 1000
+    pub oom_score_adj: Option<i32>,
+
+    /// Scheduling settings of the wineserver
+    pub server_priority: ServerPriority,
+
+    /// Specifies `LC_ALL` variable so wine uses this codepage, and is stored in the registry by `update_prefix`
+    pub codepage: Option<Codepage>,
+
+    /// Specifies `LANG` and `LC_CTYPE` variables, `LC_ALL` set by the codepage takes priority
+    pub locale: Option<String>,
+
+    /// Overlays explicitly enabled (`true`) or disabled (`false`)
+    pub overlays: BTreeMap<Overlay, bool>,
+
+    /// Specifies `VKBASALT_CONFIG_FILE` variable
+    pub vkbasalt_config: Option<PathBuf>,
+
+    /// Enable HDR output in DXVK, proton and gamescope
+    pub hdr: bool,
+
+    /// Controllers detection settings
+    pub controller: Controller,
+
+    /// Use libraries bundled with the wine build, see `PortableRuntime`
+    pub portable: bool,
+
+    /// D3D12 translation settings
+    pub vkd3d: Vkd3d,
+
+    /// Libraries overrides set by the `WINEDLLOVERRIDES` variable
+    pub dll_overrides: DllOverrides,
+
+    /// Programs which run wine, from the outermost one
+    pub wrappers: Vec<Wrapper>,
+
+    /// Virtual display wrapping all the wrappers, wine and wineboot commands
+    pub headless: Option<Headless>,
+
+    /// Systemd scope wrapping all the processes started by the `WineRunExt` methods
+    pub scope: Option<SystemdScope>,
+
+    /// Network access of the processes started by the `WineRunExt` methods
+    pub network: NetworkMode,
+
+    /// HTTP and HTTPS proxy URL, e.g. `http://proxy.example.com:3128`
+    pub proxy: Option<String>,
+
+    /// Time zone of the processes, e.g. `Asia/Tokyo` or `UTC`
+    pub timezone: Option<String>,
+
+    /// Fake system time specification passed to the `faketime` program, e.g. `@2024-01-01 12:00:00`
+    pub faketime: Option<String>,
+
+    /// What to do with missing Gecko and Mono addons when the prefix is created
+    pub missing_addons: MissingAddons,
+
+    /// Never show wine dialogs: skip missing addons, install msi packages quietly (see `install_msi`)
+    /// and disable crash dialog in newly created prefixes
+    pub unattended: bool,
+
+    /// Refuse operations which would modify the wine prefix, see `Wine::verify_read_only`
+    pub read_only: bool,
+
+    /// Custom environment variables, e.g. `DXVK_HUD` or `MANGOHUD`.
+    /// They are applied last and override the ones generated from other fields
+    pub env: HashMap<OsString, OsString>,
+
+    /// Event sinks notified about everything this struct does
+    pub events: Events
 }
 
 impl Default for Wine {
@@ -87,11 +296,38 @@ impl Wine {
     pub fn new<T: Into<PathBuf>>(binary: T, prefix: Option<T>, arch: Option<WineArch>, wineboot: Option<T>, wineserver: Option<T>, wineloader: WineLoader) -> Self {
         Wine {
             binary: binary.into(),
+            cache: Arc::new(BinaryCache::default()),
             prefix: prefix.map(|value| value.into()),
             arch,
             wineboot: wineboot.map(|value| value.into()),
             wineserver: wineserver.map(|value| value.into()),
-            wineloader
+            wineloader,
+            log_level: None,
+            wine_debug: None,
+            limits: Limits::default(),
+            oom_score_adj: None,
+            server_priority: ServerPriority::default(),
+            codepage: None,
+            locale: None,
+            overlays: BTreeMap::new(),
+            vkbasalt_config: None,
+            hdr: false,
+            controller: Controller::default(),
+            portable: false,
+            vkd3d: Vkd3d::default(),
+            dll_overrides: DllOverrides::default(),
+            wrappers: Vec::new(),
+            headless: None,
+            scope: None,
+            network: NetworkMode::default(),
+            proxy: None,
+            timezone: None,
+            faketime: None,
+            missing_addons: MissingAddons::default(),
+            unattended: false,
+            read_only: false,
+            env: HashMap::new(),
+            events: Events::default()
         }
     }
 
@@ -101,6 +337,8 @@ impl Wine {
 
     /// Try to get version of provided wine binary. Runs command: `wine --version`
     /// 
+    /// Successfully received version is cached
+    /// 
     /// ```
     /// use wincompatlib::prelude::*;
     /// 
@@ -110,13 +348,23 @@ impl Wine {
     /// }
     /// ```
     pub fn version(&self) -> Result<OsString> {
-        let output = Command::new(&self.binary)
-           .arg("--version")
-           .stdout(Stdio::piped())
-           .stderr(Stdio::null())
-           .output()?;
+        if let Some(version) = self.cache.version.get() {
+            return Ok(version.clone());
+        }
 
-        Ok(OsString::from_vec(output.stdout))
+        let output = self.events.output(Command::new(&self.binary)
+            .arg("--version")
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null()))
+            .map_err(|err| Error::from_spawn(&self.binary, err))?;
+
+        let version = OsString::from_vec(output.stdout);
+
+        if output.status.success() {
+            let _ = self.cache.version.set(version.clone());
+        }
+
+        Ok(version)
     }
 
     /// Get wine binary path
@@ -150,7 +398,9 @@ impl Wine {
     /// assert_eq!(Wine::from_binary("/wine_build_without_wineboot/wine").wineboot(), PathBuf::from("wineboot"));
     /// ```
     pub fn wineboot(&self) -> PathBuf {
-        self.wineboot.clone().unwrap_or_else(|| self.get_inner_binary("wineboot"))
+        self.wineboot.clone().unwrap_or_else(|| {
+            self.cache.wineboot.get_or_init(|| self.get_inner_binary("wineboot")).clone()
+        })
     }
 
     /// Get path to wineserver binary, or "wineserver" if not specified
@@ -167,7 +417,9 @@ impl Wine {
     /// assert_eq!(Wine::from_binary("/wine_build_without_wineserver/wine").wineserver(), PathBuf::from("wineserver"));
     /// ```
     pub fn wineserver(&self) -> PathBuf {
-        self.wineserver.clone().unwrap_or_else(|| self.get_inner_binary("wineserver"))
+        self.wineserver.clone().unwrap_or_else(|| {
+            self.cache.wineserver.get_or_init(|| self.get_inner_binary("wineserver")).clone()
+        })
     }
 
     /// Get path to wine binary, or "wine" if not specified (`WineLoader::Default`)
@@ -181,6 +433,8 @@ impl Wine {
 
     /// Get environment variables map from current struct's values
     /// 
+    /// Custom variables which names are not valid UTF-8 are skipped, see `get_os_envs`
+    /// 
     /// ```
     /// use wincompatlib::prelude::*;
     /// 
@@ -189,7 +443,7 @@ impl Wine {
     /// let wine = Wine::default().with_arch(WineArch::Win64);
     /// 
     /// Command::new(wine.binary())
-    ///     .envs(wine.get_envs())
+    ///     .envs(wine.get_os_envs())
     ///     .spawn();
     /// ```
     pub fn get_envs(&self) -> HashMap<&str, OsString> {
@@ -220,6 +474,102 @@ impl Wine {
             }
         }
 
+        env.extend(self.server_priority.get_envs());
+
+        if let Some(locale) = &self.locale {
+            env.insert("LANG", OsString::from(locale));
+            env.insert("LC_CTYPE", OsString::from(locale));
+        }
+
+        if let Some(codepage) = self.codepage {
+            env.insert("LC_ALL", OsString::from(codepage.locale()));
+        }
+
+        for (overlay, enabled) in &self.overlays {
+            env.extend(overlay.get_envs(*enabled));
+        }
+
+        if !self.dll_overrides.is_empty() {
+            env.insert("WINEDLLOVERRIDES", OsString::from(self.dll_overrides.to_string()));
+        }
+
+        if let Some(proxy) = &self.proxy {
+            for key in ["http_proxy", "https_proxy", "HTTP_PROXY", "HTTPS_PROXY"] {
+                env.insert(key, OsString::from(proxy));
+            }
+        }
+
+        if let Some(timezone) = &self.timezone {
+            env.insert("TZ", OsString::from(timezone));
+        }
+
+        // Wine and wineserver use monotonic clock for timeouts and sync,
+        // so faking it makes the wineserver hang
+        if self.faketime.is_some() {
+            env.insert("FAKETIME_DONT_FAKE_MONOTONIC", OsString::from("1"));
+        }
+
+        if let Some(config) = &self.vkbasalt_config {
+            env.insert("VKBASALT_CONFIG_FILE", config.as_os_str().to_os_string());
+        }
+
+        if self.hdr {
+            env.insert("DXVK_HDR", OsString::from("1"));
+            env.insert("PROTON_ENABLE_HDR", OsString::from("1"));
+        }
+
+        env.extend(self.controller.get_envs());
+
+        if self.portable {
+            env.extend(PortableRuntime::from_binary(&self.binary).get_envs());
+        }
+
+        if let Some(log_level) = self.log_level {
+            for (key, value) in log_level.get_envs() {
+                env.insert(key, OsString::from(value));
+            }
+        }
+
+        // Applied after the log level so explicit settings take priority
+        if let Some(debug) = &self.wine_debug {
+            env.insert("WINEDEBUG", OsString::from(debug.to_string()));
+        }
+
+        env.extend(self.vkd3d.get_envs());
+
+        // User's variables always win
+        for (key, value) in &self.env {
+            if let Some(key) = key.to_str() {
+                env.insert(key, value.clone());
+            }
+        }
+
+        env
+    }
+
+    /// Get environment variables applied to the commands started by this struct
+    ///
+    /// Same as `get_envs`, but also contains custom variables which names are not valid UTF-8
+    ///
+    /// ```
+    /// use wincompatlib::prelude::*;
+    ///
+    /// use std::ffi::OsString;
+    /// use std::os::unix::ffi::OsStringExt;
+    ///
+    /// let wine = Wine::from_binary("wine")
+    ///     .with_env(OsString::from_vec(b"GAME_\xFF".to_vec()), "1");
+    ///
+    /// assert_eq!(wine.get_os_envs().len(), wine.get_envs().len() + 1);
+    /// assert_eq!(wine.get_os_envs()[&OsString::from_vec(b"GAME_\xFF".to_vec())], "1");
+    /// ```
+    pub fn get_os_envs(&self) -> HashMap<OsString, OsString> {
+        let mut env = self.get_envs().into_iter()
+            .map(|(key, value)| (OsString::from(key), value))
+            .collect::<HashMap<_, _>>();
+
+        env.extend(self.env.clone());
+
         env
     }
 
@@ -0,0 +1,37 @@
+/// Network access of the programs started by the `WineRunExt` methods
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum NetworkMode {
+    #[default]
+    Enabled,
+
+    /// Run the whole process tree in a new network namespace with only the loopback interface.
+    /// Uses `bwrap` if it's installed, otherwise `unshare` (util-linux 2.38 or newer)
+    ///
+    /// Unprivileged user namespaces must be allowed by the kernel
+    Offline
+}
+
+impl NetworkMode {
+    /// Program and arguments placed before the wine binary, if any
+    ///
+    /// ```
+    /// use wincompatlib::prelude::*;
+    ///
+    /// assert_eq!(NetworkMode::Enabled.wrapper(), None);
+    /// assert!(NetworkMode::Offline.wrapper().is_some());
+    /// ```
+    pub fn wrapper(&self) -> Option<(&str, &[&str])> {
+        match self {
+            Self::Enabled => None,
+
+            Self::Offline if is_installed("bwrap") => Some(("bwrap", &["--dev-bind", "/", "/", "--unshare-net", "--"])),
+            Self::Offline => Some(("unshare", &["--user", "--map-current-user", "--net", "--"]))
+        }
+    }
+}
+
+fn is_installed(program: &str) -> bool {
+    std::env::var_os("PATH")
+        .map(|paths| std::env::split_paths(&paths).any(|folder| folder.join(program).exists()))
+        .unwrap_or(false)
+}
@@ -0,0 +1,60 @@
+use std::ffi::OsString;
+use std::os::unix::ffi::{OsStrExt, OsStringExt};
+
+/// Third-party overlay injected into the games by the host environment
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Overlay {
+    /// Steam overlay, injected with `LD_PRELOAD` and a vulkan layer
+    ///
+    /// Can only be disabled, enabling does nothing
+    Steam,
+
+    /// MangoHud vulkan layer
+    MangoHud,
+
+    /// vkBasalt post processing vulkan layer
+    VkBasalt,
+
+    /// OBS game capture vulkan layer
+    ObsVkCapture
+}
+
+impl Overlay {
+    /// Get environment variables enabling or disabling the overlay
+    ///
+    /// ```
+    /// use wincompatlib::prelude::*;
+    ///
+    /// assert_eq!(Overlay::MangoHud.get_envs(false), vec![("DISABLE_MANGOHUD", "1".into())]);
+    /// ```
+    pub fn get_envs(&self, enabled: bool) -> Vec<(&'static str, OsString)> {
+        match (self, enabled) {
+            (Self::Steam, true) => vec![],
+
+            (Self::Steam, false) => {
+                let mut envs = vec![("DISABLE_VK_LAYER_VALVE_steam_overlay_1", OsString::from("1"))];
+
+                // Remove overlay library from preloaded ones
+                if let Some(preload) = std::env::var_os("LD_PRELOAD") {
+                    let libs = preload.as_bytes()
+                        .split(|byte| *byte == b':' || *byte == b' ')
+                        .filter(|lib| !lib.is_empty() && !lib.ends_with(b"gameoverlayrenderer.so"))
+                        .collect::<Vec<_>>();
+
+                    envs.push(("LD_PRELOAD", OsString::from_vec(libs.join(&b':'))));
+                }
+
+                envs
+            }
+
+            (Self::MangoHud, true)      => vec![("MANGOHUD", "1".into())],
+            (Self::MangoHud, false)     => vec![("DISABLE_MANGOHUD", "1".into())],
+
+            (Self::VkBasalt, true)      => vec![("ENABLE_VKBASALT", "1".into())],
+            (Self::VkBasalt, false)     => vec![("DISABLE_VKBASALT", "1".into())],
+
+            (Self::ObsVkCapture, true)  => vec![("OBS_VKCAPTURE", "1".into())],
+            (Self::ObsVkCapture, false) => vec![("OBS_VKCAPTURE", "0".into())]
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use std::collections::BTreeMap;
+
+/// Way wine should load some library
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OverrideMode {
+    /// Use library provided by the application or the prefix
+    Native,
+
+    /// Use library implemented by wine
+    Builtin,
+
+    /// Try native library first, then builtin one
+    NativeBuiltin,
+
+    /// Try builtin library first, then native one
+    BuiltinNative,
+
+    /// Don't load library at all
+    Disabled
+}
+
+impl OverrideMode {
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(mode: &str) -> Option<Self> {
+        match mode.replace(' ', "").as_str() {
+            "native"  | "n" => Some(Self::Native),
+            "builtin" | "b" => Some(Self::Builtin),
+
+            "native,builtin" | "n,b" => Some(Self::NativeBuiltin),
+            "builtin,native" | "b,n" => Some(Self::BuiltinNative),
+
+            "" | "disabled" => Some(Self::Disabled),
+
+            _ => None
+        }
+    }
+
+    /// Get override value as it's stored in the registry
+    pub fn to_str(&self) -> &str {
+        match self {
+            Self::Native        => "native",
+            Self::Builtin       => "builtin",
+            Self::NativeBuiltin => "native,builtin",
+            Self::BuiltinNative => "builtin,native",
+            Self::Disabled      => ""
+        }
+    }
+}
+
+/// Set of libraries overrides serialized into the `WINEDLLOVERRIDES` variable
+///
+/// Libraries with the same mode are grouped together, disabled ones get empty value
+///
+/// ```
+/// use wincompatlib::prelude::*;
+///
+/// let overrides = DllOverrides::new()
+///     .with("d3d11", OverrideMode::Native)
+///     .with("DXGI.dll", OverrideMode::Native)
+///     .with("mscoree", OverrideMode::Disabled);
+///
+/// assert_eq!(overrides.to_string(), "d3d11,dxgi=native;mscoree=");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct DllOverrides {
+    /// Lowercase library names without `.dll` extension
+    pub overrides: BTreeMap<String, OverrideMode>
+}
+
+impl DllOverrides {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add (or replace) library override
+    pub fn with<T: AsRef<str>>(mut self, dll: T, mode: OverrideMode) -> Self {
+        self.insert(dll, mode);
+
+        self
+    }
+
+    /// Add (or replace) library override
+    pub fn insert<T: AsRef<str>>(&mut self, dll: T, mode: OverrideMode) {
+        let dll = dll.as_ref().trim().to_ascii_lowercase();
+        let dll = dll.strip_suffix(".dll").unwrap_or(&dll);
+
+        self.overrides.insert(dll.to_string(), mode);
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.overrides.is_empty()
+    }
+}
+
+impl std::fmt::Display for DllOverrides {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let modes = [
+            OverrideMode::Native,
+            OverrideMode::Builtin,
+            OverrideMode::NativeBuiltin,
+            OverrideMode::BuiltinNative,
+            OverrideMode::Disabled
+        ];
+
+        let groups = modes.into_iter()
+            .filter_map(|mode| {
+                let dlls = self.overrides.iter()
+                    .filter(|(_, dll_mode)| **dll_mode == mode)
+                    .map(|(dll, _)| dll.as_str())
+                    .collect::<Vec<_>>();
+
+                (!dlls.is_empty()).then(|| format!("{}={}", dlls.join(","), mode.to_str()))
+            })
+            .collect::<Vec<_>>();
+
+        write!(f, "{}", groups.join(";"))
+    }
+}
@@ -0,0 +1,103 @@
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+/// Library folders of the self-contained wine build (relocatable or extracted from AppImage)
+///
+/// Such builds expect `<root>/bin/wine` binary and libraries stored in `<root>/lib`, `<root>/lib64`
+/// or `<root>/lib32` folders, which are not known to the system's dynamic loader
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PortableRuntime {
+    /// Folders with shared libraries, specify `LD_LIBRARY_PATH` variable
+    pub lib_paths: Vec<PathBuf>,
+
+    /// Folders with wine dlls, specify `WINEDLLPATH` variable
+    pub dll_paths: Vec<PathBuf>,
+
+    /// Folders with gstreamer plugins, specify `GST_PLUGIN_SYSTEM_PATH_1_0` variable
+    pub gst_paths: Vec<PathBuf>
+}
+
+/// Join paths, appending current value of the variable to the end
+fn join_paths(paths: &[PathBuf], env: &str) -> OsString {
+    let mut value = OsString::new();
+
+    let current = std::env::var_os(env)
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from);
+
+    for path in paths.iter().chain(current.iter()) {
+        if !value.is_empty() {
+            value.push(":");
+        }
+
+        value.push(path);
+    }
+
+    value
+}
+
+impl PortableRuntime {
+    /// Find library folders of the build from path to its wine binary
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// let runtime = PortableRuntime::from_binary("/path/to/wine-build/bin/wine");
+    ///
+    /// println!("Libraries: {:?}", runtime.lib_paths);
+    /// ```
+    pub fn from_binary<T: AsRef<Path>>(binary: T) -> Self {
+        let mut runtime = Self::default();
+
+        let Some(root) = binary.as_ref().parent().and_then(Path::parent) else {
+            return runtime;
+        };
+
+        for lib in ["lib64", "lib", "lib32"] {
+            let lib = root.join(lib);
+
+            if !lib.is_dir() {
+                continue;
+            }
+
+            runtime.lib_paths.push(lib.clone());
+
+            for nested in ["x86_64-linux-gnu", "i386-linux-gnu"] {
+                if lib.join(nested).is_dir() {
+                    runtime.lib_paths.push(lib.join(nested));
+                }
+            }
+
+            if lib.join("wine").is_dir() {
+                runtime.dll_paths.push(lib.join("wine"));
+            }
+
+            if lib.join("gstreamer-1.0").is_dir() {
+                runtime.gst_paths.push(lib.join("gstreamer-1.0"));
+            }
+        }
+
+        runtime
+    }
+
+    /// Get environment variables making the build use its own libraries
+    ///
+    /// Current values of the variables are kept after the build's own folders
+    pub fn get_envs(&self) -> Vec<(&'static str, OsString)> {
+        let mut envs = Vec::new();
+
+        if !self.lib_paths.is_empty() {
+            envs.push(("LD_LIBRARY_PATH", join_paths(&self.lib_paths, "LD_LIBRARY_PATH")));
+        }
+
+        if !self.dll_paths.is_empty() {
+            envs.push(("WINEDLLPATH", join_paths(&self.dll_paths, "WINEDLLPATH")));
+        }
+
+        if !self.gst_paths.is_empty() {
+            envs.push(("GST_PLUGIN_SYSTEM_PATH_1_0", join_paths(&self.gst_paths, "GST_PLUGIN_SYSTEM_PATH_1_0")));
+        }
+
+        envs
+    }
+}
@@ -0,0 +1,158 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use super::*;
+
+/// vkBasalt post processing layer configuration (`vkBasalt.conf`)
+///
+/// ```
+/// use wincompatlib::prelude::*;
+///
+/// let config = VkBasalt::default()
+///     .with_effect("cas")
+///     .with_option("casSharpness", "0.6");
+///
+/// assert!(config.to_config().contains("effects = cas\n"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VkBasalt {
+    /// Names of the effects applied in the given order, e.g. `cas` or `smaa`
+    pub effects: Vec<String>,
+
+    /// Key toggling effects, `Home` by default
+    pub toggle_key: Option<String>,
+
+    pub enable_on_launch: bool,
+
+    /// Folders with reshade textures and shaders headers
+    pub reshade_texture_path: Option<PathBuf>,
+    pub reshade_include_path: Option<PathBuf>,
+
+    /// Effects settings and paths to the reshade effects, e.g. `smaa = /path/to/SMAA.fx`
+    pub options: BTreeMap<String, String>
+}
+
+impl Default for VkBasalt {
+    fn default() -> Self {
+        Self {
+            effects: Vec::new(),
+            toggle_key: None,
+            enable_on_launch: true,
+            reshade_texture_path: None,
+            reshade_include_path: None,
+            options: BTreeMap::new()
+        }
+    }
+}
+
+impl VkBasalt {
+    pub fn with_effect<T: Into<String>>(mut self, effect: T) -> Self {
+        self.effects.push(effect.into());
+
+        self
+    }
+
+    pub fn with_option<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
+        self.options.insert(key.into(), value.into());
+
+        self
+    }
+
+    /// Get content of the config file
+    pub fn to_config(&self) -> String {
+        let mut config = format!("effects = {}\n", self.effects.join(":"));
+
+        if let Some(key) = &self.toggle_key {
+            config += &format!("toggleKey = {key}\n");
+        }
+
+        config += &format!("enableOnLaunch = {}\n", if self.enable_on_launch { "True" } else { "False" });
+
+        if let Some(path) = &self.reshade_texture_path {
+            config += &format!("reshadeTexturePath = {}\n", path.to_string_lossy());
+        }
+
+        if let Some(path) = &self.reshade_include_path {
+            config += &format!("reshadeIncludePath = {}\n", path.to_string_lossy());
+        }
+
+        for (key, value) in &self.options {
+            config += &format!("{key} = {value}\n");
+        }
+
+        config
+    }
+
+    pub fn save<T: AsRef<Path>>(&self, path: T) -> Result<()> {
+        Ok(std::fs::write(path, self.to_config())?)
+    }
+}
+
+/// Graphics API the game uses, which defines the name of the ReShade library
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReShadeApi {
+    D3d9,
+
+    /// DirectX 10, 11 and 12
+    Dxgi,
+
+    OpenGl
+}
+
+impl ReShadeApi {
+    /// Name of the library ReShade is installed as, without extension
+    pub fn dll_name(&self) -> &str {
+        match self {
+            Self::D3d9   => "d3d9",
+            Self::Dxgi   => "dxgi",
+            Self::OpenGl => "opengl32"
+        }
+    }
+}
+
+/// Name of the `DllMod` ReShade is installed as by `Wine::install_reshade`
+pub const RESHADE_MOD: &str = "reshade";
+
+impl Wine {
+    /// Install ReShade next to the game executable
+    ///
+    /// ReShade library (`ReShade64.dll` or `ReShade32.dll`, extracted from its installer)
+    /// is copied as the API library of the game, and native override is set for this executable only.
+    /// It's installed as the `RESHADE_MOD` dll mod, so installed files are recorded in the prefix's `PrefixConfig`
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// Wine::default()
+    ///     .with_prefix("/path/to/prefix")
+    ///     .install_reshade("/path/to/game/game.exe", "/path/to/ReShade64.dll", ReShadeApi::Dxgi)
+    ///     .expect("Failed to install ReShade");
+    /// ```
+    pub fn install_reshade<E: AsRef<Path>, R: AsRef<Path>>(&self, exe: E, reshade: R, api: ReShadeApi) -> Result<()> {
+        self.check_writable()?;
+
+        let exe = exe.as_ref();
+
+        let Some(folder) = exe.parent() else {
+            return Err(Error::new(ErrorKind::InvalidInput, format!("Invalid executable path: {exe:?}")));
+        };
+
+        let name = format!("{}.dll", api.dll_name());
+
+        if folder.join(&name).exists() {
+            return Err(Error::new(ErrorKind::AlreadyExists, format!("Game already has {:?} library", folder.join(&name))));
+        }
+
+        self.install_dll_mod(&DllMod::new(RESHADE_MOD).with_file(name, reshade.as_ref()), exe)?;
+
+        Ok(())
+    }
+
+    /// Remove ReShade installed by `install_reshade`
+    ///
+    /// Only the files recorded in the prefix's `PrefixConfig` are removed. Returns
+    /// `ErrorKind::NotFound` error if ReShade is not installed for this executable
+    pub fn uninstall_reshade<E: AsRef<Path>>(&self, exe: E) -> Result<()> {
+        self.uninstall_dll_mod(RESHADE_MOD, exe)
+    }
+}
@@ -0,0 +1,147 @@
+use std::path::Path;
+
+use super::*;
+
+use crate::pe::PeFile;
+use crate::prefix::PrefixConfig;
+
+/// Libraries which need components that wine doesn't provide: (library, `PrefixConfig` component)
+///
+/// Only components recorded by the library's installers can be listed here
+const REQUIRED_COMPONENTS: &[(&str, &str)] = &[
+    ("d3d12.dll", "vkd3d-proton")
+];
+
+/// Problem which will likely make the game fail to start
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PreflightIssue {
+    /// 64 bit executable can't run in the win32 prefix
+    ArchMismatch,
+
+    /// Wine build can't run 32 bit executables
+    No32bitSupport,
+
+    /// Executable imports library which needs the component not installed in the prefix
+    MissingComponent {
+        dll: String,
+        component: String
+    },
+
+    /// .NET executable needs Mono or .NET runtime installed in the prefix
+    MissingDotnet,
+
+    /// Imported library is neither in the game folder nor in the prefix
+    MissingDll(String),
+
+    /// Executable's path will be broken by the non-UTF-8 locale
+    Locale(LocaleIssue)
+}
+
+impl std::fmt::Display for PreflightIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ArchMismatch => write!(f, "64 bit executable can't run in the win32 prefix"),
+            Self::No32bitSupport => write!(f, "Wine build doesn't support 32 bit executables"),
+            Self::MissingComponent { dll, component } => write!(f, "{dll} is imported but {component} is not installed"),
+            Self::MissingDotnet => write!(f, ".NET executable needs Mono or .NET runtime installed"),
+            Self::MissingDll(dll) => write!(f, "{dll} library is not found"),
+            Self::Locale(issue) => write!(f, "{issue}")
+        }
+    }
+}
+
+impl Wine {
+    /// Check that the prefix has everything the executable needs
+    ///
+    /// Executable's architecture and imported libraries are compared with the prefix
+    /// and components installed in its `PrefixConfig`. Only direct imports are checked
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// let issues = Wine::default()
+    ///     .with_prefix("/path/to/prefix")
+    ///     .preflight("/path/to/game.exe")
+    ///     .expect("Failed to check game");
+    ///
+    /// for issue in issues {
+    ///     eprintln!("{issue}");
+    /// }
+    /// ```
+    pub fn preflight<T: AsRef<Path>>(&self, exe: T) -> Result<Vec<PreflightIssue>> {
+        let exe = exe.as_ref();
+        let prefix = self.prefix_path();
+
+        let pe = PeFile::open(exe)?;
+
+        let prefix_arch = WineArch::from_prefix(&prefix)?
+            .or(self.arch)
+            .unwrap_or(WineArch::Win64);
+
+        let mut issues = Vec::new();
+
+        if let Some(issue) = self.check_locale(exe) {
+            issues.push(PreflightIssue::Locale(issue));
+        }
+
+        let system = match (pe.is_64bit, prefix_arch) {
+            (true, WineArch::Win32) => {
+                issues.push(PreflightIssue::ArchMismatch);
+
+                return Ok(issues);
+            }
+
+            (false, WineArch::Win64) => {
+                if self.has_32bit_support() == Some(false) {
+                    issues.push(PreflightIssue::No32bitSupport);
+                }
+
+                "syswow64"
+            }
+
+            _ => "system32"
+        };
+
+        let config = PrefixConfig::load_or_default(&prefix)?;
+
+        let system = prefix.join("drive_c/windows").join(system);
+        let game_folder = exe.parent().unwrap_or(Path::new("."));
+
+        for dll in pe.imports()? {
+            let name = dll.to_ascii_lowercase();
+
+            if name == "mscoree.dll" {
+                let has_dotnet = prefix.join("drive_c/windows/mono").exists()
+                    || prefix.join("drive_c/windows/Microsoft.NET").exists();
+
+                if !has_dotnet {
+                    issues.push(PreflightIssue::MissingDotnet);
+                }
+
+                continue;
+            }
+
+            if let Some((_, component)) = REQUIRED_COMPONENTS.iter().find(|(required, _)| *required == name) {
+                if !config.components.contains_key(*component) {
+                    issues.push(PreflightIssue::MissingComponent {
+                        dll: dll.clone(),
+                        component: component.to_string()
+                    });
+                }
+
+                continue;
+            }
+
+            // Resolved by wine through the api sets
+            if name.starts_with("api-ms-") || name.starts_with("ext-ms-") {
+                continue;
+            }
+
+            if !system.join(&name).exists() && !game_folder.join(&dll).exists() && !game_folder.join(&name).exists() {
+                issues.push(PreflightIssue::MissingDll(dll));
+            }
+        }
+
+        Ok(issues)
+    }
+}
@@ -0,0 +1,234 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::process::{Child, ExitStatus};
+use crate::error::Result;
+use crate::prefix::prefix_processes;
+
+/// Get ids of the process and all its descendants
+pub(crate) fn process_tree(root: u32) -> HashSet<u32> {
+    let mut children = HashMap::<u32, Vec<u32>>::new();
+
+    if let Ok(entries) = std::fs::read_dir("/proc") {
+        for entry in entries.flatten() {
+            let Some(pid) = entry.file_name().to_str().and_then(|pid| pid.parse::<u32>().ok()) else {
+                continue;
+            };
+
+            // Process name is wrapped in parentheses and can contain spaces
+            let Ok(stat) = std::fs::read_to_string(entry.path().join("stat")) else {
+                continue;
+            };
+
+            let ppid = stat.rsplit_once(')')
+                .and_then(|(_, rest)| rest.split_whitespace().nth(1))
+                .and_then(|ppid| ppid.parse::<u32>().ok());
+
+            if let Some(ppid) = ppid {
+                children.entry(ppid).or_default().push(pid);
+            }
+        }
+    }
+
+    let mut tree = HashSet::from([root]);
+    let mut queue = vec![root];
+
+    while let Some(pid) = queue.pop() {
+        for child in children.get(&pid).into_iter().flatten() {
+            if tree.insert(*child) {
+                queue.push(*child);
+            }
+        }
+    }
+
+    tree
+}
+
+/// Handle of the process started by the library
+#[derive(Debug)]
+pub struct WineProcess {
+    child: Child,
+    log_file: Option<PathBuf>,
+    prefix: Option<PathBuf>
+}
+
+impl WineProcess {
+    pub fn new(child: Child, log_file: Option<PathBuf>) -> Self {
+        Self {
+            child,
+            log_file,
+            prefix: None
+        }
+    }
+
+    /// Specify wine prefix the process runs in
+    ///
+    /// Processes of this prefix are suspended and killed together with the process tree,
+    /// so the ones which were started by wine separately or got reparented are found as well
+    pub fn with_prefix<T: Into<PathBuf>>(self, prefix: T) -> Self {
+        Self {
+            prefix: Some(prefix.into()),
+            ..self
+        }
+    }
+
+    /// Get process id
+    pub fn id(&self) -> u32 {
+        self.child.id()
+    }
+
+    /// Get path to the wine prefix the process runs in
+    pub fn prefix(&self) -> Option<&Path> {
+        self.prefix.as_deref()
+    }
+
+    /// Get path to the file process output is redirected to
+    pub fn log_file(&self) -> Option<&Path> {
+        self.log_file.as_deref()
+    }
+
+    /// Check if process has finished without blocking
+    pub fn try_wait(&mut self) -> Result<Option<ExitStatus>> {
+        Ok(self.child.try_wait()?)
+    }
+
+    /// Wait until process finishes
+    pub fn wait(&mut self) -> Result<ExitStatus> {
+        Ok(self.child.wait()?)
+    }
+
+    /// Kill the process
+    ///
+    /// Processes it started keep running, use `kill_tree` to terminate them as well
+    pub fn kill(&mut self) -> Result<()> {
+        Ok(self.child.kill()?)
+    }
+
+    /// Kill the process and all its descendants, e.g. game processes forked by its launcher
+    ///
+    /// Processes are stopped first so they can't fork while the tree is collected.
+    /// Descendants which were already reparented after their parent exited can only be found
+    /// by their `WINEPREFIX` variable, so all the processes of the prefix specified by `with_prefix`
+    /// are killed as well (`run_detached` specifies it automatically)
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// let mut process = Wine::default()
+    ///     .run_detached(["/path/to/launcher.exe"], "/path/to/launcher.log")
+    ///     .expect("Failed to run launcher");
+    ///
+    /// process.kill_tree().expect("Failed to kill launcher");
+    /// ```
+    pub fn kill_tree(&mut self) -> Result<()> {
+        if self.child.try_wait()?.is_some() {
+            return Ok(());
+        }
+
+        for pid in self.stop_tree()? {
+            signal(pid, libc::SIGKILL)?;
+        }
+
+        self.child.wait()?;
+
+        Ok(())
+    }
+
+    /// Get ids of the process, its descendants and processes of its prefix
+    fn processes(&self) -> HashSet<u32> {
+        let mut processes = process_tree(self.id());
+
+        if let Some(prefix) = &self.prefix {
+            processes.extend(prefix_processes(prefix));
+        }
+
+        processes.remove(&std::process::id());
+
+        processes
+    }
+
+    /// Stop the process, its descendants and processes of its prefix until their list doesn't change
+    fn stop_tree(&self) -> Result<HashSet<u32>> {
+        let mut stopped = HashSet::new();
+
+        loop {
+            let found = self.processes().into_iter()
+                .filter(|pid| !stopped.contains(pid))
+                .collect::<Vec<_>>();
+
+            if found.is_empty() {
+                return Ok(stopped);
+            }
+
+            for pid in found {
+                signal(pid, libc::SIGSTOP)?;
+
+                stopped.insert(pid);
+            }
+        }
+    }
+
+    /// Pause the process and all its descendants (`SIGSTOP`), e.g. to free resources for background downloads
+    ///
+    /// Processes of the prefix specified by `with_prefix` are paused as well.
+    /// Note that games can lose connection to their servers or audio devices while suspended
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// let mut game = Wine::default()
+    ///     .run_detached(["/path/to/game.exe"], "/path/to/game.log")
+    ///     .expect("Failed to run game");
+    ///
+    /// game.suspend().expect("Failed to pause game");
+    ///
+    /// // Download updates...
+    ///
+    /// game.resume().expect("Failed to resume game");
+    /// ```
+    pub fn suspend(&mut self) -> Result<()> {
+        if self.child.try_wait()?.is_some() {
+            return Ok(());
+        }
+
+        self.stop_tree()?;
+
+        Ok(())
+    }
+
+    /// Continue the process and all its descendants paused by `suspend` (`SIGCONT`)
+    pub fn resume(&mut self) -> Result<()> {
+        if self.child.try_wait()?.is_some() {
+            return Ok(());
+        }
+
+        for pid in self.processes() {
+            signal(pid, libc::SIGCONT)?;
+        }
+
+        Ok(())
+    }
+
+    /// Get inner `Child` struct
+    pub fn into_child(self) -> Child {
+        self.child
+    }
+}
+
+/// Send signal to the process, ignoring already finished ones
+fn signal(pid: u32, signal: libc::c_int) -> Result<()> {
+    if unsafe { libc::kill(pid as libc::pid_t, signal) } == -1 {
+        let err = std::io::Error::last_os_error();
+
+        if err.raw_os_error() != Some(libc::ESRCH) {
+            return Err(err.into());
+        }
+    }
+
+    Ok(())
+}
+
+impl From<Child> for WineProcess {
+    fn from(child: Child) -> Self {
+        Self::new(child, None)
+    }
+}
@@ -0,0 +1,139 @@
+use std::fs::File;
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+use std::os::unix::process::CommandExt;
+use std::process::{Child, ExitStatus};
+
+use super::*;
+
+/// Process running under a pseudo-terminal
+///
+/// Everything the process writes (including colors and cursor movements)
+/// can be read from the master side of the terminal, and input can be written to it
+#[derive(Debug)]
+pub struct PtyProcess {
+    child: Child,
+    master: File
+}
+
+impl PtyProcess {
+    /// Get process id
+    pub fn id(&self) -> u32 {
+        self.child.id()
+    }
+
+    /// Get master side of the pseudo-terminal
+    ///
+    /// Reading from it returns process output, writing to it sends input to the process
+    pub fn master(&self) -> &File {
+        &self.master
+    }
+
+    /// Change terminal size
+    pub fn resize(&self, columns: u16, rows: u16) -> Result<()> {
+        let size = libc::winsize {
+            ws_row: rows,
+            ws_col: columns,
+            ws_xpixel: 0,
+            ws_ypixel: 0
+        };
+
+        if unsafe { libc::ioctl(self.master.as_raw_fd(), libc::TIOCSWINSZ, &size) } == -1 {
+            return Err(Error::last_os_error());
+        }
+
+        Ok(())
+    }
+
+    /// Check if process has finished without blocking
+    pub fn try_wait(&mut self) -> Result<Option<ExitStatus>> {
+        Ok(self.child.try_wait()?)
+    }
+
+    /// Wait until process finishes
+    pub fn wait(&mut self) -> Result<ExitStatus> {
+        Ok(self.child.wait()?)
+    }
+
+    /// Kill the process
+    pub fn kill(&mut self) -> Result<()> {
+        Ok(self.child.kill()?)
+    }
+}
+
+pub trait WinePtyExt {
+    fn run_pty<T, S>(&self, args: T) -> Result<PtyProcess>
+    where
+        T: IntoIterator<Item = S>,
+        S: AsRef<OsStr>;
+}
+
+impl WinePtyExt for Wine {
+    /// Execute some command with args using wine under a pseudo-terminal
+    ///
+    /// Useful to display interactive and colored output of console programs in terminal UIs
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// use std::io::Read;
+    ///
+    /// let process = Wine::default().run_pty(["cmd", "/c", "dir"]).unwrap();
+    /// let mut output = String::new();
+    ///
+    /// // Returns an error when the process closes its terminal
+    /// let _ = process.master().read_to_string(&mut output);
+    ///
+    /// println!("{output}");
+    /// ```
+    fn run_pty<T, S>(&self, args: T) -> Result<PtyProcess>
+    where
+        T: IntoIterator<Item = S>,
+        S: AsRef<OsStr>
+    {
+        let mut master = 0;
+        let mut slave = 0;
+
+        if unsafe { libc::openpty(&mut master, &mut slave, std::ptr::null_mut(), std::ptr::null(), std::ptr::null()) } == -1 {
+            return Err(Error::last_os_error());
+        }
+
+        let master = unsafe { OwnedFd::from_raw_fd(master) };
+        let slave = unsafe { OwnedFd::from_raw_fd(slave) };
+
+        // Don't leak master side to the spawned process
+        if unsafe { libc::fcntl(master.as_raw_fd(), libc::F_SETFD, libc::FD_CLOEXEC) } == -1 {
+            return Err(Error::last_os_error());
+        }
+
+        let mut command = self.command(args);
+
+        command
+            .stdin(slave.try_clone()?)
+            .stdout(slave.try_clone()?)
+            .stderr(slave);
+
+        // Make terminal a controlling one for the new session
+        unsafe {
+            command.pre_exec(|| {
+                if libc::setsid() == -1 || libc::ioctl(0, libc::TIOCSCTTY, 0) == -1 {
+                    return Err(std::io::Error::last_os_error());
+                }
+
+                Ok(())
+            });
+        }
+
+        // Command keeps slave side opened until it's dropped
+        let child = self.track_server(&self.prefix_path(), || {
+            self.events.spawn(&mut command)
+                .map_err(|err| Error::from_spawn(command.get_program(), err))
+        })?;
+
+        drop(command);
+
+        Ok(PtyProcess {
+            child,
+            master: File::from(master)
+        })
+    }
+}
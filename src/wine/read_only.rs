@@ -0,0 +1,68 @@
+use crate::prefix::PrefixSnapshot;
+
+use super::*;
+
+impl Wine {
+    /// Return `Error::ReadOnlyPrefix` if read-only mode is enabled
+    ///
+    /// Checked by the library methods which write to the prefix (registry changes,
+    /// prefix updates, DXVK, mods and fonts installation) before doing anything
+    pub fn check_writable(&self) -> Result<()> {
+        if self.read_only {
+            return Err(Error::ReadOnlyPrefix(self.prefix_path()));
+        }
+
+        Ok(())
+    }
+
+    /// Run operation in read-only mode and verify that it didn't modify the prefix
+    ///
+    /// Library methods which would write to the prefix fail immediately, while
+    /// changes made by the launched programs themselves are reported as `Error::PrefixModified`
+    /// once the operation is finished. Wineserver saves the registry when it exits,
+    /// so the operation should wait for it to catch all the changes
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// use std::time::Duration;
+    ///
+    /// let wine = Wine::default().with_prefix("/path/to/golden/prefix");
+    ///
+    /// let result = wine.verify_read_only(|wine| {
+    ///     wine.run("/path/to/game.exe")?.wait()?;
+    ///     wine.wait_for_server_exit(Duration::from_secs(30))?;
+    ///
+    ///     Ok(())
+    /// });
+    ///
+    /// if let Err(wincompatlib::Error::PrefixModified { diff, .. }) = result {
+    ///     eprintln!("Golden prefix was modified: {:?}", diff.changed);
+    /// }
+    /// ```
+    pub fn verify_read_only<T, F>(&self, operation: F) -> Result<T>
+    where
+        F: FnOnce(&Wine) -> Result<T>
+    {
+        let prefix = self.prefix_path();
+
+        if !prefix.exists() {
+            return Err(Error::PrefixMissing(prefix));
+        }
+
+        let snapshot = PrefixSnapshot::capture(&prefix)?;
+
+        let output = operation(&self.clone().with_read_only(true))?;
+
+        let diff = snapshot.diff()?;
+
+        if !diff.is_empty() {
+            return Err(Error::PrefixModified {
+                prefix,
+                diff
+            });
+        }
+
+        Ok(output)
+    }
+}
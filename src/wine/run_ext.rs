@@ -1,8 +1,26 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::{Child, Command};
+use std::os::unix::process::CommandExt;
 
 use super::*;
 
+/// How the standard input of the executed command is handled
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum WineStdin {
+    /// Create pipe which can be accessed as `Child::stdin`
+    #[default]
+    Piped,
+
+    /// Keep stdin of the current process connected
+    Inherit,
+
+    /// Don't provide any input
+    Null,
+
+    /// Feed given bytes and close stdin afterwards
+    Bytes(Vec<u8>)
+}
+
 pub trait WineRunExt {
     fn run<T: AsRef<OsStr>>(&self, binary: T) -> Result<Child>;
 
@@ -11,15 +29,178 @@ pub trait WineRunExt {
         T: IntoIterator<Item = S>,
         S: AsRef<OsStr>;
 
+    fn run_exe<E, A>(&self, exe: E, args: &[A]) -> Result<Child>
+    where
+        E: AsRef<OsStr>,
+        A: AsRef<OsStr>;
+
+    fn to_command<E, A>(&self, program: E, args: &[A]) -> Result<Command>
+    where
+        E: AsRef<OsStr>,
+        A: AsRef<OsStr>;
+
     fn run_args_with_env<T, K, S>(&self, args: T, envs: K) -> Result<Child>
     where
         T: IntoIterator<Item = S>,
         K: IntoIterator<Item = (S, S)>,
         S: AsRef<OsStr>;
 
+    fn run_with_scoped_env<E, K, V, T>(&self, envs: E, binary: T) -> Result<Child>
+    where
+        E: IntoIterator<Item = (K, V)>,
+        K: AsRef<OsStr>,
+        V: AsRef<OsStr>,
+        T: AsRef<OsStr>;
+
+    fn run_args_with_stdin<T, S>(&self, args: T, stdin: WineStdin) -> Result<Child>
+    where
+        T: IntoIterator<Item = S>,
+        S: AsRef<OsStr>;
+
+    fn run_detached<T, S, L>(&self, args: T, log_file: L) -> Result<WineProcess>
+    where
+        T: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+        L: Into<PathBuf>;
+
+    fn run_script<T: AsRef<Path>>(&self, script: T) -> Result<Child>;
+    fn install_msi<T: AsRef<Path>>(&self, package: T) -> Result<Child>;
+
+    fn open_in_prefix<T: AsRef<OsStr>>(&self, path_or_url: T) -> Result<Child>;
+
     fn winepath(&self, path: &str) -> Result<PathBuf>;
 }
 
+impl Wine {
+    /// Read log file (e.g. written by `run_detached`) with the secrets of the struct's events redacted
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// let wine = Wine::default().with_secret("hunter2");
+    ///
+    /// wine.run_detached(["/your/game.exe", "--token=hunter2"], "/path/to/game.log")
+    ///     .expect("Failed to run the game");
+    ///
+    /// println!("{}", wine.read_log("/path/to/game.log").expect("Failed to read log"));
+    /// ```
+    pub fn read_log<T: AsRef<Path>>(&self, log_file: T) -> Result<String> {
+        let log = std::fs::read(log_file)?;
+
+        Ok(String::from_utf8_lossy(&self.events.redact_bytes(&log)).to_string())
+    }
+
+    /// Run wine with given arguments and wait for its output
+    ///
+    /// Failed command is recorded in the events history, see `SupportBundle`
+    pub(crate) fn run_args_output<T, S>(&self, args: T) -> Result<Output>
+    where
+        T: IntoIterator<Item = S>,
+        S: AsRef<OsStr>
+    {
+        let mut command = self.command(args);
+
+        self.track_server(&self.prefix_path(), || {
+            self.events.output(&mut command)
+                .map_err(|err| Error::from_spawn(command.get_program(), err))
+        })
+    }
+
+    /// Create command running wine with given arguments and environment of current struct
+    pub(crate) fn command<T, S>(&self, args: T) -> Command
+    where
+        T: IntoIterator<Item = S>,
+        S: AsRef<OsStr>
+    {
+        // Fake time is applied to wine only, so it goes after all the other wrappers
+        let faketime = self.faketime.as_ref()
+            .map(|faketime| Wrapper::new("faketime").with_args([faketime]));
+
+        let wrappers = self.wrappers.iter()
+            .chain(faketime.as_ref())
+            .collect::<Vec<_>>();
+
+        let program = match wrappers.first() {
+            Some(wrapper) => wrapper.program.as_os_str(),
+            None => self.binary.as_os_str()
+        };
+
+        // Network namespace is created for wine itself, so the virtual display
+        // and systemd scope stay outside of it
+        let network = self.network.wrapper();
+
+        let first = match network {
+            Some((wrapper, _)) => OsStr::new(wrapper),
+            None => program
+        };
+
+        let mut command = match &self.scope {
+            Some(scope) => {
+                let mut command = Command::new("systemd-run");
+
+                command.args(scope.args());
+
+                // Virtual display is placed into the scope as well
+                if let Some(headless) = self.headless {
+                    let (wrapper, args) = headless.wrapper();
+
+                    command.arg(wrapper).args(args);
+                }
+
+                command.arg(first);
+
+                command
+            }
+
+            None => self.headless_command(first)
+        };
+
+        if let Some((_, args)) = network {
+            command.args(args).arg(program);
+        }
+
+        for (i, wrapper) in wrappers.iter().enumerate() {
+            if i > 0 {
+                command.arg(&wrapper.program);
+            }
+
+            // Gamescope needs its own flag to pass HDR output through
+            if self.hdr && wrapper.is("gamescope") && !wrapper.args.iter().any(|arg| arg == "--hdr-enabled") {
+                command.arg("--hdr-enabled");
+            }
+
+            command.args(&wrapper.args);
+        }
+
+        if !wrappers.is_empty() {
+            command.arg(&self.binary);
+        }
+
+        command
+            .args(args)
+            .envs(self.get_os_envs());
+
+        if !self.limits.is_empty() {
+            let limits = self.limits;
+
+            unsafe {
+                command.pre_exec(move || limits.apply());
+            }
+        }
+
+        if let Some(score) = self.oom_score_adj {
+            // Format the value beforehand as pre_exec must not allocate
+            let score = score.clamp(-1000, 1000).to_string().into_bytes();
+
+            unsafe {
+                command.pre_exec(move || super::limits::apply_oom_score_adj(&score));
+            }
+        }
+
+        command
+    }
+}
+
 impl WineRunExt for Wine {
     /// Execute some command using wine
     /// 
@@ -47,6 +228,63 @@ impl WineRunExt for Wine {
         self.run_args_with_env(args, [])
     }
 
+    /// Execute windows executable with given arguments
+    /// 
+    /// Each argument is passed to wine as a separate item and is never split,
+    /// so paths with spaces and unicode characters don't need any quoting.
+    /// Wine builds the windows command line from them quoting arguments the way
+    /// `CommandLineToArgvW` expects (same as `windows_quote` does)
+    /// 
+    /// Don't join the executable and its arguments in a single string:
+    /// it will be treated as the executable's path
+    /// 
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    /// 
+    /// let process = Wine::default().run_exe("/path/to/My Games/ゲーム.exe", &["--profile", "Player One"]);
+    /// ```
+    fn run_exe<E, A>(&self, exe: E, args: &[A]) -> Result<Child>
+    where
+        E: AsRef<OsStr>,
+        A: AsRef<OsStr>
+    {
+        self.run_args(std::iter::once(exe.as_ref()).chain(args.iter().map(AsRef::as_ref)))
+    }
+
+    /// Build command running the program with wine without spawning it
+    /// 
+    /// Command has all the wrappers, environment variables and limits of the struct
+    /// and inherits stdio, so the caller can configure it before spawning.
+    /// Prefix's wineserver is tracked the same way as with other run methods,
+    /// but event sinks are not notified about this command
+    /// 
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    /// 
+    /// use std::process::Stdio;
+    /// use std::os::unix::process::CommandExt;
+    /// 
+    /// let mut command = Wine::default()
+    ///     .to_command("/path/to/game.exe", &["--windowed"])
+    ///     .expect("Failed to build command");
+    /// 
+    /// let child = command
+    ///     .stdout(Stdio::null())
+    ///     .process_group(0)
+    ///     .spawn()
+    ///     .expect("Failed to run game");
+    /// ```
+    fn to_command<E, A>(&self, program: E, args: &[A]) -> Result<Command>
+    where
+        E: AsRef<OsStr>,
+        A: AsRef<OsStr>
+    {
+        // Command is started by the caller, so its wineserver can't be tracked
+        self.check_server(&self.prefix_path())?;
+
+        Ok(self.command(std::iter::once(program.as_ref()).chain(args.iter().map(AsRef::as_ref))))
+    }
+
     /// Execute some command with args and environment variables using wine
     /// 
     /// ```no_run
@@ -62,37 +300,251 @@ impl WineRunExt for Wine {
         K: IntoIterator<Item = (S, S)>,
         S: AsRef<OsStr>
     {
-        let mut command = Command::new(&self.binary);
+        let mut command = self.command(args);
 
         command
-            .args(args)
+            .envs(envs)
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped());
 
-        if let Some(prefix) = &self.prefix {
-            command.env("WINEPREFIX", prefix);
+        self.track_server(&self.prefix_path(), || {
+            self.events.spawn(&mut command)
+                .map_err(|err| Error::from_spawn(command.get_program(), err))
+        })
+    }
+
+    /// Execute binary with extra environment variables applied only to this run
+    /// 
+    /// Same as `run_args_with_env([binary], envs)`, but variables names and values can have different types.
+    /// Variables override the ones set by the struct, which itself stays untouched,
+    /// so one configured instance can be shared to run different games
+    /// 
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    /// 
+    /// let wine = Wine::default();
+    /// 
+    /// let first = wine.run_with_scoped_env([("DXVK_HUD", "fps")], "/path/to/first.exe");
+    /// let second = wine.run_with_scoped_env([("WINEDEBUG", "+seh")], "/path/to/second.exe");
+    /// ```
+    fn run_with_scoped_env<E, K, V, T>(&self, envs: E, binary: T) -> Result<Child>
+    where
+        E: IntoIterator<Item = (K, V)>,
+        K: AsRef<OsStr>,
+        V: AsRef<OsStr>,
+        T: AsRef<OsStr>
+    {
+        let envs = envs.into_iter()
+            .map(|(key, value)| (key.as_ref().to_os_string(), value.as_ref().to_os_string()));
+
+        self.run_args_with_env([binary.as_ref().to_os_string()], envs)
+    }
+
+    /// Execute some command with args using wine, with specified stdin handling
+    /// 
+    /// Useful to automate console installers and patchers which prompt for input
+    /// 
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    /// 
+    /// // Answer "y" to the installer's question
+    /// let process = Wine::default().run_args_with_stdin(["/your/installer.exe"], WineStdin::Bytes(b"y\n".to_vec()));
+    /// ```
+    fn run_args_with_stdin<T, S>(&self, args: T, stdin: WineStdin) -> Result<Child>
+    where
+        T: IntoIterator<Item = S>,
+        S: AsRef<OsStr>
+    {
+        let mut command = self.command(args);
+
+        command
+            .stdin(match &stdin {
+                WineStdin::Piped |
+                WineStdin::Bytes(_) => Stdio::piped(),
+                WineStdin::Inherit  => Stdio::inherit(),
+                WineStdin::Null     => Stdio::null()
+            })
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+
+        let mut child = self.track_server(&self.prefix_path(), || {
+            self.events.spawn(&mut command)
+                .map_err(|err| Error::from_spawn(command.get_program(), err))
+        })?;
+
+        if let WineStdin::Bytes(bytes) = stdin {
+            if let Some(mut child_stdin) = child.stdin.take() {
+                // Write in a separate thread so we don't deadlock
+                // if the process fills its stdout before reading the whole input
+                std::thread::spawn(move || {
+                    use std::io::Write;
+
+                    // Process can exit without reading everything, that's not our problem
+                    let _ = child_stdin.write_all(&bytes);
+                });
+            }
+        }
+
+        Ok(child)
+    }
+
+    /// Execute some command with args in its own session, redirecting its output to the log file
+    /// 
+    /// Returns immediately, and the process keeps running even if the caller exits
+    /// 
+    /// Output is written to the log as is, so it can contain secrets registered
+    /// in the struct's events. The log is created readable by the current user only,
+    /// use `Wine::read_log` to get its redacted content
+    /// 
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    /// 
+    /// let process = Wine::default()
+    ///     .run_detached(["/your/game.exe"], "/path/to/game.log")
+    ///     .expect("Failed to run the game");
+    /// 
+    /// println!("Game started with pid {}", process.id());
+    /// ```
+    fn run_detached<T, S, L>(&self, args: T, log_file: L) -> Result<WineProcess>
+    where
+        T: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+        L: Into<PathBuf>
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+
+        let log_file = log_file.into();
+
+        let log = std::fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .mode(0o600)
+            .open(&log_file)?;
+
+        let mut command = self.command(args);
+
+        // Output goes straight to the file so the process doesn't depend on the caller
+        command.stdin(Stdio::null())
+            .stdout(log.try_clone()?)
+            .stderr(log);
+
+        // Start new session so the process is not bound to the caller's terminal and process group
+        unsafe {
+            command.pre_exec(|| {
+                if libc::setsid() == -1 {
+                    return Err(std::io::Error::last_os_error());
+                }
+
+                Ok(())
+            });
         }
 
-        if let Some(arch) = self.arch {
-            command.env("WINEARCH", arch.to_str());
+        let child = self.track_server(&self.prefix_path(), || {
+            self.events.spawn(&mut command)
+                .map_err(|err| Error::from_spawn(command.get_program(), err))
+        })?;
+
+        self.events.record_log_file(log_file.clone());
+
+        Ok(WineProcess::new(child, Some(log_file)).with_prefix(self.prefix_path()))
+    }
+
+    /// Run batch script (`.bat` or `.cmd`) through `cmd /c` from the script's folder
+    /// 
+    /// Stdin is closed so `pause` commands don't wait forever.
+    /// Files with other extensions are executed as usual
+    /// 
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    /// 
+    /// let output = Wine::default()
+    ///     .with_prefix("/path/to/prefix")
+    ///     .run_script("/path/to/game/install.bat")
+    ///     .expect("Failed to run script")
+    ///     .wait_with_output()
+    ///     .expect("Failed to wait for script");
+    /// ```
+    fn run_script<T: AsRef<Path>>(&self, script: T) -> Result<Child> {
+        let script = script.as_ref();
+
+        let is_batch = script.extension()
+            .map(|ext| ext.eq_ignore_ascii_case("bat") || ext.eq_ignore_ascii_case("cmd"))
+            .unwrap_or(false);
+
+        if !is_batch {
+            return self.run(script);
         }
 
-        if let Some(server) = &self.wineserver {
-            command.env("WINESERVER", server);
+        let (Some(folder), Some(name)) = (script.parent(), script.file_name()) else {
+            return Err(Error::new(ErrorKind::InvalidInput, format!("Invalid script path: {:?}", script)));
+        };
+
+        // Wine maps current unix folder to the windows working directory,
+        // so cmd finds the script by its name and relative paths inside of it work
+        let mut command = self.command([OsStr::new("cmd"), OsStr::new("/c"), name]);
+
+        if !folder.as_os_str().is_empty() {
+            command.current_dir(folder);
         }
 
-        match &self.wineloader {
-            WineLoader::Default => (),
-            WineLoader::Current => {
-                command.env("WINELOADER", &self.binary);
-            },
-            WineLoader::Custom(path) => {
-                command.env("WINELOADER", path);
-            }
+        command
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+
+        self.track_server(&self.prefix_path(), || {
+            self.events.spawn(&mut command)
+                .map_err(|err| Error::from_spawn(command.get_program(), err))
+        })
+    }
+
+    /// Install `.msi` package using `msiexec /i`, quietly if the struct is `unattended`
+    /// 
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    /// 
+    /// Wine::default()
+    ///     .with_prefix("/path/to/prefix")
+    ///     .with_unattended(true)
+    ///     .install_msi("/path/to/vcredist.msi")
+    ///     .expect("Failed to run installer")
+    ///     .wait()
+    ///     .expect("Failed to wait for installer");
+    /// ```
+    fn install_msi<T: AsRef<Path>>(&self, package: T) -> Result<Child> {
+        let mut args = vec![OsStr::new("msiexec"), OsStr::new("/i"), package.as_ref().as_os_str()];
+
+        if self.unattended {
+            args.push(OsStr::new("/qn"));
         }
 
-        command.envs(envs).spawn()
+        self.run_args(args)
+    }
+
+    /// Open document, URL or any other file with the program associated with it inside of the wine prefix
+    /// 
+    /// Unix paths are passed to `start.exe /unix`, and URLs (anything containing `://`, or `mailto:` links)
+    /// are opened as is, which is useful for login flows of launchers
+    /// 
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    /// 
+    /// let wine = Wine::default().with_prefix("/path/to/prefix");
+    /// 
+    /// wine.open_in_prefix("/path/to/manual.pdf").expect("Failed to open manual");
+    /// wine.open_in_prefix("https://example.com/login").expect("Failed to open URL");
+    /// ```
+    fn open_in_prefix<T: AsRef<OsStr>>(&self, path_or_url: T) -> Result<Child> {
+        let path_or_url = path_or_url.as_ref();
+        let value = path_or_url.to_string_lossy();
+
+        if value.contains("://") || value.starts_with("mailto:") {
+            self.run_args([OsStr::new("start"), path_or_url])
+        } else {
+            self.run_args([OsStr::new("start"), OsStr::new("/unix"), path_or_url])
+        }
     }
 
     /// Get unix path to the windows folder in the wine prefix
@@ -103,7 +555,7 @@ impl WineRunExt for Wine {
     /// println!("System32 path: {:?}", Wine::default().winepath("C:\\windows\\system32"));
     /// ```
     fn winepath(&self, path: &str) -> Result<PathBuf> {
-        let output = self.run_args(["winepath", "-u", path])?.wait_with_output()?;
+        let output = self.run_args_output(["winepath", "-u", path])?;
 
         match output.status.success() {
             true => {
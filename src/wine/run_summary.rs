@@ -0,0 +1,100 @@
+use std::process::{Child, ExitStatus};
+use std::time::{Duration, Instant};
+
+use super::*;
+
+use crate::prefix::prefix_processes;
+
+use super::process::process_tree;
+
+/// Statistics of the finished game session
+///
+/// ```no_run
+/// use wincompatlib::prelude::*;
+///
+/// use std::time::Duration;
+///
+/// let wine = Wine::default().with_prefix("/path/to/prefix");
+///
+/// let mut game = wine.run("/path/to/game.exe").expect("Failed to run game");
+///
+/// let summary = RunSummary::collect(&wine, &mut game, Duration::from_secs(1))
+///     .expect("Failed to wait for game");
+///
+/// println!("Played for {:?}, used {} MiB of memory", summary.duration, summary.peak_rss / 1024 / 1024);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunSummary {
+    pub duration: Duration,
+
+    /// Peak resident memory of the process tree and other prefix processes, in bytes
+    pub peak_rss: u64,
+
+    pub status: ExitStatus,
+
+    /// Name of the GPU used by DXVK, see `RunSummary::with_dxvk_log`
+    pub dxvk_device: Option<String>
+}
+
+/// Get resident memory of the process in bytes
+fn process_rss(pid: u32) -> u64 {
+    std::fs::read_to_string(format!("/proc/{pid}/status"))
+        .ok()
+        .and_then(|status| {
+            status.lines()
+                .find_map(|line| line.strip_prefix("VmRSS:"))
+                .and_then(|rss| rss.trim().trim_end_matches("kB").trim().parse::<u64>().ok())
+        })
+        .map(|rss| rss * 1024)
+        .unwrap_or_default()
+}
+
+impl RunSummary {
+    /// Wait until the process finishes, sampling memory usage of its tree every `interval`
+    ///
+    /// Processes of the wine prefix are counted as well because wine can start them
+    /// out of the process tree
+    pub fn collect(wine: &Wine, child: &mut Child, interval: Duration) -> Result<Self> {
+        let started = Instant::now();
+        let prefix = wine.prefix_path();
+
+        let mut peak_rss = 0;
+
+        let status = loop {
+            let mut processes = process_tree(child.id());
+
+            processes.extend(prefix_processes(&prefix));
+
+            peak_rss = peak_rss.max(processes.into_iter().map(process_rss).sum());
+
+            if let Some(status) = child.try_wait()? {
+                break status;
+            }
+
+            std::thread::sleep(interval);
+        };
+
+        Ok(Self {
+            duration: started.elapsed(),
+            peak_rss,
+            status,
+            dxvk_device: None
+        })
+    }
+
+    #[cfg(feature = "dxvk")]
+    /// Take the GPU used by DXVK from the game's log, see `DxvkLog::find`
+    pub fn with_dxvk_log<T: AsRef<std::path::Path>, F: AsRef<std::path::Path>>(self, exe: T, log_folder: Option<F>) -> Self {
+        use crate::dxvk_log::DxvkLog;
+
+        let dxvk_device = DxvkLog::find(exe, log_folder).into_iter()
+            .filter_map(|path| DxvkLog::open(path).ok())
+            .find_map(|log| log.adapters.into_iter().next())
+            .map(|adapter| adapter.name);
+
+        Self {
+            dxvk_device,
+            ..self
+        }
+    }
+}
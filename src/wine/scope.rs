@@ -0,0 +1,160 @@
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+use super::*;
+
+/// Transient systemd scope (cgroup v2) wine process tree is placed into
+///
+/// Processes are started by `systemd-run --user --scope`, so scope limits apply to the whole tree,
+/// including the wineserver, and `stop` reliably terminates all of them
+///
+/// ```
+/// use wincompatlib::prelude::*;
+///
+/// let scope = SystemdScope::new("game-session")
+///     .with_memory_max(8 * 1024 * 1024 * 1024)
+///     .with_cpu_quota(400);
+///
+/// assert!(scope.args().contains(&String::from("MemoryMax=8589934592")));
+///
+/// let wine = Wine::default().with_scope(scope);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SystemdScope {
+    /// Unit name without `.scope` suffix
+    pub unit: String,
+
+    /// Hard memory limit in bytes (`MemoryMax`)
+    pub memory_max: Option<u64>,
+
+    /// Memory usage after which processes are throttled, in bytes (`MemoryHigh`)
+    pub memory_high: Option<u64>,
+
+    /// CPU time limit in percents of one core (`CPUQuota`), e.g. 200 for two cores
+    pub cpu_quota: Option<u32>,
+
+    /// Relative CPU share from 1 to 10000, 100 by default (`CPUWeight`)
+    pub cpu_weight: Option<u32>
+}
+
+impl SystemdScope {
+    pub fn new<T: Into<String>>(unit: T) -> Self {
+        Self {
+            unit: unit.into(),
+            memory_max: None,
+            memory_high: None,
+            cpu_quota: None,
+            cpu_weight: None
+        }
+    }
+
+    pub fn with_memory_max(self, bytes: u64) -> Self {
+        Self {
+            memory_max: Some(bytes),
+            ..self
+        }
+    }
+
+    pub fn with_memory_high(self, bytes: u64) -> Self {
+        Self {
+            memory_high: Some(bytes),
+            ..self
+        }
+    }
+
+    pub fn with_cpu_quota(self, percents: u32) -> Self {
+        Self {
+            cpu_quota: Some(percents),
+            ..self
+        }
+    }
+
+    pub fn with_cpu_weight(self, weight: u32) -> Self {
+        Self {
+            cpu_weight: Some(weight),
+            ..self
+        }
+    }
+
+    /// Full unit name, e.g. `game-session.scope`
+    pub fn unit_name(&self) -> String {
+        format!("{}.scope", self.unit)
+    }
+
+    /// Arguments of the `systemd-run` command placed before the wrapped program
+    pub fn args(&self) -> Vec<String> {
+        let mut args = vec![
+            String::from("--user"),
+            String::from("--scope"),
+            String::from("--quiet"),
+            String::from("--collect"),
+            format!("--unit={}", self.unit)
+        ];
+
+        let properties = [
+            ("MemoryMax", self.memory_max.map(|bytes| bytes.to_string())),
+            ("MemoryHigh", self.memory_high.map(|bytes| bytes.to_string())),
+            ("CPUQuota", self.cpu_quota.map(|percents| format!("{percents}%"))),
+            ("CPUWeight", self.cpu_weight.map(|weight| weight.to_string()))
+        ];
+
+        for (name, value) in properties {
+            if let Some(value) = value {
+                args.push(String::from("-p"));
+                args.push(format!("{name}={value}"));
+            }
+        }
+
+        args.push(String::from("--"));
+
+        args
+    }
+
+    fn systemctl(&self, args: &[&str]) -> Result<std::process::Output> {
+        Ok(Command::new("systemctl")
+            .arg("--user")
+            .args(args)
+            .arg(self.unit_name())
+            .stdin(Stdio::null())
+            .output()?)
+    }
+
+    /// Check if the scope has running processes
+    pub fn is_active(&self) -> bool {
+        self.systemctl(&["is-active", "--quiet"])
+            .map(|output| output.status.success())
+            .unwrap_or(false)
+    }
+
+    /// Get path to the cgroup of the scope, e.g. `/sys/fs/cgroup/user.slice/.../game-session.scope`
+    pub fn cgroup(&self) -> Option<PathBuf> {
+        let output = self.systemctl(&["show", "--property=ControlGroup", "--value"]).ok()?;
+
+        let cgroup = String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+        if cgroup.is_empty() {
+            return None;
+        }
+
+        Some(PathBuf::from("/sys/fs/cgroup").join(cgroup.trim_start_matches('/')))
+    }
+
+    /// Get ids of all the processes of the scope
+    pub fn processes(&self) -> Vec<u32> {
+        self.cgroup()
+            .and_then(|cgroup| std::fs::read_to_string(cgroup.join("cgroup.procs")).ok())
+            .map(|procs| procs.lines().filter_map(|pid| pid.parse().ok()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Terminate all the processes of the scope
+    pub fn stop(&self) -> Result<()> {
+        let output = self.systemctl(&["stop"])?;
+
+        if !output.status.success() {
+            return Err(Error::other(format!("Failed to stop {}: {}", self.unit_name(), String::from_utf8_lossy(&output.stderr).trim())));
+        }
+
+        Ok(())
+    }
+}
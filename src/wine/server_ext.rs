@@ -0,0 +1,150 @@
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use super::*;
+
+use crate::prefix::{is_server_running, server_process};
+
+/// Wineservers started by the library in the current process: prefix path -> wineserver binary
+static ACTIVE_SERVERS: Mutex<Option<HashMap<PathBuf, PathBuf>>> = Mutex::new(None);
+
+fn normalize(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Get wineservers started by the library in the current process, keyed by prefix path
+///
+/// ```
+/// use wincompatlib::prelude::*;
+///
+/// for (prefix, wineserver) in active_servers() {
+///     println!("{:?} is served by {:?}", prefix, wineserver);
+/// }
+/// ```
+pub fn active_servers() -> HashMap<PathBuf, PathBuf> {
+    ACTIVE_SERVERS.lock()
+        .map(|servers| servers.clone().unwrap_or_default())
+        .unwrap_or_default()
+}
+
+impl Wine {
+    /// Check that this struct's wineserver can be used for the prefix
+    ///
+    /// Returns `ErrorKind::ResourceBusy` error if the prefix is still served
+    /// by a wineserver of a different wine build started from this process,
+    /// because running different builds in the same prefix corrupts it
+    pub(crate) fn check_server(&self, prefix: &Path) -> Result<()> {
+        let prefix = normalize(prefix);
+        let wineserver = normalize(&self.wineserver());
+
+        let servers = ACTIVE_SERVERS.lock()
+            .map_err(|_| Error::other("Wineservers registry is poisoned"))?;
+
+        if let Some(active) = servers.as_ref().and_then(|servers| servers.get(&prefix)) {
+            if active != &wineserver && is_server_running(&prefix) {
+                return Err(Error::new(ErrorKind::ResourceBusy, format!(
+                    "Wine prefix {:?} is already used by another wine build with wineserver {:?}",
+                    prefix,
+                    active
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Start process in the prefix and remember that this struct's wineserver is used for it
+    ///
+    /// Wineserver is recorded only if `spawn` succeeds, see `check_server`
+    pub(crate) fn track_server<T>(&self, prefix: &Path, spawn: impl FnOnce() -> Result<T>) -> Result<T> {
+        self.check_server(prefix)?;
+
+        let result = spawn()?;
+
+        if let Ok(mut servers) = ACTIVE_SERVERS.lock() {
+            servers.get_or_insert_with(HashMap::new)
+                .insert(normalize(prefix), normalize(&self.wineserver()));
+        }
+
+        Ok(result)
+    }
+}
+
+pub trait WineServerExt {
+    fn wait_for_server_exit(&self, timeout: Duration) -> Result<bool>;
+    fn apply_server_priority(&self) -> Result<bool>;
+}
+
+impl WineServerExt for Wine {
+    /// Wait until all the processes in the prefix finish and wineserver exits. Runs `wineserver -w` command
+    ///
+    /// Returns `Ok(true)` if wineserver has exited, or `Ok(false)` if timeout was reached first
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// use std::time::Duration;
+    ///
+    /// let wine = Wine::default().with_prefix("/path/to/prefix");
+    ///
+    /// if wine.wait_for_server_exit(Duration::from_secs(30)).expect("Failed to wait for wineserver") {
+    ///     println!("Prefix is idle");
+    /// }
+    /// ```
+    fn wait_for_server_exit(&self, timeout: Duration) -> Result<bool> {
+        let mut child = self.events.spawn(Command::new(self.wineserver())
+            .arg("-w")
+            .envs(self.get_os_envs())
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null()))
+            .map_err(|err| Error::from_spawn(self.wineserver(), err))?;
+
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            if child.try_wait()?.is_some() {
+                return Ok(true);
+            }
+
+            if Instant::now() >= deadline {
+                // This is only a waiting client so killing it doesn't affect wineserver itself
+                child.kill()?;
+                child.wait()?;
+
+                return Ok(false);
+            }
+
+            std::thread::sleep(Duration::from_millis(100));
+        }
+    }
+
+    /// Apply nice value of the `ServerPriority` to the running wineserver of the prefix
+    ///
+    /// Returns `Ok(false)` if wineserver is not running
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// let wine = Wine::default()
+    ///     .with_prefix("/path/to/prefix")
+    ///     .with_server_priority(ServerPriority {
+    ///         nice: Some(-5),
+    ///         ..ServerPriority::default()
+    ///     });
+    ///
+    /// let game = wine.run("/path/to/game.exe").expect("Failed to run game");
+    ///
+    /// wine.apply_server_priority().expect("Failed to renice wineserver");
+    /// ```
+    fn apply_server_priority(&self) -> Result<bool> {
+        let Some(pid) = server_process(self.prefix_path()) else {
+            return Ok(false);
+        };
+
+        self.server_priority.renice(pid)?;
+
+        Ok(true)
+    }
+}
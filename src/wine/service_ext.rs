@@ -0,0 +1,163 @@
+use super::*;
+
+/// State of the windows service
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ServiceState {
+    Stopped,
+    StartPending,
+    StopPending,
+    Running,
+    ContinuePending,
+    PausePending,
+    Paused,
+
+    Unknown(u32)
+}
+
+impl From<u32> for ServiceState {
+    fn from(state: u32) -> Self {
+        match state {
+            1 => Self::Stopped,
+            2 => Self::StartPending,
+            3 => Self::StopPending,
+            4 => Self::Running,
+            5 => Self::ContinuePending,
+            6 => Self::PausePending,
+            7 => Self::Paused,
+
+            state => Self::Unknown(state)
+        }
+    }
+}
+
+/// Windows service installed in the prefix
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Service {
+    pub name: String,
+    pub display_name: Option<String>,
+    pub state: ServiceState
+}
+
+impl Service {
+    /// Parse output of the `sc query` command
+    ///
+    /// ```
+    /// use wincompatlib::prelude::*;
+    ///
+    /// let services = Service::parse_query("
+    /// SERVICE_NAME: EasyAntiCheat
+    /// DISPLAY_NAME: EasyAntiCheat
+    ///         TYPE               : 10  WIN32_OWN_PROCESS
+    ///         STATE              : 4  RUNNING
+    ///         WIN32_EXIT_CODE    : 0  (0x0)
+    /// ");
+    ///
+    /// assert_eq!(services[0].name, "EasyAntiCheat");
+    /// assert_eq!(services[0].state, ServiceState::Running);
+    /// ```
+    pub fn parse_query(output: &str) -> Vec<Self> {
+        let mut services: Vec<Self> = Vec::new();
+
+        for line in output.lines() {
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+
+            let value = value.trim();
+
+            match key.trim() {
+                "SERVICE_NAME" => services.push(Self {
+                    name: value.to_string(),
+                    display_name: None,
+                    state: ServiceState::Unknown(0)
+                }),
+
+                "DISPLAY_NAME" => if let Some(service) = services.last_mut() {
+                    service.display_name = Some(value.to_string());
+                }
+
+                "STATE" => if let Some(service) = services.last_mut() {
+                    let state = value.split_whitespace()
+                        .next()
+                        .and_then(|state| state.parse::<u32>().ok())
+                        .unwrap_or(0);
+
+                    service.state = ServiceState::from(state);
+                }
+
+                _ => ()
+            }
+        }
+
+        services
+    }
+}
+
+pub trait WineServiceExt {
+    fn services(&self) -> Result<Vec<Service>>;
+    fn service(&self, name: &str) -> Result<Option<Service>>;
+    fn start_service(&self, name: &str) -> Result<()>;
+    fn stop_service(&self, name: &str) -> Result<()>;
+}
+
+fn run_service_command(wine: &Wine, args: &[&str]) -> Result<Output> {
+    wine.run_args_output(args)
+}
+
+impl WineServiceExt for Wine {
+    /// List all the services installed in the prefix. Runs `sc query state= all` command
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// for service in Wine::default().with_prefix("/path/to/prefix").services().unwrap() {
+    ///     println!("{}: {:?}", service.name, service.state);
+    /// }
+    /// ```
+    fn services(&self) -> Result<Vec<Service>> {
+        let output = run_service_command(self, &["sc", "query", "state=", "all"])?;
+
+        match output.status.success() {
+            true  => Ok(Service::parse_query(&String::from_utf8_lossy(&output.stdout))),
+            false => Err(Error::other(format!("Failed to query services: {}", String::from_utf8_lossy(&output.stdout).trim())))
+        }
+    }
+
+    /// Get service by its name. Runs `sc query <name>` command
+    ///
+    /// Returns `Ok(None)` if service is not installed
+    fn service(&self, name: &str) -> Result<Option<Service>> {
+        let output = run_service_command(self, &["sc", "query", name])?;
+
+        Ok(Service::parse_query(&String::from_utf8_lossy(&output.stdout)).into_iter().next())
+    }
+
+    /// Start service. Runs `net start <name>` command
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// Wine::default()
+    ///     .with_prefix("/path/to/prefix")
+    ///     .start_service("EasyAntiCheat")
+    ///     .expect("Failed to start service");
+    /// ```
+    fn start_service(&self, name: &str) -> Result<()> {
+        let output = run_service_command(self, &["net", "start", name])?;
+
+        match output.status.success() {
+            true  => Ok(()),
+            false => Err(Error::other(format!("Failed to start service {name}: {}", String::from_utf8_lossy(&output.stdout).trim())))
+        }
+    }
+
+    /// Stop service. Runs `net stop <name>` command
+    fn stop_service(&self, name: &str) -> Result<()> {
+        let output = run_service_command(self, &["net", "stop", name])?;
+
+        match output.status.success() {
+            true  => Ok(()),
+            false => Err(Error::other(format!("Failed to stop service {name}: {}", String::from_utf8_lossy(&output.stdout).trim())))
+        }
+    }
+}
@@ -0,0 +1,186 @@
+use std::cmp::Ordering;
+use std::str::FromStr;
+
+use super::*;
+
+/// Build variant of wine
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum WineVariant {
+    #[default]
+    Vanilla,
+    Staging,
+    Tkg,
+
+    /// GloriousEggroll's builds, e.g. `GE-Proton8-26` or `lutris-GE-Proton8-26`
+    GE,
+
+    /// Valve's builds, e.g. `proton-8.0-3`
+    Proton
+}
+
+/// Version of the wine build parsed from the `wine --version` output
+///
+/// Versions are ordered by their numeric components with missing ones treated as zeros,
+/// so `8.0` and `8.0.0` are equal. Ordering doesn't take the variant into account
+/// unless the components are the same
+///
+/// ```
+/// use wincompatlib::prelude::*;
+///
+/// let version = WineVersion::parse("wine-8.0.2 (Staging)").unwrap();
+///
+/// assert_eq!(version.components, [8, 0, 2]);
+/// assert_eq!(version.variant, WineVariant::Staging);
+///
+/// assert!(version >= WineVersion::parse("wine-8.0").unwrap());
+/// assert!(version < WineVersion::parse("wine-9.1").unwrap());
+///
+/// let version = WineVersion::parse("GE-Proton8-26").unwrap();
+///
+/// assert_eq!(version.components, [8, 26]);
+/// assert_eq!(version.variant, WineVariant::GE);
+/// ```
+#[derive(Debug, Clone)]
+pub struct WineVersion {
+    /// Original version string
+    pub raw: String,
+
+    pub components: Vec<u64>,
+    pub variant: WineVariant
+}
+
+impl WineVersion {
+    /// Parse version string, e.g. `wine-9.1`, `wine-8.0.2 (Staging)` or `GE-Proton8-26`
+    ///
+    /// Returns `Error::VersionParse` if the string doesn't contain a version number
+    pub fn parse<T: AsRef<str>>(version: T) -> Result<Self> {
+        let raw = version.as_ref().trim();
+        let lowercase = raw.to_ascii_lowercase();
+
+        let variant = if lowercase.contains("ge-proton") || lowercase.contains("wine-ge") || lowercase.contains("lutris-ge") {
+            WineVariant::GE
+        } else if lowercase.contains("tkg") {
+            WineVariant::Tkg
+        } else if lowercase.contains("staging") {
+            WineVariant::Staging
+        } else if lowercase.contains("proton") {
+            WineVariant::Proton
+        } else {
+            WineVariant::Vanilla
+        };
+
+        let Some(start) = raw.find(|char: char| char.is_ascii_digit()) else {
+            return Err(Error::VersionParse(raw.to_string()));
+        };
+
+        // Components are separated by dots (8.0.2) or dashes (GE-Proton8-26),
+        // everything after them (-rc1, (Staging)) is ignored
+        let version = &raw[start..];
+
+        let end = version.find(|char: char| !char.is_ascii_digit() && char != '.' && char != '-')
+            .unwrap_or(version.len());
+
+        let components = version[..end].split(['.', '-'])
+            .map_while(|component| component.parse().ok())
+            .collect();
+
+        Ok(Self {
+            raw: raw.to_string(),
+            components,
+            variant
+        })
+    }
+
+    #[inline]
+    pub fn major(&self) -> u64 {
+        self.components.first().copied().unwrap_or_default()
+    }
+
+    #[inline]
+    pub fn minor(&self) -> u64 {
+        self.components.get(1).copied().unwrap_or_default()
+    }
+
+    /// Check if version is the same or newer than the given components
+    ///
+    /// ```
+    /// use wincompatlib::prelude::*;
+    ///
+    /// let version = WineVersion::parse("wine-8.0.2").unwrap();
+    ///
+    /// assert!(version.is_at_least(&[8]));
+    /// assert!(version.is_at_least(&[8, 0, 2]));
+    /// assert!(!version.is_at_least(&[8, 1]));
+    /// ```
+    pub fn is_at_least(&self, components: &[u64]) -> bool {
+        compare_components(&self.components, components) != Ordering::Less
+    }
+}
+
+/// Compare version components treating missing ones as zeros
+fn compare_components(a: &[u64], b: &[u64]) -> Ordering {
+    for i in 0..a.len().max(b.len()) {
+        let a = a.get(i).copied().unwrap_or_default();
+        let b = b.get(i).copied().unwrap_or_default();
+
+        match a.cmp(&b) {
+            Ordering::Equal => continue,
+            ordering => return ordering
+        }
+    }
+
+    Ordering::Equal
+}
+
+impl PartialEq for WineVersion {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for WineVersion {}
+
+impl PartialOrd for WineVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for WineVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        compare_components(&self.components, &other.components)
+            .then(self.variant.cmp(&other.variant))
+    }
+}
+
+impl FromStr for WineVersion {
+    type Err = Error;
+
+    #[inline]
+    fn from_str(version: &str) -> Result<Self> {
+        Self::parse(version)
+    }
+}
+
+impl std::fmt::Display for WineVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.raw)
+    }
+}
+
+impl Wine {
+    /// Get parsed version of the wine binary, see `Wine::version`
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// let version = Wine::default().wine_version().expect("Failed to get wine version");
+    ///
+    /// if !version.is_at_least(&[8]) {
+    ///     eprintln!("Wine 8.0 or newer is required, found {version}");
+    /// }
+    /// ```
+    pub fn wine_version(&self) -> Result<WineVersion> {
+        WineVersion::parse(self.version()?.to_string_lossy())
+    }
+}
@@ -0,0 +1,148 @@
+use std::ffi::OsString;
+
+/// Direct3D feature level reported by VKD3D, specified by `VKD3D_FEATURE_LEVEL` variable
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Vkd3dFeatureLevel {
+    Level11_0,
+    Level11_1,
+    Level12_0,
+    Level12_1,
+    Level12_2
+}
+
+impl Vkd3dFeatureLevel {
+    pub fn to_str(&self) -> &str {
+        match self {
+            Self::Level11_0 => "11_0",
+            Self::Level11_1 => "11_1",
+            Self::Level12_0 => "12_0",
+            Self::Level12_1 => "12_1",
+            Self::Level12_2 => "12_2"
+        }
+    }
+}
+
+/// Verbosity of VKD3D logs, specified by `VKD3D_DEBUG` and `VKD3D_SHADER_DEBUG` variables
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Vkd3dDebugLevel {
+    None,
+    Err,
+    Fixme,
+    Warn,
+    Info,
+    Trace
+}
+
+impl Vkd3dDebugLevel {
+    pub fn to_str(&self) -> &str {
+        match self {
+            Self::None  => "none",
+            Self::Err   => "err",
+            Self::Fixme => "fixme",
+            Self::Warn  => "warn",
+            Self::Info  => "info",
+            Self::Trace => "trace"
+        }
+    }
+}
+
+/// Option of the `VKD3D_CONFIG` variable
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Vkd3dOption {
+    /// Enable DXR (raytracing) support
+    Dxr,
+
+    /// Enable DXR 1.1 support
+    Dxr11,
+
+    /// Disable DXR even if it's supported by the driver
+    NoDxr,
+
+    /// Use single queue for all the commands, workaround for some driver issues
+    SingleQueue,
+
+    /// Disable host visible video memory usage for uploads
+    NoUploadHvv,
+
+    /// Use static descriptors for constant buffers, workaround for some games
+    ForceStaticCbv,
+
+    /// Log pipeline library usage
+    PipelineLibraryLog,
+
+    /// Option not listed here
+    Custom(String)
+}
+
+impl Vkd3dOption {
+    pub fn to_str(&self) -> &str {
+        match self {
+            Self::Dxr                => "dxr",
+            Self::Dxr11              => "dxr11",
+            Self::NoDxr              => "nodxr",
+            Self::SingleQueue        => "single_queue",
+            Self::NoUploadHvv        => "no_upload_hvv",
+            Self::ForceStaticCbv     => "force_static_cbv",
+            Self::PipelineLibraryLog => "pipeline_library_log",
+            Self::Custom(option)     => option
+        }
+    }
+}
+
+/// D3D12 translation settings of VKD3D
+///
+/// ```
+/// use wincompatlib::prelude::*;
+///
+/// let wine = Wine::from_binary("wine")
+///     .with_vkd3d(Vkd3d {
+///         config: vec![Vkd3dOption::Dxr11, Vkd3dOption::SingleQueue],
+///         feature_level: Some(Vkd3dFeatureLevel::Level12_1),
+///         ..Vkd3d::default()
+///     });
+///
+/// assert_eq!(wine.get_envs()["VKD3D_CONFIG"], "dxr11,single_queue");
+/// assert_eq!(wine.get_envs()["VKD3D_FEATURE_LEVEL"], "12_1");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Vkd3d {
+    /// Options of the `VKD3D_CONFIG` variable
+    pub config: Vec<Vkd3dOption>,
+
+    pub feature_level: Option<Vkd3dFeatureLevel>,
+
+    /// Specifies `VKD3D_DEBUG` variable, overriding the one set by `LogLevel`
+    pub debug: Option<Vkd3dDebugLevel>,
+
+    /// Specifies `VKD3D_SHADER_DEBUG` variable, overriding the one set by `LogLevel`
+    pub shader_debug: Option<Vkd3dDebugLevel>
+}
+
+impl Vkd3d {
+    pub fn get_envs(&self) -> Vec<(&'static str, OsString)> {
+        let mut envs = Vec::new();
+
+        if !self.config.is_empty() {
+            let config = self.config.iter()
+                .map(Vkd3dOption::to_str)
+                .collect::<Vec<_>>()
+                .join(",");
+
+            envs.push(("VKD3D_CONFIG", OsString::from(config)));
+        }
+
+        if let Some(level) = self.feature_level {
+            envs.push(("VKD3D_FEATURE_LEVEL", OsString::from(level.to_str())));
+        }
+
+        if let Some(level) = self.debug {
+            envs.push(("VKD3D_DEBUG", OsString::from(level.to_str())));
+        }
+
+        if let Some(level) = self.shader_debug {
+            envs.push(("VKD3D_SHADER_DEBUG", OsString::from(level.to_str())));
+        }
+
+        envs
+    }
+}
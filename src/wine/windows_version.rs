@@ -0,0 +1,77 @@
+use std::path::Path;
+
+use super::*;
+
+use crate::prefix::RegFile;
+
+/// Windows version reported by wine to the applications
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum WindowsVersion {
+    Win98,
+    Win2000,
+    WinXP,
+    WinXP64,
+    Vista,
+    Win7,
+    Win8,
+    Win81,
+    Win10,
+    Win11
+}
+
+impl WindowsVersion {
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(version: &str) -> Option<Self> {
+        match version {
+            "win98"   => Some(Self::Win98),
+            "win2k"   => Some(Self::Win2000),
+            "winxp"   => Some(Self::WinXP),
+            "winxp64" => Some(Self::WinXP64),
+            "vista"   => Some(Self::Vista),
+            "win7"    => Some(Self::Win7),
+            "win8"    => Some(Self::Win8),
+            "win81"   => Some(Self::Win81),
+            "win10"   => Some(Self::Win10),
+            "win11"   => Some(Self::Win11),
+
+            _ => None
+        }
+    }
+
+    /// Get version name used by `winecfg`
+    pub fn to_str(&self) -> &str {
+        match self {
+            Self::Win98   => "win98",
+            Self::Win2000 => "win2k",
+            Self::WinXP   => "winxp",
+            Self::WinXP64 => "winxp64",
+            Self::Vista   => "vista",
+            Self::Win7    => "win7",
+            Self::Win8    => "win8",
+            Self::Win81   => "win81",
+            Self::Win10   => "win10",
+            Self::Win11   => "win11"
+        }
+    }
+
+    /// Read windows version configured for the whole prefix
+    ///
+    /// Returns `Ok(None)` if version is not set explicitly, so wine's default one is used
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// match WindowsVersion::from_prefix("/path/to/prefix") {
+    ///     Ok(Some(version)) => println!("Windows version: {}", version.to_str()),
+    ///     Ok(None) => println!("Default windows version is used"),
+    ///     Err(err) => eprintln!("Failed to read prefix registry: {err}")
+    /// }
+    /// ```
+    pub fn from_prefix<T: AsRef<Path>>(prefix: T) -> Result<Option<Self>> {
+        let registry = RegFile::open(prefix.as_ref().join("user.reg"))?;
+
+        Ok(registry.key("Software\\Wine")
+            .and_then(|key| key.string("Version"))
+            .and_then(Self::from_str))
+    }
+}
@@ -0,0 +1,205 @@
+/// Class of the wine debug messages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum WineDebugClass {
+    Err,
+    Warn,
+    Fixme,
+    Trace
+}
+
+impl WineDebugClass {
+    pub fn to_str(&self) -> &str {
+        match self {
+            Self::Err   => "err",
+            Self::Warn  => "warn",
+            Self::Fixme => "fixme",
+            Self::Trace => "trace"
+        }
+    }
+}
+
+/// Wine debug channel
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum WineDebugChannel {
+    /// Every channel
+    All,
+
+    /// Prefix messages with time, process and thread ids
+    Timestamp,
+    Pid,
+    Tid,
+
+    /// Exceptions
+    Seh,
+
+    /// Loaded libraries
+    LoadDll,
+    Module,
+
+    /// Every call of the wine functions. Very slow
+    Relay,
+
+    /// Full strings instead of truncated ones
+    Debugstr,
+
+    D3d,
+    Dxgi,
+    Vulkan,
+    Heap,
+    File,
+    Reg,
+    Dinput,
+    Hid,
+    Winhttp,
+
+    /// Channel not listed here
+    Custom(String)
+}
+
+impl WineDebugChannel {
+    pub fn to_str(&self) -> &str {
+        match self {
+            Self::All             => "all",
+            Self::Timestamp       => "timestamp",
+            Self::Pid             => "pid",
+            Self::Tid             => "tid",
+            Self::Seh             => "seh",
+            Self::LoadDll         => "loaddll",
+            Self::Module          => "module",
+            Self::Relay           => "relay",
+            Self::Debugstr        => "debugstr",
+            Self::D3d             => "d3d",
+            Self::Dxgi            => "dxgi",
+            Self::Vulkan          => "vulkan",
+            Self::Heap            => "heap",
+            Self::File            => "file",
+            Self::Reg             => "reg",
+            Self::Dinput          => "dinput",
+            Self::Hid             => "hid",
+            Self::Winhttp         => "winhttp",
+            Self::Custom(channel) => channel
+        }
+    }
+}
+
+impl From<&str> for WineDebugChannel {
+    fn from(channel: &str) -> Self {
+        [
+            Self::All, Self::Timestamp, Self::Pid, Self::Tid, Self::Seh, Self::LoadDll,
+            Self::Module, Self::Relay, Self::Debugstr, Self::D3d, Self::Dxgi, Self::Vulkan,
+            Self::Heap, Self::File, Self::Reg, Self::Dinput, Self::Hid, Self::Winhttp
+        ].into_iter()
+            .find(|known| known.to_str() == channel)
+            .unwrap_or_else(|| Self::Custom(channel.to_string()))
+    }
+}
+
+/// Single `[class]+channel` or `[class]-channel` item of the `WINEDEBUG` variable
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WineDebugRule {
+    /// Affected class, all of them if not specified
+    pub class: Option<WineDebugClass>,
+
+    pub channel: WineDebugChannel,
+    pub enabled: bool
+}
+
+impl std::fmt::Display for WineDebugRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let class = self.class.as_ref()
+            .map(WineDebugClass::to_str)
+            .unwrap_or_default();
+
+        let sign = if self.enabled { '+' } else { '-' };
+
+        write!(f, "{class}{sign}{}", self.channel.to_str())
+    }
+}
+
+/// Channels of the wine debug output, specified by `WINEDEBUG` variable.
+/// Rules are applied by wine in the given order
+///
+/// ```
+/// use wincompatlib::prelude::*;
+///
+/// let debug = WineDebug::new()
+///     .disable_class(WineDebugClass::Fixme, WineDebugChannel::All)
+///     .enable(WineDebugChannel::D3d)
+///     .enable("mscoree");
+///
+/// assert_eq!(debug.to_string(), "fixme-all,+d3d,+mscoree");
+///
+/// let wine = Wine::from_binary("wine")
+///     .with_wine_debug(debug);
+///
+/// assert_eq!(wine.get_envs()["WINEDEBUG"], "fixme-all,+d3d,+mscoree");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct WineDebug {
+    pub rules: Vec<WineDebugRule>
+}
+
+impl WineDebug {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Disable all the messages (`-all`)
+    pub fn silent() -> Self {
+        Self::new().disable(WineDebugChannel::All)
+    }
+
+    /// Enable every channel with timestamps, process and thread ids. Very slow
+    pub fn full_trace() -> Self {
+        Self::new()
+            .enable(WineDebugChannel::Timestamp)
+            .enable(WineDebugChannel::Pid)
+            .enable(WineDebugChannel::Tid)
+            .enable(WineDebugChannel::All)
+    }
+
+    pub fn with_rule(mut self, class: Option<WineDebugClass>, channel: impl Into<WineDebugChannel>, enabled: bool) -> Self {
+        self.rules.push(WineDebugRule {
+            class,
+            channel: channel.into(),
+            enabled
+        });
+
+        self
+    }
+
+    /// Enable all the classes of the channel (`+channel`)
+    #[inline]
+    pub fn enable(self, channel: impl Into<WineDebugChannel>) -> Self {
+        self.with_rule(None, channel, true)
+    }
+
+    /// Disable all the classes of the channel (`-channel`)
+    #[inline]
+    pub fn disable(self, channel: impl Into<WineDebugChannel>) -> Self {
+        self.with_rule(None, channel, false)
+    }
+
+    /// Enable class of the channel (`class+channel`)
+    #[inline]
+    pub fn enable_class(self, class: WineDebugClass, channel: impl Into<WineDebugChannel>) -> Self {
+        self.with_rule(Some(class), channel, true)
+    }
+
+    /// Disable class of the channel (`class-channel`)
+    #[inline]
+    pub fn disable_class(self, class: WineDebugClass, channel: impl Into<WineDebugChannel>) -> Self {
+        self.with_rule(Some(class), channel, false)
+    }
+}
+
+impl std::fmt::Display for WineDebug {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let rules = self.rules.iter()
+            .map(WineDebugRule::to_string)
+            .collect::<Vec<_>>();
+
+        write!(f, "{}", rules.join(","))
+    }
+}
@@ -1,13 +1,44 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use super::*;
 
+use crate::events::EventSink;
+
 pub trait WineWithExt {
     fn with_prefix<T: Into<PathBuf>>(self, prefix: T) -> Self;
     fn with_arch(self, arch: WineArch) -> Self;
     fn with_boot<T: Into<PathBuf>>(self, boot: T) -> Self;
     fn with_server<T: Into<PathBuf>>(self, server: T) -> Self;
     fn with_loader(self, loader: WineLoader) -> Self;
+    fn with_log_level(self, log_level: LogLevel) -> Self;
+    fn with_wine_debug(self, debug: WineDebug) -> Self;
+    fn with_limits(self, limits: Limits) -> Self;
+    fn with_oom_score_adj(self, score: i32) -> Self;
+    fn with_server_priority(self, priority: ServerPriority) -> Self;
+    fn with_codepage(self, codepage: Codepage) -> Self;
+    fn with_locale<T: Into<String>>(self, locale: T) -> Self;
+    fn with_locale_fix<T: AsRef<Path>>(self, path: T) -> Self;
+    fn with_overlay(self, overlay: Overlay, enabled: bool) -> Self;
+    fn with_vkbasalt<T: Into<PathBuf>>(self, config: T) -> Self;
+    fn with_hdr(self, hdr: bool) -> Self;
+    fn with_dll_override<T: AsRef<str>>(self, dll: T, mode: OverrideMode) -> Self;
+    fn with_wrapper(self, wrapper: Wrapper) -> Self;
+    fn with_headless(self, headless: Headless) -> Self;
+    fn with_scope(self, scope: SystemdScope) -> Self;
+    fn with_network(self, network: NetworkMode) -> Self;
+    fn with_proxy<T: Into<String>>(self, proxy: T) -> Self;
+    fn with_timezone<T: Into<String>>(self, timezone: T) -> Self;
+    fn with_faketime<T: Into<String>>(self, faketime: T) -> Self;
+    fn with_controller(self, controller: Controller) -> Self;
+    fn with_portable_runtime(self, portable: bool) -> Self;
+    fn with_vkd3d(self, vkd3d: Vkd3d) -> Self;
+    fn with_missing_addons(self, missing_addons: MissingAddons) -> Self;
+    fn with_unattended(self, unattended: bool) -> Self;
+    fn with_read_only(self, read_only: bool) -> Self;
+    fn with_env<K: Into<OsString>, V: Into<OsString>>(self, key: K, value: V) -> Self;
+    fn with_event_sink<T: EventSink + 'static>(self, sink: T) -> Self;
+    fn with_secret<T: Into<OsString>>(self, secret: T) -> Self;
+    fn with_secret_env<T: Into<OsString>>(self, key: T) -> Self;
 }
 
 impl WineWithExt for Wine {
@@ -85,4 +116,521 @@ impl WineWithExt for Wine {
             ..self
         }
     }
+
+    /// Set verbosity of wine, DXVK, VKD3D and proton logs
+    /// 
+    /// ```
+    /// use wincompatlib::prelude::*;
+    /// 
+    /// let wine = Wine::from_binary("wine")
+    ///     .with_log_level(LogLevel::Silent);
+    /// 
+    /// assert_eq!(wine.get_envs()["WINEDEBUG"], "-all");
+    /// ```
+    fn with_log_level(self, log_level: LogLevel) -> Self {
+        Self {
+            log_level: Some(log_level),
+            ..self
+        }
+    }
+
+    /// Set wine debug channels, overriding `WINEDEBUG` variable of the log level
+    /// 
+    /// ```
+    /// use wincompatlib::prelude::*;
+    /// 
+    /// let wine = Wine::from_binary("wine")
+    ///     .with_log_level(LogLevel::Normal)
+    ///     .with_wine_debug(WineDebug::silent().enable(WineDebugChannel::LoadDll));
+    /// 
+    /// assert_eq!(wine.get_envs()["WINEDEBUG"], "-all,+loaddll");
+    /// assert_eq!(wine.get_envs()["DXVK_LOG_LEVEL"], "warn");
+    /// ```
+    fn with_wine_debug(self, debug: WineDebug) -> Self {
+        Self {
+            wine_debug: Some(debug),
+            ..self
+        }
+    }
+
+    /// Set resource limits of the spawned processes
+    /// 
+    /// ```
+    /// use wincompatlib::prelude::*;
+    /// 
+    /// // Enable core dumps to debug crashes
+    /// let wine = Wine::from_binary("wine")
+    ///     .with_limits(Limits {
+    ///         core_dump_size: Some(Limits::UNLIMITED),
+    ///         ..Limits::default()
+    ///     });
+    /// ```
+    fn with_limits(self, limits: Limits) -> Self {
+        Self {
+            limits,
+            ..self
+        }
+    }
+
+    /// Set OOM killer score adjustment of the spawned processes, from -1000 to 1000
+    /// 
+    /// Positive values make the game killed first under memory pressure instead of the launcher
+    /// or the desktop session. Negative values protect it, but values lower than the current one
+    /// require `CAP_SYS_RESOURCE`, otherwise processes fail to start
+    /// 
+    /// ```
+    /// use wincompatlib::prelude::*;
+    /// 
+    /// let wine = Wine::from_binary("wine")
+    ///     .with_oom_score_adj(500);
+    /// ```
+    fn with_oom_score_adj(self, score: i32) -> Self {
+        Self {
+            oom_score_adj: Some(score),
+            ..self
+        }
+    }
+
+    /// Set scheduling settings of the wineserver
+    /// 
+    /// ```
+    /// use wincompatlib::prelude::*;
+    /// 
+    /// let wine = Wine::from_binary("wine")
+    ///     .with_server_priority(ServerPriority {
+    ///         nice: Some(-5),
+    ///         rt_server: Some(90),
+    ///         rt_base: Some(15)
+    ///     });
+    /// ```
+    fn with_server_priority(self, server_priority: ServerPriority) -> Self {
+        Self {
+            server_priority,
+            ..self
+        }
+    }
+
+    /// Run applications with given codepage by setting `LC_ALL` variable
+    /// 
+    /// ANSI and OEM codepages are stored in the prefix's registry by `update_prefix`,
+    /// use `WineRegExt::set_codepage` to store them in already created prefix
+    /// 
+    /// ```
+    /// use wincompatlib::prelude::*;
+    /// 
+    /// let wine = Wine::from_binary("wine")
+    ///     .with_codepage(Codepage::Japanese);
+    /// 
+    /// assert_eq!(wine.get_envs()["LC_ALL"], "ja_JP.UTF-8");
+    /// ```
+    fn with_codepage(self, codepage: Codepage) -> Self {
+        Self {
+            codepage: Some(codepage),
+            ..self
+        }
+    }
+
+    /// Run applications with given locale by setting `LANG` and `LC_CTYPE` variables
+    /// 
+    /// See `Wine::check_locale` to find out if the locale breaks game's paths
+    /// 
+    /// ```
+    /// use wincompatlib::prelude::*;
+    /// 
+    /// let wine = Wine::from_binary("wine")
+    ///     .with_locale("de_DE.UTF-8");
+    /// 
+    /// assert_eq!(wine.get_envs()["LANG"], "de_DE.UTF-8");
+    /// ```
+    fn with_locale<T: Into<String>>(self, locale: T) -> Self {
+        Self {
+            locale: Some(locale.into()),
+            ..self
+        }
+    }
+
+    /// Switch to the suggested UTF-8 locale if the path would be broken by the current one
+    /// 
+    /// Locale specified by the host's `LC_ALL` variable can't be overridden this way,
+    /// use `with_codepage` in this case
+    /// 
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    /// 
+    /// let game = "/path/to/ゲーム/game.exe";
+    /// 
+    /// Wine::default()
+    ///     .with_locale_fix(game)
+    ///     .run(game)
+    ///     .expect("Failed to run game");
+    /// ```
+    fn with_locale_fix<T: AsRef<Path>>(self, path: T) -> Self {
+        match self.check_locale(path) {
+            Some(issue) if issue.variable != "LC_ALL" => self.with_locale(issue.suggested),
+            _ => self
+        }
+    }
+
+    /// Enable or disable third-party overlay, as they frequently crash wine games
+    /// 
+    /// ```
+    /// use wincompatlib::prelude::*;
+    /// 
+    /// let wine = Wine::from_binary("wine")
+    ///     .with_overlay(Overlay::Steam, false)
+    ///     .with_overlay(Overlay::MangoHud, true);
+    /// 
+    /// assert_eq!(wine.get_envs()["MANGOHUD"], "1");
+    /// ```
+    fn with_overlay(mut self, overlay: Overlay, enabled: bool) -> Self {
+        self.overlays.insert(overlay, enabled);
+
+        self
+    }
+
+    /// Enable vkBasalt layer and make it use given config file
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// VkBasalt::default()
+    ///     .with_effect("cas")
+    ///     .save("/path/to/vkBasalt.conf")
+    ///     .expect("Failed to save vkBasalt config");
+    ///
+    /// let wine = Wine::default().with_vkbasalt("/path/to/vkBasalt.conf");
+    /// ```
+    fn with_vkbasalt<T: Into<PathBuf>>(mut self, config: T) -> Self {
+        self.overlays.insert(Overlay::VkBasalt, true);
+        self.vkbasalt_config = Some(config.into());
+
+        self
+    }
+
+    /// Enable HDR output in DXVK and proton, and in gamescope if it's used as a wrapper
+    /// 
+    /// ```
+    /// use wincompatlib::prelude::*;
+    /// 
+    /// let wine = Wine::from_binary("wine")
+    ///     .with_hdr(true);
+    /// 
+    /// assert_eq!(wine.get_envs()["DXVK_HDR"], "1");
+    /// ```
+    fn with_hdr(self, hdr: bool) -> Self {
+        Self {
+            hdr,
+            ..self
+        }
+    }
+
+    /// Override library for the processes started by this struct using `WINEDLLOVERRIDES` variable
+    /// 
+    /// Use `WineRegExt::set_dll_override` to store it in the prefix's registry instead
+    /// 
+    /// ```
+    /// use wincompatlib::prelude::*;
+    /// 
+    /// let wine = Wine::from_binary("wine")
+    ///     .with_dll_override("d3d11", OverrideMode::Native)
+    ///     .with_dll_override("dxgi", OverrideMode::Native)
+    ///     .with_dll_override("winemenubuilder.exe", OverrideMode::Disabled);
+    /// 
+    /// assert_eq!(wine.get_envs()["WINEDLLOVERRIDES"], "d3d11,dxgi=native;winemenubuilder.exe=");
+    /// ```
+    fn with_dll_override<T: AsRef<str>>(mut self, dll: T, mode: OverrideMode) -> Self {
+        self.dll_overrides.insert(dll, mode);
+
+        self
+    }
+
+    /// Run wine through the wrapper program. Wrappers added later are run by the previous ones
+    /// 
+    /// ```
+    /// use wincompatlib::prelude::*;
+    /// 
+    /// let wine = Wine::from_binary("wine")
+    ///     .with_wrapper(Wrapper::new("gamemoderun"))
+    ///     .with_wrapper(Wrapper::new("gamescope").with_args(["-f", "--"]));
+    /// ```
+    fn with_wrapper(mut self, wrapper: Wrapper) -> Self {
+        self.wrappers.push(wrapper);
+
+        self
+    }
+
+    /// Run commands in the virtual display
+    /// 
+    /// Set `headless` field to `None` to use the real display again
+    /// 
+    /// ```
+    /// use wincompatlib::prelude::*;
+    /// 
+    /// let mut wine = Wine::default();
+    /// 
+    /// // Use virtual display only if there's no real one
+    /// if Headless::is_needed() {
+    ///     if let Some(headless) = Headless::detect() {
+    ///         wine = wine.with_headless(headless);
+    ///     }
+    /// }
+    /// ```
+    fn with_headless(self, headless: Headless) -> Self {
+        Self {
+            headless: Some(headless),
+            ..self
+        }
+    }
+
+    /// Place processes started by this struct into the transient systemd scope
+    /// 
+    /// Set `scope` field to `None` to run processes directly again
+    /// 
+    /// ```
+    /// use wincompatlib::prelude::*;
+    /// 
+    /// let wine = Wine::from_binary("wine")
+    ///     .with_scope(SystemdScope::new("game-session").with_memory_max(8 << 30));
+    /// ```
+    fn with_scope(self, scope: SystemdScope) -> Self {
+        Self {
+            scope: Some(scope),
+            ..self
+        }
+    }
+
+    /// Block network for the processes started by this struct, see `NetworkMode`
+    /// 
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    /// 
+    /// let game = Wine::from_binary("wine")
+    ///     .with_prefix("/path/to/prefix")
+    ///     .with_network(NetworkMode::Offline)
+    ///     .run("/path/to/game.exe")
+    ///     .expect("Failed to run game");
+    /// ```
+    fn with_network(self, network: NetworkMode) -> Self {
+        Self {
+            network,
+            ..self
+        }
+    }
+
+    /// Run applications with given HTTP and HTTPS proxy by setting `http_proxy` and `https_proxy` variables
+    /// 
+    /// The proxy is stored in the prefix's WinInet settings by `update_prefix`,
+    /// use `WineRegExt::set_proxy` to store it in already created prefix.
+    /// Credentials of the proxy URL are registered as secrets (see `with_secret`)
+    /// 
+    /// ```
+    /// use wincompatlib::prelude::*;
+    /// 
+    /// let wine = Wine::from_binary("wine")
+    ///     .with_proxy("http://proxy.example.com:3128");
+    /// 
+    /// assert_eq!(wine.get_envs()["https_proxy"], "http://proxy.example.com:3128");
+    /// ```
+    fn with_proxy<T: Into<String>>(mut self, proxy: T) -> Self {
+        let proxy = proxy.into();
+
+        if let Some(userinfo) = super::reg_ext::proxy_userinfo(&proxy) {
+            self.events.add_secret(userinfo);
+
+            // Password can be printed without the user name
+            if let Some((_, password)) = userinfo.split_once(':').filter(|(_, password)| !password.is_empty()) {
+                self.events.add_secret(password);
+            }
+        }
+
+        Self {
+            proxy: Some(proxy),
+            ..self
+        }
+    }
+
+    /// Run applications in given time zone by setting `TZ` variable
+    /// 
+    /// ```
+    /// use wincompatlib::prelude::*;
+    /// 
+    /// let wine = Wine::from_binary("wine")
+    ///     .with_timezone("Asia/Tokyo");
+    /// 
+    /// assert_eq!(wine.get_envs()["TZ"], "Asia/Tokyo");
+    /// ```
+    fn with_timezone<T: Into<String>>(self, timezone: T) -> Self {
+        Self {
+            timezone: Some(timezone.into()),
+            ..self
+        }
+    }
+
+    /// Run wine under the `faketime` program (libfaketime) with given time specification,
+    /// e.g. `@2024-01-01 12:00:00` to start the clock at this time or `+2d` to shift it.
+    /// The wrapper is placed right before the wine binary, so other wrappers keep the real time.
+    /// Set `faketime` field to `None` to use the real time again
+    /// 
+    /// ```
+    /// use wincompatlib::prelude::*;
+    /// 
+    /// let wine = Wine::from_binary("wine")
+    ///     .with_faketime("@2024-01-01 12:00:00");
+    /// ```
+    fn with_faketime<T: Into<String>>(self, faketime: T) -> Self {
+        Self {
+            faketime: Some(faketime.into()),
+            ..self
+        }
+    }
+
+    /// Set controllers detection settings, see `Controller`
+    fn with_controller(self, controller: Controller) -> Self {
+        Self {
+            controller,
+            ..self
+        }
+    }
+
+    /// Make self-contained wine build use libraries from its own `lib` folders
+    /// 
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    /// 
+    /// let wine = Wine::from_binary("/path/to/wine-build/bin/wine")
+    ///     .with_portable_runtime(true);
+    /// 
+    /// println!("Libraries path: {:?}", wine.get_envs().get("LD_LIBRARY_PATH"));
+    /// ```
+    fn with_portable_runtime(self, portable: bool) -> Self {
+        Self {
+            portable,
+            ..self
+        }
+    }
+
+    /// Set D3D12 translation settings, see `Vkd3d`
+    fn with_vkd3d(self, vkd3d: Vkd3d) -> Self {
+        Self {
+            vkd3d,
+            ..self
+        }
+    }
+
+    /// Set what to do with Gecko and Mono addons missing when the prefix is created
+    /// 
+    /// ```
+    /// use wincompatlib::prelude::*;
+    /// 
+    /// // Never block prefix creation on the installation dialog
+    /// let wine = Wine::default().with_missing_addons(MissingAddons::Skip);
+    /// ```
+    fn with_missing_addons(self, missing_addons: MissingAddons) -> Self {
+        Self {
+            missing_addons,
+            ..self
+        }
+    }
+
+    /// Prepare prefixes and run installers without showing any wine dialogs,
+    /// e.g. on the server
+    /// 
+    /// ```
+    /// use wincompatlib::prelude::*;
+    /// 
+    /// let wine = Wine::default().with_unattended(true);
+    /// ```
+    fn with_unattended(self, unattended: bool) -> Self {
+        Self {
+            unattended,
+            ..self
+        }
+    }
+
+    /// Fail with `Error::ReadOnlyPrefix` instead of modifying the wine prefix
+    /// 
+    /// ```
+    /// use wincompatlib::prelude::*;
+    /// 
+    /// let wine = Wine::from_binary("wine")
+    ///     .with_prefix("/path/to/golden/prefix")
+    ///     .with_read_only(true);
+    /// 
+    /// assert!(wine.set_dll_override("d3d11", OverrideMode::Native).is_err());
+    /// ```
+    fn with_read_only(self, read_only: bool) -> Self {
+        Self {
+            read_only,
+            ..self
+        }
+    }
+
+    /// Add custom environment variable. It overrides the variable generated from other settings
+    /// 
+    /// ```
+    /// use wincompatlib::prelude::*;
+    /// 
+    /// let wine = Wine::from_binary("wine")
+    ///     .with_log_level(LogLevel::Silent)
+    ///     .with_env("DXVK_HUD", "fps")
+    ///     .with_env("WINEDEBUG", "+loaddll");
+    /// 
+    /// assert_eq!(wine.get_envs()["DXVK_HUD"], "fps");
+    /// assert_eq!(wine.get_envs()["WINEDEBUG"], "+loaddll");
+    /// ```
+    fn with_env<K: Into<OsString>, V: Into<OsString>>(mut self, key: K, value: V) -> Self {
+        self.env.insert(key.into(), value.into());
+
+        self
+    }
+
+    /// Subscribe event sink to everything this struct does
+    /// 
+    /// ```
+    /// use wincompatlib::prelude::*;
+    /// 
+    /// struct Logger;
+    /// 
+    /// impl EventSink for Logger {
+    ///     fn on_command_start(&self, command: &CommandInfo) {
+    ///         println!("Running {command}");
+    ///     }
+    /// }
+    /// 
+    /// let wine = Wine::from_binary("wine")
+    ///     .with_event_sink(Logger);
+    /// ```
+    fn with_event_sink<T: EventSink + 'static>(mut self, sink: T) -> Self {
+        self.events.subscribe(sink);
+
+        self
+    }
+
+    /// Hide given value (e.g. auth token passed to a launcher) from the commands and output reported to event sinks
+    /// 
+    /// ```
+    /// use wincompatlib::prelude::*;
+    /// 
+    /// let wine = Wine::from_binary("wine")
+    ///     .with_secret("my-auth-token");
+    /// ```
+    fn with_secret<T: Into<OsString>>(mut self, secret: T) -> Self {
+        self.events.add_secret(secret);
+
+        self
+    }
+
+    /// Hide value of the given environment variable from the commands reported to event sinks
+    /// 
+    /// ```
+    /// use wincompatlib::prelude::*;
+    /// 
+    /// let wine = Wine::from_binary("wine")
+    ///     .with_secret_env("LAUNCHER_AUTH_TOKEN");
+    /// ```
+    fn with_secret_env<T: Into<OsString>>(mut self, key: T) -> Self {
+        self.events.add_secret_env(key);
+
+        self
+    }
 }
@@ -0,0 +1,126 @@
+use std::path::{Path, PathBuf};
+use crate::error::Result;
+
+use super::*;
+
+/// Problem with 32 bit programs support in the win64 prefix
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Wow64Warning {
+    /// Wine build was compiled without 32 bit support,
+    /// so 32 bit programs (and `x32` DXVK libraries) won't work
+    Unsupported,
+
+    /// Wine build supports 32 bit programs but the test program failed to run
+    Broken {
+        output: String
+    }
+}
+
+impl std::fmt::Display for Wow64Warning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unsupported => write!(f, "Wine build doesn't support 32 bit programs"),
+            Self::Broken { output } => write!(f, "Failed to run 32 bit program in the wine prefix: {output}")
+        }
+    }
+}
+
+/// Find wine binary in `PATH` if only its name is given
+pub(crate) fn resolve_binary(binary: &Path) -> Option<PathBuf> {
+    if binary.parent().map(|parent| !parent.as_os_str().is_empty()).unwrap_or(false) {
+        return Some(binary.to_path_buf());
+    }
+
+    std::env::split_paths(&std::env::var_os("PATH")?)
+        .map(|folder| folder.join(binary))
+        .find(|path| path.exists())
+}
+
+impl Wine {
+    /// Check if wine build contains 32 bit libraries
+    ///
+    /// Returns `None` if libraries folder of the build wasn't found
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// if Wine::from_binary("/path/to/wine").has_32bit_support() == Some(false) {
+    ///     println!("32 bit games won't work with this build");
+    /// }
+    /// ```
+    pub fn has_32bit_support(&self) -> Option<bool> {
+        let root = resolve_binary(&self.binary)?
+            .parent()?
+            .parent()?
+            .to_path_buf();
+
+        let mut found = false;
+
+        for lib in ["lib", "lib32", "lib64"] {
+            let wine = root.join(lib).join("wine");
+
+            // Wine 5.7+ puts libraries into arch specific folders
+            if wine.join("i386-windows").is_dir() || wine.join("i386-unix").is_dir() {
+                return Some(true);
+            }
+
+            found |= wine.is_dir();
+        }
+
+        // Older builds store 32 bit libraries in lib32, or in lib if lib64 is present
+        let old_layout = root.join("lib32/wine").is_dir() || (root.join("lib/wine").is_dir() && root.join("lib64/wine").is_dir());
+
+        found.then_some(old_layout)
+    }
+
+    /// Verify that 32 bit programs work in the win64 prefix by running builtin
+    /// `cmd.exe` from the `syswow64` folder
+    ///
+    /// Found problem is reported as a warning to the event sinks and returned.
+    /// Win32 prefixes are not checked
+    ///
+    /// ```no_run
+    /// use wincompatlib::prelude::*;
+    ///
+    /// let wine = Wine::from_binary("/path/to/wine")
+    ///     .with_prefix("/path/to/prefix");
+    ///
+    /// if let Some(warning) = wine.check_wow64().expect("Failed to check prefix") {
+    ///     eprintln!("{warning}");
+    /// }
+    /// ```
+    pub fn check_wow64(&self) -> Result<Option<Wow64Warning>> {
+        let prefix = self.prefix_path();
+
+        let arch = WineArch::from_prefix(&prefix)?
+            .or(self.arch)
+            .unwrap_or(WineArch::Win64);
+
+        if arch == WineArch::Win32 {
+            return Ok(None);
+        }
+
+        let warning = if self.has_32bit_support() == Some(false) {
+            Some(Wow64Warning::Unsupported)
+        } else {
+            let output = self.run_args(["C:\\windows\\syswow64\\cmd.exe", "/c", "exit 32"])?
+                .wait_with_output()?;
+
+            self.events.process_output(&output);
+
+            match output.status.code() {
+                Some(32) => None,
+
+                _ => Some(Wow64Warning::Broken {
+                    output: String::from_utf8_lossy(&output.stderr).trim().to_string()
+                })
+            }
+        };
+
+        if let Some(warning) = &warning {
+            self.events.warning(warning.clone());
+        }
+
+        Ok(warning)
+    }
+}
@@ -0,0 +1,42 @@
+use std::ffi::{OsStr, OsString};
+use std::path::PathBuf;
+
+/// Program which runs wine as its own child process, e.g. `gamescope` or `gamemoderun`
+///
+/// Arguments are placed between the wrapper program and wine binary,
+/// so they should include separator if the wrapper expects one
+///
+/// ```
+/// use wincompatlib::prelude::*;
+///
+/// let wrapper = Wrapper::new("gamescope").with_args(["-W", "2560", "-H", "1440", "--"]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Wrapper {
+    pub program: PathBuf,
+    pub args: Vec<OsString>
+}
+
+impl Wrapper {
+    pub fn new<T: Into<PathBuf>>(program: T) -> Self {
+        Self {
+            program: program.into(),
+            args: Vec::new()
+        }
+    }
+
+    pub fn with_args<T, S>(mut self, args: T) -> Self
+    where
+        T: IntoIterator<Item = S>,
+        S: AsRef<OsStr>
+    {
+        self.args.extend(args.into_iter().map(|arg| arg.as_ref().to_os_string()));
+
+        self
+    }
+
+    /// Check if wrapper runs given program
+    pub fn is(&self, program: &str) -> bool {
+        self.program.file_name() == Some(OsStr::new(program))
+    }
+}
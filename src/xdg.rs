@@ -0,0 +1,22 @@
+use std::path::PathBuf;
+
+/// Get user's home folder: `$HOME`
+pub fn home() -> PathBuf {
+    PathBuf::from(std::env::var_os("HOME").unwrap_or_default())
+}
+
+/// Get user's data folder: `$XDG_DATA_HOME` or `$HOME/.local/share`
+pub fn data_home() -> PathBuf {
+    match std::env::var_os("XDG_DATA_HOME") {
+        Some(path) if !path.is_empty() => PathBuf::from(path),
+        _ => home().join(".local/share")
+    }
+}
+
+/// Get user's config folder: `$XDG_CONFIG_HOME` or `$HOME/.config`
+pub fn config_home() -> PathBuf {
+    match std::env::var_os("XDG_CONFIG_HOME") {
+        Some(path) if !path.is_empty() => PathBuf::from(path),
+        _ => home().join(".config")
+    }
+}